@@ -1,10 +1,10 @@
 //! Utility functions and extension traits that depend on generated bindings
 
-use gdnative_core::core_types::NodePath;
+use gdnative_core::core_types::{NodePath, StringName};
 use gdnative_core::export::NativeClass;
 use gdnative_core::object::{SubClass, TInstance, TRef};
 
-use super::generated::{Engine, Node, SceneTree};
+use super::generated::{Engine, Input, InputEvent, Node, SceneTree};
 
 /// Convenience method  to obtain a reference to an "auto-load" node, that is a child of the root
 /// node.
@@ -77,3 +77,126 @@ impl<N: SubClass<Node>, P: Into<NodePath>> NodeResolveExt<P> for N {
         self.upcast().get_node(path)?.assume_safe().cast()
     }
 }
+
+/// An input action defined in the project settings.
+///
+/// This is usually implemented by the `Action` enum that the `gdnative-build` crate generates
+/// from `project.godot`, and used with [`InputActionExt`] and [`InputEventActionExt`].
+pub trait InputAction: Copy + 'static {
+    /// All actions of this type.
+    const ALL: &'static [Self];
+
+    /// Returns the name of the action.
+    fn as_str(self) -> &'static str;
+
+    /// Returns the name of the action as a `StringName`, which should be created only once.
+    fn string_name(self) -> &'static StringName;
+}
+
+/// Convenience methods to query the state of [`InputAction`]s.
+pub trait InputActionExt {
+    /// Returns `true` if `action` is pressed. See [`Input::is_action_pressed`].
+    fn is_pressed<A: InputAction>(&self, action: A) -> bool;
+
+    /// Returns `true` if `action` was pressed in the current frame. See
+    /// [`Input::is_action_just_pressed`].
+    fn is_just_pressed<A: InputAction>(&self, action: A) -> bool;
+
+    /// Returns `true` if `action` was released in the current frame. See
+    /// [`Input::is_action_just_released`].
+    fn is_just_released<A: InputAction>(&self, action: A) -> bool;
+
+    /// Returns the strength of `action`, between 0 and 1. See [`Input::get_action_strength`].
+    fn strength<A: InputAction>(&self, action: A) -> f64;
+
+    /// Simulates pressing `action` with full strength. See [`Input::action_press`].
+    fn press<A: InputAction>(&self, action: A);
+
+    /// Simulates releasing `action`. See [`Input::action_release`].
+    fn release<A: InputAction>(&self, action: A);
+}
+
+impl InputActionExt for Input {
+    #[inline]
+    fn is_pressed<A: InputAction>(&self, action: A) -> bool {
+        self.is_action_pressed(action.string_name(), false)
+    }
+
+    #[inline]
+    fn is_just_pressed<A: InputAction>(&self, action: A) -> bool {
+        self.is_action_just_pressed(action.string_name(), false)
+    }
+
+    #[inline]
+    fn is_just_released<A: InputAction>(&self, action: A) -> bool {
+        self.is_action_just_released(action.string_name(), false)
+    }
+
+    #[inline]
+    fn strength<A: InputAction>(&self, action: A) -> f64 {
+        self.get_action_strength(action.string_name(), false)
+    }
+
+    #[inline]
+    fn press<A: InputAction>(&self, action: A) {
+        self.action_press(action.string_name(), 1.0)
+    }
+
+    #[inline]
+    fn release<A: InputAction>(&self, action: A) {
+        self.action_release(action.string_name())
+    }
+}
+
+/// Convenience methods to find the [`InputAction`]s that match input events.
+pub trait InputEventActionExt {
+    /// Returns the first action of type `A` that matches the event, if any. See
+    /// [`InputEvent::is_action`].
+    fn matching_action<A: InputAction>(&self) -> Option<A>;
+
+    /// Returns the first action of type `A` that is pressed by the event, if any. Echo events
+    /// are ignored. See [`InputEvent::is_action_pressed`].
+    fn pressed_action<A: InputAction>(&self) -> Option<A>;
+
+    /// Returns the first action of type `A` that is released by the event, if any. See
+    /// [`InputEvent::is_action_released`].
+    fn released_action<A: InputAction>(&self) -> Option<A>;
+}
+
+impl<E: SubClass<InputEvent>> InputEventActionExt for E {
+    #[inline]
+    fn matching_action<A: InputAction>(&self) -> Option<A> {
+        find_action(self.upcast::<InputEvent>(), |event, action| {
+            event.is_action(action.string_name(), false)
+        })
+    }
+
+    #[inline]
+    fn pressed_action<A: InputAction>(&self) -> Option<A> {
+        find_action(self.upcast::<InputEvent>(), |event, action| {
+            event.is_action_pressed(action.string_name(), false, false)
+        })
+    }
+
+    #[inline]
+    fn released_action<A: InputAction>(&self) -> Option<A> {
+        find_action(self.upcast::<InputEvent>(), |event, action| {
+            event.is_action_released(action.string_name(), false)
+        })
+    }
+}
+
+/// Returns the first action of type `A` that matches `event` according to `pred`.
+///
+/// Every check converts the name of the action to a `GodotString`, so events that can't be
+/// assigned to actions, like mouse motion, are rejected up front.
+fn find_action<A: InputAction>(
+    event: &InputEvent,
+    mut pred: impl FnMut(&InputEvent, A) -> bool,
+) -> Option<A> {
+    if !event.is_action_type() {
+        return None;
+    }
+
+    A::ALL.iter().copied().find(|&action| pred(event, action))
+}
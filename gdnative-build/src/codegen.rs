@@ -0,0 +1,572 @@
+//! Generation of Rust source code from `project.godot`.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use crate::project::{header_attribute, Entry, ProjectFile};
+
+/// Input actions that exist in every Godot 3 project, even if they are not listed in
+/// `project.godot`.
+pub(crate) const BUILTIN_ACTIONS: &[&str] = &[
+    "ui_accept",
+    "ui_select",
+    "ui_cancel",
+    "ui_focus_next",
+    "ui_focus_prev",
+    "ui_left",
+    "ui_right",
+    "ui_up",
+    "ui_down",
+    "ui_page_up",
+    "ui_page_down",
+    "ui_home",
+    "ui_end",
+];
+
+pub(crate) struct Options<'a> {
+    pub builtin_actions: bool,
+    /// Returns the contents of a `res://` file, or `None` if it can't be read.
+    pub read_resource: &'a dyn Fn(&str) -> Option<String>,
+}
+
+/// Generates the source code for a parsed project file.
+pub(crate) fn generate(file: &ProjectFile, options: &Options) -> Result<String, String> {
+    let actions = actions(file, options)?;
+    let autoloads = autoloads(file, options)?;
+
+    let mut out = String::new();
+    out.push_str("// Generated by gdnative-build from project.godot. Do not edit.\n\n");
+    write_actions(&mut out, &actions);
+    out.push('\n');
+    write_autoloads(&mut out, &autoloads);
+
+    Ok(out)
+}
+
+struct Action {
+    name: String,
+    variant: String,
+}
+
+fn actions(file: &ProjectFile, options: &Options) -> Result<Vec<Action>, String> {
+    let mut names = Vec::new();
+    if options.builtin_actions {
+        names.extend(BUILTIN_ACTIONS.iter().map(|&name| (name, None)));
+    }
+    for entry in file.section("input") {
+        if !names.iter().any(|&(name, _)| name == entry.key) {
+            names.push((&entry.key, Some(entry)));
+        }
+    }
+
+    let mut used = HashMap::new();
+    names
+        .into_iter()
+        .map(|(name, entry)| {
+            let variant = pascal_case(name)
+                .ok_or_else(|| error(entry, format!("cannot name a variant after `{}`", name)))?;
+            if let Some(other) = used.insert(variant.clone(), name) {
+                return Err(error(
+                    entry,
+                    format!(
+                        "input actions `{}` and `{}` would both be named `{}`",
+                        other, name, variant
+                    ),
+                ));
+            }
+
+            Ok(Action {
+                name: name.to_owned(),
+                variant,
+            })
+        })
+        .collect()
+}
+
+struct Autoload {
+    name: String,
+    path: String,
+    function: String,
+    class: Option<String>,
+}
+
+fn autoloads(file: &ProjectFile, options: &Options) -> Result<Vec<Autoload>, String> {
+    let mut used = HashMap::new();
+    file.section("autoload")
+        .map(|entry| {
+            let value = entry.string_value().ok_or_else(|| {
+                error(
+                    Some(entry),
+                    format!("expected a path for the autoload `{}`", entry.key),
+                )
+            })?;
+            // A leading `*` marks autoloads that are also global variables in scripts.
+            let path = value.strip_prefix('*').unwrap_or(&value).to_owned();
+
+            let function = snake_case(&entry.key).ok_or_else(|| {
+                error(
+                    Some(entry),
+                    format!("cannot name a function after `{}`", entry.key),
+                )
+            })?;
+            if let Some(other) = used.insert(function.clone(), &entry.key) {
+                return Err(error(
+                    Some(entry),
+                    format!(
+                        "autoloads `{}` and `{}` would both be named `{}`",
+                        other, entry.key, function
+                    ),
+                ));
+            }
+
+            let class = (options.read_resource)(&path)
+                .and_then(|source| autoload_class(file, &path, &source));
+
+            Ok(Autoload {
+                name: entry.key.clone(),
+                path,
+                function: raw_ident(function),
+                class,
+            })
+        })
+        .collect()
+}
+
+/// Returns the class of an autoload node: the type of the root node of a scene, or the class
+/// that a script extends. Returns `None` if the class can't be told from the file alone, like
+/// for inherited scenes or scripts that extend other scripts.
+fn autoload_class(file: &ProjectFile, path: &str, source: &str) -> Option<String> {
+    if path.ends_with(".tscn") {
+        // The root node is the only one without a parent.
+        let scene = ProjectFile::parse(source).ok()?;
+        let root = scene
+            .section_names()
+            .filter(|header| header.starts_with("node "))
+            .find(|header| header_attribute(header, "parent").is_none())?;
+        return header_attribute(root, "type");
+    }
+
+    if path.ends_with(".gd") {
+        let class = source.lines().find_map(|line| {
+            let line = line.split('#').next().unwrap_or_default();
+            line.trim().strip_prefix("extends ").map(str::trim)
+        })?;
+
+        let is_ident = !class.starts_with(|c: char| c.is_ascii_digit())
+            && class.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        // Classes declared with `class_name` are listed in the project, and are not engine
+        // classes.
+        let is_script_class = file
+            .section("")
+            .filter(|entry| entry.key == "_global_script_classes")
+            .any(|entry| entry.value.contains(&format!("\"class\": \"{}\"", class)));
+
+        return (is_ident && !class.is_empty() && !is_script_class).then(|| class.to_owned());
+    }
+
+    None
+}
+
+fn error(entry: Option<&Entry>, message: String) -> String {
+    match entry {
+        Some(entry) => format!("{}: {}", entry.line, message),
+        None => message,
+    }
+}
+
+fn write_actions(out: &mut String, actions: &[Action]) {
+    out.push_str(
+        "/// Input actions of the Godot project.
+///
+/// Actions can be passed to all engine methods that take action names, or used with the
+/// `InputActionExt` and `InputEventActionExt` traits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[allow(dead_code)]
+pub enum Action {\n",
+    );
+    for action in actions {
+        let _ = writeln!(out, "    /// The `{}` action.", action.name);
+        let _ = writeln!(out, "    {},", action.variant);
+    }
+    out.push_str("}\n\n#[allow(dead_code)]\nimpl Action {\n");
+
+    out.push_str(
+        "    /// All input actions, in the order of the project settings.
+    pub const ALL: &'static [Action] = &[\n",
+    );
+    for action in actions {
+        let _ = writeln!(out, "        Action::{},", action.variant);
+    }
+    out.push_str("    ];\n\n");
+
+    out.push_str(
+        "    /// Returns the name of the action.
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {\n",
+    );
+    for action in actions {
+        let _ = writeln!(
+            out,
+            "            Action::{} => {:?},",
+            action.variant, action.name
+        );
+    }
+    out.push_str("        }\n    }\n\n");
+
+    out.push_str(
+        "    /// Returns the name of the action as a `StringName`, which is created only once.
+    #[inline]
+    pub fn string_name(self) -> &'static ::gdnative::core_types::StringName {
+        match self {\n",
+    );
+    for action in actions {
+        let _ = writeln!(
+            out,
+            "            Action::{} => ::gdnative::string_name!({:?}),",
+            action.variant, action.name
+        );
+    }
+    out.push_str("        }\n    }\n\n");
+
+    out.push_str(
+        "    /// Returns the action with the given name, if any.
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL
+            .iter()
+            .copied()
+            .find(|action| action.as_str() == name)
+    }
+}
+
+",
+    );
+
+    out.push_str(
+        "impl ::gdnative::api::utils::InputAction for Action {
+    const ALL: &'static [Action] = Action::ALL;
+
+    #[inline]
+    fn as_str(self) -> &'static str {
+        Action::as_str(self)
+    }
+
+    #[inline]
+    fn string_name(self) -> &'static ::gdnative::core_types::StringName {
+        Action::string_name(self)
+    }
+}
+
+impl ::std::convert::AsRef<str> for Action {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl ::std::fmt::Display for Action {
+    #[inline]
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+",
+    );
+}
+
+fn write_autoloads(out: &mut String, autoloads: &[Autoload]) {
+    out.push_str(
+        "/// Autoload nodes of the Godot project.\n#[allow(dead_code)]\npub mod autoload {",
+    );
+    for autoload in autoloads {
+        if let Some(class) = &autoload.class {
+            let _ = write!(
+                out,
+                "
+    /// Returns the `{name}` autoload (`{path}`), which is a `{class}`.
+    ///
+    /// Returns `None` if the node does not exist or is not a `{class}`.
+    ///
+    /// # Safety
+    ///
+    /// See [`autoload`](gdnative::api::utils::autoload).
+    #[inline]
+    pub unsafe fn {function}<'a>(
+    ) -> ::std::option::Option<::gdnative::object::TRef<'a, ::gdnative::api::{class}>> {{
+        ::gdnative::api::utils::autoload({name:?})
+    }}
+",
+                name = autoload.name,
+                path = autoload.path,
+                function = autoload.function,
+                class = class,
+            );
+            continue;
+        }
+
+        let _ = write!(
+            out,
+            "
+    /// Returns the `{name}` autoload (`{path}`), cast to `T`.
+    ///
+    /// The class of the node can't be told from `{path}`, so it is chosen by the caller.
+    /// Returns `None` if the node does not exist or is not of the correct type.
+    ///
+    /// # Safety
+    ///
+    /// See [`autoload`](gdnative::api::utils::autoload).
+    #[inline]
+    pub unsafe fn {function}<'a, T>() -> ::std::option::Option<::gdnative::object::TRef<'a, T>>
+    where
+        T: ::gdnative::object::SubClass<::gdnative::api::Node>,
+    {{
+        ::gdnative::api::utils::autoload({name:?})
+    }}
+",
+            name = autoload.name,
+            path = autoload.path,
+            function = autoload.function,
+        );
+    }
+    out.push_str("}\n");
+}
+
+/// Converts a name to `PascalCase`, using ASCII letters and digits as words.
+fn pascal_case(name: &str) -> Option<String> {
+    let mut ident = String::new();
+    for word in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            ident.push(first.to_ascii_uppercase());
+            ident.extend(chars);
+        }
+    }
+
+    if ident.is_empty() {
+        return None;
+    }
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if ident == "Self" {
+        ident.push('_');
+    }
+    Some(ident)
+}
+
+/// Converts a name to `snake_case`, starting new words at case changes.
+fn snake_case(name: &str) -> Option<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut ident = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !ident.is_empty() && !ident.ends_with('_') {
+                ident.push('_');
+            }
+            continue;
+        }
+
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next = chars.get(i + 1).copied().unwrap_or('_');
+            let new_word = prev.is_ascii_lowercase()
+                || ((prev.is_ascii_uppercase() || prev.is_ascii_digit())
+                    && next.is_ascii_lowercase());
+            if new_word && !ident.is_empty() && !ident.ends_with('_') {
+                ident.push('_');
+            }
+        }
+        ident.push(c.to_ascii_lowercase());
+    }
+
+    while ident.ends_with('_') {
+        ident.pop();
+    }
+    if ident.is_empty() {
+        return None;
+    }
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    Some(ident)
+}
+
+/// Makes a `snake_case` name usable as an identifier, even if it is a keyword.
+fn raw_ident(ident: String) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+        "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+        "virtual", "where", "while", "yield",
+    ];
+
+    match ident.as_str() {
+        "self" | "super" | "crate" => ident + "_",
+        _ if KEYWORDS.contains(&ident.as_str()) => format!("r#{}", ident),
+        _ => ident,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_source(source: &str, builtin_actions: bool) -> Result<String, String> {
+        let file = ProjectFile::parse(source).unwrap();
+        let options = Options {
+            builtin_actions,
+            read_resource: &|_| None,
+        };
+        generate(&file, &options)
+    }
+
+    #[test]
+    fn pascal_case_names() {
+        assert_eq!(Some("Jump".into()), pascal_case("jump"));
+        assert_eq!(Some("UiAccept".into()), pascal_case("ui_accept"));
+        assert_eq!(Some("FireWeapon".into()), pascal_case("fire weapon"));
+        assert_eq!(Some("MoveLeft".into()), pascal_case("moveLeft"));
+        assert_eq!(Some("_2ndSlot".into()), pascal_case("2nd-slot"));
+        assert_eq!(Some("Self_".into()), pascal_case("self"));
+        assert_eq!(None, pascal_case("__"));
+    }
+
+    #[test]
+    fn snake_case_names() {
+        assert_eq!(Some("global".into()), snake_case("Global"));
+        assert_eq!(Some("hud".into()), snake_case("HUD"));
+        assert_eq!(Some("hud_layer".into()), snake_case("HUDLayer"));
+        assert_eq!(Some("player_data".into()), snake_case("PlayerData"));
+        assert_eq!(Some("music_2".into()), snake_case("Music 2"));
+        assert_eq!(Some("level2_boss".into()), snake_case("Level2Boss"));
+        assert_eq!(Some("_3d_sound".into()), snake_case("3DSound"));
+        assert_eq!(None, snake_case("-"));
+
+        assert_eq!("r#type", raw_ident("type".into()));
+        assert_eq!("self_", raw_ident("self".into()));
+        assert_eq!("events", raw_ident("events".into()));
+    }
+
+    #[test]
+    fn generates_actions() {
+        let source = generate_source(
+            "[input]\n\njump={\n\"deadzone\": 0.5,\n\"events\": [  ]\n}\n\
+             ui_accept={}\n\"fire weapon\"={}\n",
+            true,
+        )
+        .unwrap();
+
+        let variants = source
+            .lines()
+            .skip_while(|line| !line.starts_with("pub enum Action"))
+            .skip(1)
+            .take_while(|line| *line != "}")
+            .filter(|line| !line.trim_start().starts_with("///"))
+            .map(str::trim)
+            .collect::<Vec<_>>();
+        assert_eq!(BUILTIN_ACTIONS.len() + 2, variants.len());
+        assert_eq!("UiAccept,", variants[0]);
+        assert_eq!(["Jump,", "FireWeapon,"], variants[BUILTIN_ACTIONS.len()..]);
+
+        assert!(source.contains("            Action::FireWeapon => \"fire weapon\",\n"));
+        assert!(source.contains(
+            "            Action::FireWeapon => ::gdnative::string_name!(\"fire weapon\"),\n"
+        ));
+
+        let source = generate_source("[input]\njump={}\n", false).unwrap();
+        assert!(!source.contains("UiAccept"));
+        assert!(source.contains(
+            "    pub const ALL: &'static [Action] = &[\n        Action::Jump,\n    ];\n"
+        ));
+
+        let source = generate_source("", false).unwrap();
+        assert!(source.contains("pub enum Action {\n}\n"));
+    }
+
+    #[test]
+    fn generates_autoloads() {
+        let source = generate_source(
+            "[autoload]\nGlobal=\"*res://global.gd\"\nHUD=\"res://ui/hud.tscn\"\n",
+            false,
+        )
+        .unwrap();
+
+        assert!(source
+            .contains("    /// Returns the `Global` autoload (`res://global.gd`), cast to `T`.\n"));
+        assert!(source.contains("    pub unsafe fn global<'a, T>()"));
+        assert!(source.contains("        ::gdnative::api::utils::autoload(\"Global\")\n"));
+        assert!(source.contains("    pub unsafe fn hud<'a, T>()"));
+    }
+
+    #[test]
+    fn infers_autoload_classes() {
+        let file = ProjectFile::parse(
+            "_global_script_classes=[ {\n\"base\": \"Node\",\n\"class\": \"Stats\",\n\
+             \"path\": \"res://stats.gd\"\n} ]\n\n[autoload]\n\
+             Global=\"*res://global.gd\"\nHUD=\"res://ui/hud.tscn\"\nMenu=\"res://menu.tscn\"\n\
+             Player=\"res://player.gd\"\nMusic=\"res://music.gd\"\nMissing=\"res://missing.gd\"\n",
+        )
+        .unwrap();
+        let read_resource = |path: &str| {
+            let source = match path {
+                "res://global.gd" => "tool\nextends Node # Global state\n",
+                "res://ui/hud.tscn" => {
+                    "[gd_scene format=2]\n\n[node name=\"Bar\" type=\"ProgressBar\" parent=\".\"]\n\
+                     [node name=\"HUD\" type=\"CanvasLayer\"]\n"
+                }
+                // Inherited scene
+                "res://menu.tscn" => {
+                    "[ext_resource path=\"res://base.tscn\" type=\"PackedScene\" id=1]\n\
+                     [node name=\"Menu\" instance=ExtResource( 1 )]\n"
+                }
+                "res://player.gd" => "extends Stats\n",
+                "res://music.gd" => "extends \"res://sound.gd\"\n",
+                _ => return None,
+            };
+            Some(source.to_owned())
+        };
+        let options = Options {
+            builtin_actions: false,
+            read_resource: &read_resource,
+        };
+        let source = generate(&file, &options).unwrap();
+
+        assert!(source.contains(
+            "    pub unsafe fn global<'a>(\n    ) -> ::std::option::Option<::gdnative::object::TRef<'a, ::gdnative::api::Node>> {\n"
+        ));
+        assert!(source
+            .contains("    /// Returns `None` if the node does not exist or is not a `Node`.\n"));
+        assert!(source.contains("TRef<'a, ::gdnative::api::CanvasLayer>> {\n"));
+        assert!(!source.contains("ProgressBar"));
+        for function in ["menu", "player", "music", "missing"] {
+            assert!(source.contains(&format!("    pub unsafe fn {}<'a, T>()", function)));
+        }
+    }
+
+    #[test]
+    fn rejects_name_collisions() {
+        assert_eq!(
+            Err(
+                "3: input actions `move_left` and `MoveLeft` would both be named `MoveLeft`".into()
+            ),
+            generate_source("[input]\nmove_left={}\nMoveLeft={}\n", false)
+        );
+        assert_eq!(
+            Err("2: cannot name a variant after `--`".into()),
+            generate_source("[input]\n--={}\n", false)
+        );
+        assert_eq!(
+            Err("3: autoloads `Hud` and `HUD` would both be named `hud`".into()),
+            generate_source(
+                "[autoload]\nHud=\"res://a.gd\"\nHUD=\"res://b.gd\"\n",
+                false
+            )
+        );
+        assert_eq!(
+            Err("2: expected a path for the autoload `Hud`".into()),
+            generate_source("[autoload]\nHud=null\n", false)
+        );
+    }
+}
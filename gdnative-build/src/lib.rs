@@ -0,0 +1,173 @@
+//! Build script helpers for godot-rust.
+//!
+//! This crate generates Rust code from the files of a Godot project, so that mistakes like
+//! misspelled input action names are caught at compile time. It does not depend on the engine
+//! or the other godot-rust crates, and is meant to be used as a build dependency:
+//!
+//! ```toml
+//! [build-dependencies]
+//! gdnative-build = "0.11"
+//! ```
+//!
+//! # Project settings
+//!
+//! [`ProjectBindings`] reads `project.godot`, and generates:
+//!
+//! - An `Action` enum with a variant for each input action, including the `ui_*` actions
+//!   that are built into the engine. Actions can be passed to all engine methods that take
+//!   action names, and used with the [`InputActionExt`][input-ext] and
+//!   [`InputEventActionExt`][input-event-ext] extension traits.
+//! - An `autoload` module with a getter for each autoload node, built on
+//!   [`autoload`][autoload]. The getters return the class of the root node for scenes, and
+//!   the class that the script extends for GDScript files. Where the class can't be told from
+//!   the file, like for inherited scenes, NativeScripts, or scripts that extend other scripts,
+//!   the getter is generic and the caller chooses the class.
+//!
+//! In the `main` function of `build.rs`:
+//!
+//! ```no_run
+//! gdnative_build::ProjectBindings::new("../godot")
+//!     .write_to_out_dir("project.rs")
+//!     .expect("project.godot should be valid");
+//! ```
+//!
+//! And in the crate:
+//!
+//! ```ignore
+//! use gdnative::prelude::*;
+//!
+//! include!(concat!(env!("OUT_DIR"), "/project.rs"));
+//!
+//! fn update(input: &Input) {
+//!     if input.is_pressed(Action::Jump) {
+//!         // ...
+//!     }
+//!
+//!     // The root node of `hud.tscn` is a `CanvasLayer`.
+//!     let hud = unsafe { autoload::hud() };
+//! }
+//! ```
+//!
+//! [input-ext]: https://docs.rs/gdnative/latest/gdnative/api/utils/trait.InputActionExt.html
+//! [input-event-ext]: https://docs.rs/gdnative/latest/gdnative/api/utils/trait.InputEventActionExt.html
+//! [autoload]: https://docs.rs/gdnative/latest/gdnative/api/utils/fn.autoload.html
+
+mod codegen;
+mod project;
+
+use std::cell::RefCell;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Generates Rust code from the `project.godot` file of a Godot project.
+///
+/// The generated code refers to the `gdnative` crate by the absolute path `::gdnative`, so it
+/// must be a dependency of the crate that includes it, under that exact name. Crates that
+/// rename the dependency in `Cargo.toml` can't use the generated code.
+#[derive(Clone, Debug)]
+pub struct ProjectBindings {
+    path: PathBuf,
+    builtin_actions: bool,
+}
+
+impl ProjectBindings {
+    /// Creates a generator for a Godot project. `path` is either the directory of the project,
+    /// or the path to its `project.godot` file. Relative paths are relative to the crate
+    /// directory when used from a build script.
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let mut path = path.into();
+        if path.is_dir() {
+            path.push("project.godot");
+        }
+
+        ProjectBindings {
+            path,
+            builtin_actions: true,
+        }
+    }
+
+    /// Sets whether the `ui_*` input actions that are built into the engine are included in
+    /// the `Action` enum, even if they are not listed in `project.godot`. Enabled by default.
+    #[inline]
+    pub fn builtin_actions(mut self, builtin_actions: bool) -> Self {
+        self.builtin_actions = builtin_actions;
+        self
+    }
+
+    /// Returns the path of the `project.godot` file.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads `project.godot` and returns the generated code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not a valid project file, or if
+    /// several input actions or autoloads have names that map to the same Rust name. The
+    /// messages of errors in the file contain the line number.
+    pub fn generate(&self) -> io::Result<String> {
+        self.generate_with_resources().map(|(code, _)| code)
+    }
+
+    /// Generates the code, and returns it along with the paths of the autoload scenes and
+    /// scripts that were read.
+    fn generate_with_resources(&self) -> io::Result<(String, Vec<PathBuf>)> {
+        let source = std::fs::read_to_string(&self.path)?;
+        let file = project::ProjectFile::parse(&source).map_err(|err| self.error(err))?;
+
+        let project_dir = self.path.parent().unwrap_or_else(|| Path::new(""));
+        let resources = RefCell::new(Vec::new());
+        let read_resource = |path: &str| {
+            let path = project_dir.join(path.strip_prefix("res://")?);
+            let source = std::fs::read_to_string(&path).ok()?;
+            resources.borrow_mut().push(path);
+            Some(source)
+        };
+
+        let options = codegen::Options {
+            builtin_actions: self.builtin_actions,
+            read_resource: &read_resource,
+        };
+        let code = codegen::generate(&file, &options).map_err(|err| self.error(err))?;
+        Ok((code, resources.into_inner()))
+    }
+
+    /// Writes the generated code to a file in `OUT_DIR`, and tells Cargo to run the build
+    /// script again when `project.godot` or the files of the autoloads change. Meant to be
+    /// called from build scripts.
+    ///
+    /// Note that this replaces Cargo's default of running the build script again when any
+    /// file in the crate changes. Scripts that depend on other files have to emit their own
+    /// `cargo:rerun-if-changed` lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the code cannot be generated, as in [`Self::generate`], or if
+    /// the file cannot be written.
+    pub fn write_to_out_dir(&self, file_name: impl AsRef<Path>) -> io::Result<()> {
+        println!("cargo:rerun-if-changed={}", self.path.display());
+
+        let out_dir = std::env::var_os("OUT_DIR")
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "OUT_DIR is not set"))?;
+        let (code, resources) = self.generate_with_resources()?;
+        for path in resources {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+
+        let out_path = Path::new(&out_dir).join(file_name);
+        if std::fs::read_to_string(&out_path).ok().as_deref() != Some(code.as_str()) {
+            std::fs::write(out_path, code)?;
+        }
+        Ok(())
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}:{}", self.path.display(), message),
+        )
+    }
+}
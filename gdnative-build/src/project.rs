@@ -0,0 +1,472 @@
+//! Minimal reader for `project.godot` files.
+//!
+//! Only the structure of the file is read: section names, keys, and the source text of the
+//! values. Values are parsed on demand, as far as the generators need them.
+//!
+//! Scene files use the same format, with attributes in their section headers, and are read
+//! with the same reader.
+
+use std::fmt;
+
+/// Contents of a `project.godot` file.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ProjectFile {
+    sections: Vec<Section>,
+}
+
+#[derive(Clone, Debug)]
+struct Section {
+    name: String,
+    entries: Vec<Entry>,
+}
+
+/// A `key=value` pair, with the value as it appears in the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Entry {
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+/// Error while reading a `project.godot` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl ProjectFile {
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut reader = Reader { source, pos: 0 };
+        let mut file = ProjectFile::default();
+        let mut current = Section {
+            name: String::new(),
+            entries: Vec::new(),
+        };
+
+        loop {
+            reader.skip_whitespace();
+            match reader.peek() {
+                None => break,
+                Some('[') => {
+                    let name = reader.section_name()?;
+                    file.push(std::mem::replace(
+                        &mut current,
+                        Section {
+                            name,
+                            entries: Vec::new(),
+                        },
+                    ));
+                }
+                Some(_) => {
+                    let entry = reader.entry()?;
+                    match current.entries.iter_mut().find(|e| e.key == entry.key) {
+                        Some(existing) => *existing = entry,
+                        None => current.entries.push(entry),
+                    }
+                }
+            }
+        }
+
+        file.push(current);
+        Ok(file)
+    }
+
+    /// Returns the entries of a section, in the order of the file. Sections that appear
+    /// several times are merged.
+    pub fn section<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Entry> + 'a {
+        self.sections
+            .iter()
+            .filter(move |section| section.name == name)
+            .flat_map(|section| section.entries.iter())
+    }
+
+    /// Returns the names of all sections, in the order of the file.
+    pub fn section_names(&self) -> impl Iterator<Item = &str> {
+        self.sections.iter().map(|section| section.name.as_str())
+    }
+
+    fn push(&mut self, section: Section) {
+        if section.name.is_empty() && section.entries.is_empty() {
+            return;
+        }
+
+        match self.sections.iter_mut().find(|s| s.name == section.name) {
+            Some(existing) => {
+                for entry in section.entries {
+                    match existing.entries.iter_mut().find(|e| e.key == entry.key) {
+                        Some(old) => *old = entry,
+                        None => existing.entries.push(entry),
+                    }
+                }
+            }
+            None => self.sections.push(section),
+        }
+    }
+}
+
+impl Entry {
+    /// Returns the value if it is a string literal.
+    pub fn string_value(&self) -> Option<String> {
+        let mut reader = Reader {
+            source: &self.value,
+            pos: 0,
+        };
+        let value = reader.string().ok()?;
+        reader.skip_whitespace();
+        reader.peek().is_none().then_some(value)
+    }
+}
+
+/// Returns the value of an attribute in a section header of a scene file, like `type` in
+/// `node name="Player" type="KinematicBody2D"`. Strings are unescaped, other values are
+/// returned as they appear in the file.
+pub(crate) fn header_attribute(header: &str, key: &str) -> Option<String> {
+    let mut reader = Reader {
+        source: header,
+        pos: 0,
+    };
+
+    // Skip the name of the tag.
+    reader.pos = header.find(char::is_whitespace)?;
+    loop {
+        reader.skip_whitespace();
+        reader.peek()?;
+
+        let len = reader.rest().find('=')?;
+        let name = reader.rest()[..len].trim();
+        reader.pos += len + 1;
+
+        let value = if reader.peek() == Some('"') {
+            reader.string().ok()?
+        } else {
+            let start = reader.pos;
+            let mut depth = 0_usize;
+            while let Some(c) = reader.peek() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth = depth.saturating_sub(1),
+                    c if c.is_whitespace() && depth == 0 => break,
+                    _ => {}
+                }
+                reader.bump();
+            }
+            header[start..reader.pos].to_owned()
+        };
+
+        if name == key {
+            return Some(value);
+        }
+    }
+}
+
+struct Reader<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        self.error_at(self.pos, message)
+    }
+
+    fn error_at(&self, pos: usize, message: impl Into<String>) -> ParseError {
+        let before = &self.source[..pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before[before.rfind('\n').map_or(0, |i| i + 1)..]
+            .chars()
+            .count()
+            + 1;
+
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    fn line(&self) -> usize {
+        self.source[..self.pos].matches('\n').count() + 1
+    }
+
+    /// Skips whitespace, including line breaks, and `;` comments.
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == ';' {
+                let len = self.rest().find('\n').unwrap_or(self.rest().len());
+                self.pos += len;
+            } else if c.is_whitespace() {
+                self.pos += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn section_name(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.bump();
+        let line = &self.rest()[..self.rest().find('\n').unwrap_or(self.rest().len())];
+        match line.find(']') {
+            Some(end) => {
+                let name = line[..end].trim().to_owned();
+                self.pos += end + 1;
+                Ok(name)
+            }
+            None => Err(self.error_at(start, "expected ']' at the end of the section name")),
+        }
+    }
+
+    fn entry(&mut self) -> Result<Entry, ParseError> {
+        let line = self.line();
+
+        let key = if self.peek() == Some('"') {
+            self.string()?
+        } else {
+            let len = self.rest().find(['=', '\n']).unwrap_or(self.rest().len());
+            let key = self.rest()[..len].trim_end().to_owned();
+            self.pos += len;
+            key
+        };
+
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.bump();
+        }
+        if self.peek() != Some('=') {
+            return Err(self.error("expected '=' after the key"));
+        }
+        self.bump();
+
+        let start = self.pos;
+        self.skip_value()?;
+        let value = self.source[start..self.pos].trim().to_owned();
+
+        Ok(Entry { key, value, line })
+    }
+
+    /// Skips a value, which ends at the first line break outside of brackets and strings.
+    fn skip_value(&mut self) -> Result<(), ParseError> {
+        let mut open = Vec::new();
+
+        while let Some(c) = self.peek() {
+            match c {
+                '\n' if open.is_empty() => break,
+                '"' => {
+                    self.string()?;
+                    continue;
+                }
+                '(' | '[' | '{' => open.push((self.pos, c)),
+                ')' | ']' | '}' => {
+                    let expected = match open.pop() {
+                        Some((_, '(')) => ')',
+                        Some((_, '[')) => ']',
+                        Some(_) => '}',
+                        None => return Err(self.error(format!("unexpected '{}'", c))),
+                    };
+                    if c != expected {
+                        return Err(self.error(format!("expected '{}'", expected)));
+                    }
+                }
+                _ => {}
+            }
+            self.bump();
+        }
+
+        match open.pop() {
+            Some((pos, c)) => Err(self.error_at(pos, format!("unclosed '{}'", c))),
+            None => Ok(()),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        if self.bump() != Some('"') {
+            return Err(self.error_at(start, "expected a string"));
+        }
+
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(self.error_at(start, "unterminated string")),
+                Some('"') => return Ok(value),
+                Some('\\') => match self.bump() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('r') => value.push('\r'),
+                    Some(c) => value.push(c),
+                    None => return Err(self.error_at(start, "unterminated string")),
+                },
+                Some(c) => value.push(c),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = r#"; Engine configuration file.
+; It's best edited using the editor UI and not directly,
+; since the parameters that go here are not all obvious.
+
+config_version=4
+
+_global_script_classes=[ {
+"class": "PlayerScript",
+"path": "res://Player.gdns"
+} ]
+
+[application]
+
+config/name="Demo ] [input]"
+
+[autoload]
+
+Global="*res://global.gd"
+HUD="res://ui/hud.tscn"
+
+[input]
+
+jump={
+"deadzone": 0.5,
+"events": [ Object(InputEventKey,"pressed":false,"scancode":32,"unicode":0,"echo":false,"script":null)
+ ]
+}
+"fire weapon"={
+"deadzone": 0.5,
+"events": [  ]
+}
+move_left={ "deadzone": 0.5, "events": [ ] }
+jump={
+"deadzone": 0.2,
+"events": [  ]
+}
+"#;
+
+    #[test]
+    fn reads_sections() {
+        let file = ProjectFile::parse(PROJECT).unwrap();
+
+        let keys = |section| {
+            file.section(section)
+                .map(|e| e.key.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(vec!["config_version", "_global_script_classes"], keys(""));
+        assert_eq!(vec!["config/name"], keys("application"));
+        assert_eq!(vec!["Global", "HUD"], keys("autoload"));
+        assert_eq!(vec!["jump", "fire weapon", "move_left"], keys("input"));
+        assert!(keys("rendering").is_empty());
+
+        let jump = file.section("input").next().unwrap();
+        assert_eq!(33, jump.line);
+        assert!(jump.value.contains("0.2"));
+    }
+
+    #[test]
+    fn reads_string_values() {
+        let file = ProjectFile::parse(PROJECT).unwrap();
+
+        let values = file
+            .section("autoload")
+            .map(|e| e.string_value())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Some("*res://global.gd".to_owned()),
+                Some("res://ui/hud.tscn".to_owned())
+            ],
+            values
+        );
+
+        let config = file.section("").next().unwrap();
+        assert_eq!(None, config.string_value());
+
+        let entry = ProjectFile::parse(r#"a="say \"hi\"\n""#).unwrap();
+        let entry = entry.section("").next().unwrap();
+        assert_eq!(Some("say \"hi\"\n".to_owned()), entry.string_value());
+    }
+
+    #[test]
+    fn merges_repeated_sections() {
+        let file = ProjectFile::parse("[input]\na=1\n[other]\nb=2\n[input]\nc=3\na=4\n").unwrap();
+
+        let input = file.section("input").collect::<Vec<_>>();
+        assert_eq!(2, input.len());
+        assert_eq!(
+            ("a", "4", 7),
+            (&*input[0].key, &*input[0].value, input[0].line)
+        );
+        assert_eq!(
+            ("c", "3", 6),
+            (&*input[1].key, &*input[1].value, input[1].line)
+        );
+    }
+
+    #[test]
+    fn reads_header_attributes() {
+        let header = r#"node name="Say \"hi\"" parent="." instance=ExtResource( 1 ) index=2"#;
+        let attr = |key| header_attribute(header, key);
+
+        assert_eq!(Some("Say \"hi\"".to_owned()), attr("name"));
+        assert_eq!(Some(".".to_owned()), attr("parent"));
+        assert_eq!(Some("ExtResource( 1 )".to_owned()), attr("instance"));
+        assert_eq!(Some("2".to_owned()), attr("index"));
+        assert_eq!(None, attr("type"));
+        assert_eq!(None, header_attribute("node", "name"));
+
+        let scene = ProjectFile::parse(
+            "[gd_scene load_steps=2 format=2]\n\n\
+             [ext_resource path=\"res://hud.gd\" type=\"Script\" id=1]\n\n\
+             [node name=\"HUD\" type=\"CanvasLayer\"]\nscript = ExtResource( 1 )\n",
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                "gd_scene load_steps=2 format=2",
+                "ext_resource path=\"res://hud.gd\" type=\"Script\" id=1",
+                "node name=\"HUD\" type=\"CanvasLayer\"",
+            ],
+            scene.section_names().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        let error = |source| ProjectFile::parse(source).unwrap_err();
+
+        assert_eq!((1, 1), pos(error("[input\na=1")));
+        assert_eq!((2, 7), pos(error("[input]\nabcdef\n")));
+        assert_eq!((3, 10), pos(error("[input]\na={\n\"b\": [ 1 }\n")));
+        assert_eq!((2, 3), pos(error("[input]\na={\n")));
+        assert_eq!((1, 3), pos(error("a=\"abc")));
+        assert_eq!((1, 4), pos(error("a=1)")));
+
+        fn pos(error: ParseError) -> (usize, usize) {
+            (error.line, error.column)
+        }
+    }
+}
@@ -5,6 +5,8 @@ use std::ops::{Deref, DerefMut};
 use std::ptr;
 use std::slice;
 
+use crate::core_types::Pod;
+
 /// A pool array access that may be unaligned.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct MaybeUnaligned<G> {
@@ -143,6 +145,47 @@ impl<G: Guard> Aligned<G> {
     }
 }
 
+impl<G: Guard> Aligned<G>
+where
+    G::Target: Pod,
+{
+    /// Reinterprets the elements as a flat slice of their scalar components, without copying,
+    /// e.g. `&[f32]` with three values per element for `Vector3`.
+    #[inline]
+    pub fn as_flat_slice(&self) -> &[<G::Target as Pod>::Scalar] {
+        let elements = self.as_slice();
+        unsafe {
+            slice::from_raw_parts(
+                elements.as_ptr() as *const <G::Target as Pod>::Scalar,
+                elements.len() * <G::Target as Pod>::SCALARS,
+            )
+        }
+    }
+
+    /// Reinterprets the elements as a mutable flat slice of their scalar components, without
+    /// copying, e.g. `&mut [f32]` with three values per element for `Vector3`.
+    #[inline]
+    pub fn as_flat_mut_slice(&mut self) -> &mut [<G::Target as Pod>::Scalar]
+    where
+        G: WritePtr,
+    {
+        let elements = self.as_mut_slice();
+        unsafe {
+            slice::from_raw_parts_mut(
+                elements.as_mut_ptr() as *mut <G::Target as Pod>::Scalar,
+                elements.len() * <G::Target as Pod>::SCALARS,
+            )
+        }
+    }
+
+    /// Reinterprets the elements as raw bytes in native endianness, without copying.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        let elements = self.as_slice();
+        unsafe { slice::from_raw_parts(elements.as_ptr() as *const u8, mem::size_of_val(elements)) }
+    }
+}
+
 impl<G: Guard> Deref for Aligned<G> {
     type Target = [G::Target];
     #[inline]
@@ -4,7 +4,9 @@ use std::marker::PhantomData;
 use crate::private::get_api;
 use crate::sys;
 
-use crate::core_types::{GodotString, OwnedToVariant, ToVariantEq, Variant, VariantArray};
+use crate::core_types::{
+    FromVariant, FromVariantError, GodotString, OwnedToVariant, ToVariantEq, Variant, VariantArray,
+};
 use crate::object::NewRef;
 use std::fmt;
 
@@ -26,6 +28,7 @@ use crate::object::ownership::*;
 /// safe.
 ///
 /// [thread-safety]: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html
+#[repr(transparent)]
 pub struct Dictionary<Own: Ownership = Shared> {
     sys: sys::godot_dictionary,
 
@@ -171,6 +174,9 @@ impl<Own: Ownership> Dictionary<Own> {
     }
 
     /// Returns a GodotString of the `Dictionary`.
+    ///
+    /// See the [`json`][crate::core_types::json] module for a version that doesn't
+    /// require the engine, with support for indentation and sorted keys.
     #[inline]
     pub fn to_json(&self) -> GodotString {
         unsafe { GodotString((get_api().godot_dictionary_to_json)(self.sys())) }
@@ -204,17 +210,41 @@ impl<Own: Ownership> Dictionary<Own> {
         Iter::new(self)
     }
 
+    /// Returns an iterator through all key-value pairs in the `Dictionary`, converted to
+    /// `K` and `V`.
+    ///
+    /// Entries that can't be converted are reported as `FromVariantError::InvalidItem`,
+    /// with the index of the entry in iteration order. Iteration continues after errors.
+    #[inline]
+    pub fn iter_as<K: FromVariant, V: FromVariant>(&self) -> IterAs<'_, K, V, Own> {
+        IterAs {
+            iter: self.iter().enumerate(),
+            _marker: PhantomData,
+        }
+    }
+
     /// Create a copy of the dictionary.
     ///
     /// This creates a new dictionary and is **not** a cheap reference count
     /// increment.
     #[inline]
     pub fn duplicate(&self) -> Dictionary<Unique> {
-        let d = Dictionary::new();
-        for (k, v) in self {
-            d.insert(&k, &v);
+        unsafe {
+            let sys = (get_api().godot_dictionary_duplicate)(self.sys(), false);
+            Dictionary::<Unique>::from_sys(sys)
+        }
+    }
+
+    /// Create a deep copy of the dictionary.
+    ///
+    /// This creates a new dictionary and is **not** a cheap reference count
+    /// increment. Nested dictionaries and arrays are copied as well.
+    #[inline]
+    pub fn duplicate_deep(&self) -> Dictionary<Unique> {
+        unsafe {
+            let sys = (get_api().godot_dictionary_duplicate)(self.sys(), true);
+            Dictionary::<Unique>::from_sys(sys)
         }
-        d
     }
 
     #[doc(hidden)]
@@ -313,6 +343,60 @@ impl<Own: LocalThreadOwnership> Dictionary<Own> {
     pub fn clear(&self) {
         unsafe { (get_api().godot_dictionary_clear)(self.sys_mut()) }
     }
+
+    /// Gets the entry corresponding to the key for in-place manipulation.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let counts = Dictionary::new();
+    /// for word in ["a", "b", "a"] {
+    ///     counts
+    ///         .entry(word)
+    ///         .and_modify(|count| *count = Variant::new(count.to::<i64>().unwrap() + 1))
+    ///         .or_insert(1);
+    /// }
+    /// ```
+    #[inline]
+    pub fn entry<K>(&self, key: K) -> Entry<'_, Own>
+    where
+        K: OwnedToVariant + ToVariantEq,
+    {
+        let key = key.owned_to_variant();
+        if self.contains(&key) {
+            Entry::Occupied(OccupiedEntry { dict: self, key })
+        } else {
+            Entry::Vacant(VacantEntry { dict: self, key })
+        }
+    }
+
+    /// Retains only the key-value pairs specified by the predicate.
+    #[inline]
+    pub fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&Variant, &Variant) -> bool,
+    {
+        // Entries can't be erased during iteration, since the iterator needs the last key.
+        let erased = self
+            .iter()
+            .filter_map(|(key, value)| (!f(&key, &value)).then_some(key))
+            .collect::<Vec<_>>();
+
+        for key in erased {
+            self.erase(key);
+        }
+    }
+
+    /// Adds the entries of `other` to this dictionary. If `overwrite` is `true`, existing
+    /// values are replaced by the ones in `other`. Otherwise, existing values are kept.
+    #[inline]
+    pub fn merge<OtherOwn: Ownership>(&self, other: &Dictionary<OtherOwn>, overwrite: bool) {
+        for (key, value) in other {
+            if overwrite || !self.contains(&key) {
+                self.insert(key, value);
+            }
+        }
+    }
 }
 
 /// Operations allowed on unique Dictionaries.
@@ -502,6 +586,169 @@ impl IntoIterator for Dictionary<Unique> {
     }
 }
 
+/// A view into a single entry in a `Dictionary`, which may either be vacant or occupied.
+///
+/// This enum is created by the `entry` method on `Dictionary`. Since values are stored as
+/// `Variant`s, which are cheaply cloned, methods return copies of the values instead of
+/// references.
+#[derive(Debug)]
+pub enum Entry<'a, Own: LocalThreadOwnership> {
+    Occupied(OccupiedEntry<'a, Own>),
+    Vacant(VacantEntry<'a, Own>),
+}
+
+impl<'a, Own: LocalThreadOwnership> Entry<'a, Own> {
+    /// Returns the key of this entry.
+    #[inline]
+    pub fn key(&self) -> &Variant {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant, and returns the value.
+    #[inline]
+    pub fn or_insert<V: OwnedToVariant>(self, default: V) -> Variant {
+        match self {
+            Entry::Occupied(entry) => entry.get(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Inserts the result of `default` if the entry is vacant, and returns the value.
+    #[inline]
+    pub fn or_insert_with<V, F>(self, default: F) -> Variant
+    where
+        V: OwnedToVariant,
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.get(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Inserts `Nil` if the entry is vacant, and returns the value.
+    #[inline]
+    pub fn or_nil(self) -> Variant {
+        self.or_insert(Variant::nil())
+    }
+
+    /// Modifies the value of an occupied entry in place, before any potential inserts.
+    #[inline]
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Variant),
+    {
+        match self {
+            Entry::Occupied(entry) => {
+                let mut value = entry.get();
+                f(&mut value);
+                entry.insert(value);
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+/// A view into an occupied entry in a `Dictionary`. It is part of the `Entry` enum.
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, Own: LocalThreadOwnership> {
+    dict: &'a Dictionary<Own>,
+    key: Variant,
+}
+
+impl<'a, Own: LocalThreadOwnership> OccupiedEntry<'a, Own> {
+    /// Returns the key of this entry.
+    #[inline]
+    pub fn key(&self) -> &Variant {
+        &self.key
+    }
+
+    /// Returns a copy of the value of this entry.
+    #[inline]
+    pub fn get(&self) -> Variant {
+        self.dict.get_or_nil(&self.key)
+    }
+
+    /// Sets the value of this entry, and returns the old value.
+    #[inline]
+    pub fn insert<V: OwnedToVariant>(&self, value: V) -> Variant {
+        let old = self.get();
+        self.dict.insert(&self.key, value);
+        old
+    }
+
+    /// Removes this entry from the dictionary, and returns its value.
+    #[inline]
+    pub fn remove(self) -> Variant {
+        let old = self.get();
+        self.dict.erase(&self.key);
+        old
+    }
+}
+
+/// A view into a vacant entry in a `Dictionary`. It is part of the `Entry` enum.
+#[derive(Debug)]
+pub struct VacantEntry<'a, Own: LocalThreadOwnership> {
+    dict: &'a Dictionary<Own>,
+    key: Variant,
+}
+
+impl<'a, Own: LocalThreadOwnership> VacantEntry<'a, Own> {
+    /// Returns the key of this entry.
+    #[inline]
+    pub fn key(&self) -> &Variant {
+        &self.key
+    }
+
+    /// Takes ownership of the key.
+    #[inline]
+    pub fn into_key(self) -> Variant {
+        self.key
+    }
+
+    /// Sets the value of this entry, and returns a copy of it.
+    #[inline]
+    pub fn insert<V: OwnedToVariant>(self, value: V) -> Variant {
+        let value = value.owned_to_variant();
+        self.dict.insert(&self.key, &value);
+        value
+    }
+}
+
+/// Iterator through all key-value pairs in a `Dictionary`, converted to `K` and `V`.
+///
+/// This struct is created by the `iter_as` method on `Dictionary`.
+#[derive(Debug)]
+pub struct IterAs<'a, K, V, Own: Ownership> {
+    iter: std::iter::Enumerate<Iter<'a, Own>>,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<'a, K: FromVariant, V: FromVariant, Own: Ownership> Iterator for IterAs<'a, K, V, Own> {
+    type Item = Result<(K, V), FromVariantError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(index, (key, value))| {
+            K::from_variant(&key)
+                .and_then(|key| V::from_variant(&value).map(|value| (key, value)))
+                .map_err(|error| FromVariantError::InvalidItem {
+                    index,
+                    error: Box::new(error),
+                })
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
 impl<K, V> FromIterator<(K, V)> for Dictionary<Unique>
 where
     K: ToVariantEq + OwnedToVariant,
@@ -584,6 +831,67 @@ godot_test!(test_dictionary {
     assert_eq!(expected_keys, iter_keys);
 });
 
+godot_test!(test_dictionary_entry {
+    let dict = Dictionary::new();
+
+    for word in ["a", "b", "a", "c", "a"] {
+        dict.entry(word)
+            .and_modify(|count| *count = Variant::new(count.to::<i64>().unwrap() + 1))
+            .or_insert(1);
+    }
+    assert_eq!(dict.get("a"), Some(Variant::new(3)));
+    assert_eq!(dict.get("b"), Some(Variant::new(1)));
+
+    assert_eq!(dict.entry("d").or_insert_with(|| 4), Variant::new(4));
+    assert_eq!(dict.entry("d").or_insert_with(|| 5), Variant::new(4));
+    assert!(dict.entry("e").or_nil().is_nil());
+    assert!(dict.contains("e"));
+
+    match dict.entry("d") {
+        Entry::Occupied(entry) => assert_eq!(entry.remove(), Variant::new(4)),
+        Entry::Vacant(_) => panic!("entry should be occupied"),
+    }
+    assert!(!dict.contains("d"));
+
+    dict.retain(|_, value| value.to::<i64>().map_or(false, |n| n > 1));
+    assert_eq!(dict.len(), 1);
+    assert!(dict.contains("a"));
+
+    let other = Dictionary::new();
+    other.insert("a", 10);
+    other.insert("f", 6);
+    dict.merge(&other, false);
+    assert_eq!(dict.get("a"), Some(Variant::new(3)));
+    assert_eq!(dict.get("f"), Some(Variant::new(6)));
+    dict.merge(&other, true);
+    assert_eq!(dict.get("a"), Some(Variant::new(10)));
+
+    let typed = dict.iter_as::<String, i64>().collect::<Result<Vec<_>, _>>();
+    assert_eq!(typed, Ok(vec![("a".to_string(), 10), ("f".to_string(), 6)]));
+
+    dict.insert(1, "one");
+    match dict.iter_as::<String, i64>().nth(2) {
+        Some(Err(FromVariantError::InvalidItem { index, .. })) => assert_eq!(index, 2),
+        other => panic!("expected InvalidItem, got {other:?}"),
+    }
+});
+
+godot_test!(test_dictionary_duplicate_deep {
+    let inner = Dictionary::new();
+    inner.insert("x", 1);
+    let outer = Dictionary::new();
+    outer.insert("inner", inner.into_shared());
+
+    let shallow = outer.duplicate();
+    let deep = outer.duplicate_deep();
+    unsafe {
+        outer.get("inner").unwrap().coerce_to::<Dictionary>().assume_unique().insert("y", 2);
+    }
+
+    assert!(shallow.get("inner").unwrap().coerce_to::<Dictionary>().contains("y"));
+    assert!(!deep.get("inner").unwrap().coerce_to::<Dictionary>().contains("y"));
+});
+
 godot_test!(test_dictionary_clone_clear {
     let foo = Variant::new("foo");
     let bar = Variant::new("bar");
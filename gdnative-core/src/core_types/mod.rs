@@ -4,6 +4,8 @@
 //! are the counterparts to built-in types in GDScript.
 //!
 //! godot-rust provides optional serialization support for many core types.  Enable the feature `serde` to make use of it.
+//! The feature also adds `to_variant` and `from_variant`, which convert any `Serialize` or `Deserialize` type to and
+//! from a `Variant`. Like the other `Variant` items, they are exported directly from this module.
 
 mod geom;
 
@@ -15,6 +17,8 @@ mod node_path;
 mod pool_array;
 mod rid;
 mod string;
+mod typed_array;
+mod typed_dictionary;
 mod variant;
 mod variant_array;
 mod vector2;
@@ -29,7 +33,21 @@ pub use node_path::*;
 pub use pool_array::*;
 pub use rid::*;
 pub use string::*;
-pub use variant::*;
+pub use typed_array::*;
+pub use typed_dictionary::*;
+pub use variant::{
+    CallError, CoerceFromVariant, FromVariant, FromVariantError, InvalidOp, MaybeNot, ObjectValue,
+    OwnedToVariant, ToVariant, ToVariantDictionary, ToVariantEq, Variant, VariantDispatch,
+    VariantEnumRepr, VariantKey, VariantMut, VariantOperator, VariantRef, VariantStructRepr,
+    VariantType, VariantValue,
+};
+
+pub use variant::{binary, config, json, scene, text};
+
+#[cfg(feature = "serde")]
+pub use variant::{
+    from_variant, to_variant, SerializeError, VariantDeserializer, VariantSerializer,
+};
 pub use variant_array::*;
 pub use vector2::*;
 pub use vector3::*;
@@ -3,6 +3,7 @@ use crate::object::NewRef;
 use crate::private::get_api;
 use crate::sys;
 use std::fmt;
+use std::ops::Range;
 
 /// A reference-counted relative or absolute path in a scene tree, for use with `Node.get_node()` and similar
 /// functions. It can reference a node, a resource within a node, or a property of a node or
@@ -20,6 +21,7 @@ use std::fmt;
 /// the final node or resource.
 ///
 /// More info at [Godot's official documentation](https://godot.readthedocs.io/en/latest/classes/class_nodepath.html)
+#[repr(transparent)]
 pub struct NodePath(pub(crate) sys::godot_node_path);
 
 impl NodePath {
@@ -67,8 +69,36 @@ impl NodePath {
 
     /// Get the number of node names which make up the path.
     #[inline]
-    pub fn name_count(&mut self) -> i32 {
-        unsafe { (get_api().godot_node_path_get_name_count)(&mut self.0) }
+    pub fn name_count(&self) -> i32 {
+        unsafe { (get_api().godot_node_path_get_name_count)(&self.0) }
+    }
+
+    /// Returns the node name of the specified `idx`, 0 to name_count()
+    #[inline]
+    pub fn get_name(&self, idx: i32) -> GodotString {
+        unsafe { GodotString((get_api().godot_node_path_get_name)(&self.0, idx)) }
+    }
+
+    /// Returns an iterator over the node names in the path, e.g. `"Path2D"`, `"PathFollow2D"`
+    /// and `"Sprite"` for `"Path2D/PathFollow2D/Sprite:texture:size"`.
+    #[inline]
+    pub fn names(&self) -> Names<'_> {
+        Names {
+            path: self,
+            range: 0..self.name_count(),
+            get: NodePath::get_name,
+        }
+    }
+
+    /// Returns an iterator over the resource and property names in the path, e.g. `"texture"`
+    /// and `"size"` for `"Path2D/PathFollow2D/Sprite:texture:size"`.
+    #[inline]
+    pub fn subnames(&self) -> Names<'_> {
+        Names {
+            path: self,
+            range: 0..self.get_subname_count(),
+            get: NodePath::get_subname,
+        }
     }
 
     /// Returns the resource name of the specified `idx`, 0 to subname_count()
@@ -83,8 +113,10 @@ impl NodePath {
         unsafe { (get_api().godot_node_path_get_subname_count)(&self.0) }
     }
 
+    /// Returns all subnames joined by `":"`, e.g. `"texture:size"` for
+    /// `"Path2D/PathFollow2D/Sprite:texture:size"`.
     #[inline]
-    pub fn get_concatenated_subnames(&self) -> GodotString {
+    pub fn concatenated_subnames(&self) -> GodotString {
         unsafe {
             GodotString((get_api().godot_node_path_get_concatenated_subnames)(
                 &self.0,
@@ -92,6 +124,29 @@ impl NodePath {
         }
     }
 
+    #[inline]
+    #[deprecated = "Renamed to `concatenated_subnames`."]
+    pub fn get_concatenated_subnames(&self) -> GodotString {
+        self.concatenated_subnames()
+    }
+
+    /// Returns a path that only consists of the subnames of this path, with the node names
+    /// removed, e.g. `":texture:size"` for `"Path2D/PathFollow2D/Sprite:texture:size"`.
+    #[inline]
+    pub fn as_property_path(&self) -> NodePath {
+        unsafe { NodePath((get_api().godot_node_path_get_as_property_path)(&self.0)) }
+    }
+
+    /// Appends the relative path `other` to this path.
+    ///
+    /// # Errors
+    ///
+    /// If `other` is absolute, or if node names would follow subnames.
+    #[inline]
+    pub fn join(&self, other: &NodePath) -> Result<NodePath, NodePathError> {
+        NodePathBuilder::from_path(self).path(other).build()
+    }
+
     /// Returns the `NodePath` as a `GodotString`
     #[inline]
     pub fn to_godot_string(&self) -> GodotString {
@@ -117,6 +172,228 @@ impl NodePath {
     }
 }
 
+/// Iterator over the node names or subnames of a `NodePath`.
+///
+/// This struct is created by the `names` and `subnames` methods on `NodePath`.
+pub struct Names<'a> {
+    path: &'a NodePath,
+    range: Range<i32>,
+    get: fn(&NodePath, i32) -> GodotString,
+}
+
+impl<'a> Iterator for Names<'a> {
+    type Item = GodotString;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|idx| (self.get)(self.path, idx))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Names<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|idx| (self.get)(self.path, idx))
+    }
+}
+
+impl<'a> ExactSizeIterator for Names<'a> {}
+
+impl<'a> fmt::Debug for Names<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("Names")
+            .field("path", self.path)
+            .field("range", &self.range)
+            .finish()
+    }
+}
+
+/// Builder for `NodePath`s that checks node names and subnames as they are added, so that
+/// paths can't be corrupted by names containing separators.
+///
+/// # Example
+///
+/// ```no_run
+/// # use gdnative::prelude::*;
+/// # use gdnative::core_types::NodePathBuilder;
+/// let path = NodePathBuilder::new()
+///     .name("Path2D")
+///     .name("PathFollow2D")
+///     .subname("position")
+///     .subname("x")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(path.to_string(), "Path2D/PathFollow2D:position:x");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NodePathBuilder {
+    absolute: bool,
+    names: Vec<String>,
+    subnames: Vec<String>,
+    error: Option<NodePathError>,
+}
+
+impl NodePathBuilder {
+    /// Creates a builder for a relative path.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a builder for an absolute path, starting at the root of the scene tree.
+    #[inline]
+    pub fn absolute() -> Self {
+        NodePathBuilder {
+            absolute: true,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a builder that starts with the names and subnames of `path`.
+    #[inline]
+    pub fn from_path(path: &NodePath) -> Self {
+        NodePathBuilder {
+            absolute: path.is_absolute(),
+            names: path.names().map(|name| name.to_string()).collect(),
+            subnames: path.subnames().map(|name| name.to_string()).collect(),
+            error: None,
+        }
+    }
+
+    /// Appends a node name. `"."` and `".."` are allowed to refer to the current node and
+    /// its parent. Names must not be empty or contain any of `/:."`.
+    #[inline]
+    pub fn name(mut self, name: impl AsRef<str>) -> Self {
+        let name = name.as_ref();
+        if self.error.is_none() {
+            self.error = if !self.subnames.is_empty() {
+                Some(NodePathError::NameAfterSubname(name.to_owned()))
+            } else {
+                validate_name(name).err()
+            };
+        }
+        self.names.push(name.to_owned());
+        self
+    }
+
+    /// Appends `".."`, referring to the parent node.
+    #[inline]
+    pub fn parent(self) -> Self {
+        self.name("..")
+    }
+
+    /// Appends a resource or property name. Subnames must not be empty or contain `:`.
+    #[inline]
+    pub fn subname(mut self, subname: impl AsRef<str>) -> Self {
+        let subname = subname.as_ref();
+        if self.error.is_none() {
+            self.error = validate_subname(subname).err();
+        }
+        self.subnames.push(subname.to_owned());
+        self
+    }
+
+    /// Appends the names and subnames of the relative path `path`.
+    #[inline]
+    pub fn path(mut self, path: &NodePath) -> Self {
+        if path.is_absolute() && self.error.is_none() {
+            self.error = Some(NodePathError::AbsoluteJoin(path.to_string()));
+        }
+        for name in path.names() {
+            self = self.name(name.to_string());
+        }
+        for subname in path.subnames() {
+            self = self.subname(subname.to_string());
+        }
+        self
+    }
+
+    /// Builds the `NodePath`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered while adding names and subnames.
+    #[inline]
+    pub fn build(self) -> Result<NodePath, NodePathError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let mut path = String::new();
+        if self.absolute {
+            path.push('/');
+        }
+        path.push_str(&self.names.join("/"));
+        for subname in &self.subnames {
+            path.push(':');
+            path.push_str(subname);
+        }
+
+        Ok(NodePath::from_str(&path))
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), NodePathError> {
+    let is_valid = match name {
+        "" => false,
+        "." | ".." => true,
+        _ => !name.contains(['/', ':', '.', '"']),
+    };
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(NodePathError::InvalidName(name.to_owned()))
+    }
+}
+
+fn validate_subname(subname: &str) -> Result<(), NodePathError> {
+    if subname.is_empty() || subname.contains(':') {
+        Err(NodePathError::InvalidSubname(subname.to_owned()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Error indicating that a `NodePath` could not be built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NodePathError {
+    /// A node name was empty or contained one of `/:."`.
+    InvalidName(String),
+    /// A subname was empty or contained `:`.
+    InvalidSubname(String),
+    /// A node name was added after a subname.
+    NameAfterSubname(String),
+    /// An absolute path was appended to another path.
+    AbsoluteJoin(String),
+}
+
+impl fmt::Display for NodePathError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NodePathError::InvalidName(name) => write!(f, "invalid node name {name:?}"),
+            NodePathError::InvalidSubname(name) => write!(f, "invalid subname {name:?}"),
+            NodePathError::NameAfterSubname(name) => {
+                write!(f, "node name {name:?} cannot follow a subname")
+            }
+            NodePathError::AbsoluteJoin(path) => {
+                write!(f, "cannot append absolute path {path:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NodePathError {}
+
 impl Default for NodePath {
     #[inline]
     fn default() -> Self {
@@ -233,3 +510,51 @@ mod serialize {
         }
     }
 }
+
+godot_test!(test_node_path {
+    let path = NodePath::from_str("Path2D/PathFollow2D/Sprite:texture:size");
+    assert_eq!(path.name_count(), 3);
+    assert_eq!(
+        path.names().map(|name| name.to_string()).collect::<Vec<_>>(),
+        ["Path2D", "PathFollow2D", "Sprite"],
+    );
+    assert_eq!(
+        path.subnames().rev().map(|name| name.to_string()).collect::<Vec<_>>(),
+        ["size", "texture"],
+    );
+    assert_eq!(path.concatenated_subnames().to_string(), "texture:size");
+    assert_eq!(path.as_property_path().to_string(), ":texture:size");
+
+    let built = NodePathBuilder::absolute()
+        .name("root")
+        .name("Main")
+        .subname("position")
+        .build()
+        .unwrap();
+    assert_eq!(built.to_string(), "/root/Main:position");
+    assert!(built.is_absolute());
+
+    let built = NodePathBuilder::new().parent().name("Sibling").build().unwrap();
+    assert_eq!(built.to_string(), "../Sibling");
+
+    assert_eq!(
+        NodePathBuilder::new().name("a/b").build().err(),
+        Some(NodePathError::InvalidName("a/b".into())),
+    );
+    assert_eq!(
+        NodePathBuilder::new().name("a").subname("b:c").build().err(),
+        Some(NodePathError::InvalidSubname("b:c".into())),
+    );
+    assert_eq!(
+        NodePathBuilder::new().subname("b").name("a").build().err(),
+        Some(NodePathError::NameAfterSubname("a".into())),
+    );
+
+    let base = NodePath::from_str("/root/Main");
+    let joined = base.join(&NodePath::from_str("Player:position")).unwrap();
+    assert_eq!(joined.to_string(), "/root/Main/Player:position");
+    assert_eq!(
+        base.join(&NodePath::from_str("/root")).err(),
+        Some(NodePathError::AbsoluteJoin("/root".into())),
+    );
+});
@@ -8,6 +8,11 @@ use crate::core_types::{Color, GodotString, VariantArray, Vector2, Vector3};
 use crate::object::NewRef;
 use crate::private::get_api;
 
+mod byte_array;
+mod digest;
+
+pub use byte_array::*;
+
 #[cfg(feature = "gd-test")]
 mod godot_tests;
 #[cfg(feature = "gd-test")]
@@ -41,6 +46,7 @@ pub type Write<'a, T> = Aligned<WriteGuard<'a, T>>;
 /// [`write()`][Self::write] or the [`append()`][Self::append] methods, as opposed to
 /// [`push()`][Self::push] or [`set()`][Self::set], because the latter ones trigger
 /// CoW behavior each time they are called.
+#[repr(transparent)]
 pub struct PoolArray<T: PoolElement> {
     inner: T::SysArray,
 }
@@ -240,6 +246,17 @@ impl<T: PoolElement> PoolArray<T> {
     ///
     /// The returned write guard implements `DerefMut` with target type `[T]`, i.e. can be dereferenced to `&mut [T]`.
     /// This means all mutating and read-only slice methods can be used, see [here](struct.Aligned.html#deref-methods).
+    ///
+    /// For example, the contents can be overwritten in bulk with `copy_from_slice`:
+    ///
+    /// ```no_run
+    /// use gdnative::core_types::{PoolArray, Vector2};
+    ///
+    /// let src = [Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0)];
+    /// let mut arr = PoolArray::<Vector2>::new();
+    /// arr.resize(src.len() as i32);
+    /// arr.write().copy_from_slice(&src);
+    /// ```
     #[inline]
     pub fn write(&mut self) -> Write<'_, T> {
         unsafe {
@@ -249,6 +266,31 @@ impl<T: PoolElement> PoolArray<T> {
         }
     }
 
+    /// Ensures that this instance is the only reference to its buffer, copying the contents
+    /// if it is shared with other `PoolArray`s. Returns `true` if a copy was made.
+    ///
+    /// Godot does not expose the reference count of pool arrays, so this is done by taking a
+    /// write lock, which triggers copy-on-write if the buffer is shared. Calling this before
+    /// a series of writes makes the cost of the copy explicit, and subsequent calls to
+    /// [`write()`][Self::write] will not copy again as long as no new references are made.
+    #[inline]
+    pub fn make_unique(&mut self) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let shared_ptr = self.read().as_ptr();
+        let unique_ptr = self.write().as_ptr();
+        shared_ptr != unique_ptr
+    }
+
+    /// Returns `true` if `self` and `other` are references to the same non-empty buffer, i.e.
+    /// if writing to either of them would trigger a copy.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        !self.is_empty() && self.read().as_ptr() == other.read().as_ptr()
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn sys(&self) -> *const T::SysArray {
@@ -297,6 +339,55 @@ impl<T: PoolElement + Copy> PoolArray<T> {
         let mut write = self.write();
         write[start..].copy_from_slice(src)
     }
+
+    /// Copies and appends all values in `src` to the end of the array.
+    ///
+    /// This is the same as [`append_slice()`][Self::append_slice], named after
+    /// `Vec::extend_from_slice`.
+    ///
+    /// # Panics
+    ///
+    /// If the resulting length would not fit in `i32`.
+    #[inline]
+    pub fn extend_from_slice(&mut self, src: &[T]) {
+        self.append_slice(src)
+    }
+}
+
+impl<T: Pod> PoolArray<T> {
+    /// Creates a new `PoolArray` by copying from a flat slice of scalar components, e.g.
+    /// `&[f32]` with three values per element for `Vector3`.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `src` is not a multiple of the number of components per element, or
+    /// if the resulting length does not fit in `i32`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use gdnative::core_types::{PoolArray, Vector3};
+    ///
+    /// let vertices: &[f32] = &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    /// let arr = PoolArray::<Vector3>::from_flat_slice(vertices);
+    /// assert_eq!(3, arr.len());
+    /// assert_eq!(vertices, arr.read().as_flat_slice());
+    /// ```
+    #[inline]
+    pub fn from_flat_slice(src: &[T::Scalar]) -> Self {
+        assert_eq!(
+            0,
+            src.len() % T::SCALARS,
+            "length of flat slice should be a multiple of {}",
+            T::SCALARS,
+        );
+
+        let len = src.len() / T::SCALARS;
+        let mut arr = Self::new();
+        arr.resize(i32::try_from(len).expect("length should fit in i32"));
+        arr.write().as_flat_mut_slice().copy_from_slice(src);
+        arr
+    }
 }
 
 impl<T: PoolElement> Drop for PoolArray<T> {
@@ -527,6 +618,50 @@ macros::impl_typed_array_element! {
     { .. }
 }
 
+/// Trait for element types of `PoolArray` that are plain old data: types without padding
+/// that consist only of `SCALARS` consecutive values of `Scalar`, for which any bit pattern
+/// is valid.
+///
+/// Read and write accesses to arrays of such types can be reinterpreted as flat slices of
+/// scalars or bytes without copying, using methods like
+/// [`as_flat_slice()`][crate::core_types::Aligned::as_flat_slice]. This trait is sealed.
+///
+/// # Safety
+///
+/// `Self` must have the same size and layout as `[Self::Scalar; Self::SCALARS]`.
+pub unsafe trait Pod: PoolElement + Copy {
+    /// The type of the scalar components of this type.
+    type Scalar: Pod;
+
+    /// The number of scalar components in this type.
+    const SCALARS: usize;
+}
+
+macro_rules! impl_pod {
+    ($($ty:ty => [$scalar:ty; $count:literal]),* $(,)?) => {
+        $(
+            const _: () = assert!(
+                std::mem::size_of::<$ty>() == $count * std::mem::size_of::<$scalar>()
+                    && std::mem::align_of::<$ty>() == std::mem::align_of::<$scalar>()
+            );
+
+            unsafe impl Pod for $ty {
+                type Scalar = $scalar;
+                const SCALARS: usize = $count;
+            }
+        )*
+    };
+}
+
+impl_pod! {
+    u8 => [u8; 1],
+    i32 => [i32; 1],
+    f32 => [f32; 1],
+    Vector2 => [f32; 2],
+    Vector3 => [f32; 3],
+    Color => [f32; 4],
+}
+
 mod private {
     pub trait Sealed {}
 }
@@ -0,0 +1,117 @@
+use crate::core_types::{FromVariant, GodotString, ToVariant, Variant};
+use crate::private::get_api;
+
+use super::{digest, PoolArray};
+
+/// Compression modes available for `PoolByteArray`s, same as the `COMPRESSION_*` constants of
+/// `File`.
+///
+/// See the [Godot documentation](https://docs.godotengine.org/en/3.5/classes/class_file.html#enumerations)
+/// for a comparison of the modes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CompressionMode {
+    /// Uses the FastLZ compression method.
+    FastLz,
+    /// Uses the DEFLATE compression method.
+    Deflate,
+    /// Uses the Zstandard compression method.
+    Zstd,
+    /// Uses the gzip compression method.
+    Gzip,
+}
+
+impl CompressionMode {
+    #[inline]
+    fn to_sys(self) -> i64 {
+        match self {
+            CompressionMode::FastLz => 0,
+            CompressionMode::Deflate => 1,
+            CompressionMode::Zstd => 2,
+            CompressionMode::Gzip => 3,
+        }
+    }
+}
+
+/// Methods specific to `PoolByteArray`.
+///
+/// Compression is implemented by the engine and produces the same data as the corresponding
+/// GDScript methods. Hashes are computed in Rust, and match the results of Godot's
+/// `HashingContext`.
+impl PoolArray<u8> {
+    /// Returns a new array with the data compressed using `mode`.
+    ///
+    /// The uncompressed size isn't stored in the output, and has to be passed to
+    /// [`decompress()`][Self::decompress].
+    ///
+    /// # Panics
+    ///
+    /// If calling the engine's `PoolByteArray.compress` method fails, or it doesn't return a
+    /// `PoolByteArray`. This doesn't happen with any Godot 3 version, since compressing
+    /// itself can't fail.
+    #[inline]
+    pub fn compress(&self, mode: CompressionMode) -> Self {
+        self.call_builtin("compress", &[mode.to_sys().to_variant()])
+            .expect("compress should be a valid method on PoolByteArray")
+    }
+
+    /// Returns a new array with the data decompressed using `mode`, given the size of the
+    /// original uncompressed data.
+    ///
+    /// Returns `None` if the data could not be decompressed.
+    #[inline]
+    pub fn decompress(&self, buffer_size: i32, mode: CompressionMode) -> Option<Self> {
+        self.call_builtin(
+            "decompress",
+            &[buffer_size.to_variant(), mode.to_sys().to_variant()],
+        )
+        .filter(|decompressed| !decompressed.is_empty() || self.is_empty())
+    }
+
+    /// Returns a new array with the data decompressed using `mode`, without knowing the size
+    /// of the uncompressed data in advance. The output is limited to `max_output_size` bytes.
+    ///
+    /// Only `Deflate` and `Gzip` are supported by this method. This requires Godot 3.3 or
+    /// later.
+    ///
+    /// Returns `None` if the data could not be decompressed.
+    #[inline]
+    pub fn decompress_dynamic(&self, max_output_size: i32, mode: CompressionMode) -> Option<Self> {
+        self.call_builtin(
+            "decompress_dynamic",
+            &[max_output_size.to_variant(), mode.to_sys().to_variant()],
+        )
+        .filter(|decompressed| !decompressed.is_empty() || self.is_empty())
+    }
+
+    /// Returns the MD5 digest of the contents.
+    #[inline]
+    pub fn md5(&self) -> [u8; 16] {
+        digest::md5(&self.read())
+    }
+
+    /// Returns the SHA-256 digest of the contents.
+    #[inline]
+    pub fn sha256(&self) -> [u8; 32] {
+        digest::sha256(&self.read())
+    }
+
+    /// Returns the contents as a string of lowercase hexadecimal digits, two per byte.
+    #[inline]
+    pub fn hex_encode(&self) -> GodotString {
+        let read = self.read();
+        unsafe {
+            GodotString((get_api().godot_string_hex_encode_buffer)(
+                read.as_ptr(),
+                read.len() as i32,
+            ))
+        }
+    }
+
+    fn call_builtin(&self, method: &str, args: &[Variant]) -> Option<Self> {
+        let mut variant = self.to_variant();
+
+        // SAFETY: built-in methods of `PoolByteArray` can't execute user code.
+        let result = unsafe { variant.call(method, args) }.ok()?;
+        PoolArray::from_variant(&result).ok()
+    }
+}
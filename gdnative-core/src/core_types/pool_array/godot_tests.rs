@@ -3,7 +3,7 @@ use approx::relative_eq;
 use crate::core_types::{Color, GodotString, Vector2, Vector3};
 use crate::object::NewRef as _;
 
-use super::{PoolArray, PoolElement};
+use super::{CompressionMode, PoolArray, PoolElement};
 
 fn test_array_access<T: PoolElement + Clone>(
     elements: impl IntoIterator<Item = T>,
@@ -103,4 +103,73 @@ godot_test!(
             |a, b| a == b,
         );
     }
+
+    test_pool_array_flat_access {
+        let vertices = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut arr = PoolArray::<Vector3>::from_flat_slice(&vertices);
+        assert_eq!(2, arr.len());
+        assert_eq!(Vector3::new(3.0, 4.0, 5.0), arr.get(1));
+        assert_eq!(&vertices[..], arr.read().as_flat_slice());
+        assert_eq!(24, arr.read().as_bytes().len());
+
+        arr.write().as_flat_mut_slice()[2] = 10.0;
+        assert_eq!(Vector3::new(0.0, 1.0, 10.0), arr.get(0));
+
+        arr.extend_from_slice(&[Vector3::new(6.0, 7.0, 8.0)]);
+        assert_eq!(3, arr.len());
+        assert_eq!(9, arr.read().as_flat_slice().len());
+
+        let colors = PoolArray::from_slice(&[Color::from_rgba(0.1, 0.2, 0.3, 0.4)]);
+        assert_eq!(&[0.1, 0.2, 0.3, 0.4][..], colors.read().as_flat_slice());
+    }
+
+    test_pool_array_make_unique {
+        let mut arr = PoolArray::from_slice(&[1, 2, 3]);
+        assert!(!arr.make_unique());
+
+        let shared = arr.new_ref();
+        assert!(arr.ptr_eq(&shared));
+        assert!(arr.make_unique());
+        assert!(!arr.ptr_eq(&shared));
+        assert!(!arr.make_unique());
+
+        arr.write().copy_from_slice(&[4, 5, 6]);
+        assert_eq!(vec![4, 5, 6], arr.to_vec());
+        assert_eq!(vec![1, 2, 3], shared.to_vec());
+    }
+
+    test_byte_array_compression {
+        let data = "Lorem ipsum dolor sit amet. ".repeat(32);
+        let arr = PoolArray::from_slice(data.as_bytes());
+
+        for mode in [
+            CompressionMode::FastLz,
+            CompressionMode::Deflate,
+            CompressionMode::Zstd,
+            CompressionMode::Gzip,
+        ] {
+            let compressed = arr.compress(mode);
+            assert!(compressed.len() < arr.len());
+
+            let decompressed = compressed.decompress(arr.len(), mode);
+            assert_eq!(Some(&arr), decompressed.as_ref());
+        }
+
+        let compressed = arr.compress(CompressionMode::Gzip);
+        let decompressed = compressed.decompress_dynamic(4096, CompressionMode::Gzip);
+        assert_eq!(Some(&arr), decompressed.as_ref());
+
+        let garbage = PoolArray::from_slice(&[1u8, 2, 3, 4]);
+        assert_eq!(None, garbage.decompress(64, CompressionMode::Gzip));
+    }
+
+    test_byte_array_hashing {
+        let text = GodotString::from("The quick brown fox jumps over the lazy dog");
+        let arr = text.to_utf8_buffer();
+
+        assert_eq!(&text.md5_buffer().to_vec()[..], &arr.md5()[..]);
+        assert_eq!(&text.sha256_buffer().to_vec()[..], &arr.sha256()[..]);
+        assert_eq!(text.md5_text(), PoolArray::from_slice(&arr.md5()).hex_encode());
+        assert_eq!(GodotString::from("00ff10"), PoolArray::from_slice(&[0u8, 255, 16]).hex_encode());
+    }
 );
@@ -21,6 +21,7 @@ use std::mem::transmute;
 /// [servers]: https://docs.godotengine.org/en/stable/tutorials/optimization/using_servers.html
 /// [docs]: https://docs.godotengine.org/en/stable/classes/class_rid.html
 #[derive(Copy, Clone, Debug)]
+#[repr(transparent)]
 pub struct Rid(pub(crate) sys::godot_rid);
 
 impl Rid {
@@ -55,7 +56,7 @@ impl Rid {
     }
 
     #[inline]
-    fn to_u64(self) -> u64 {
+    pub(crate) fn to_u64(self) -> u64 {
         unsafe {
             // std::mem::transmute needs source and destination types to have the same size. On 32
             // bit systems sizeof(void *) != size_of<u64>() so this fails to compile. The bindings
@@ -1,4 +1,5 @@
-use crate::core_types::Variant;
+use crate::core_types::{PoolArray, Variant, VariantArray};
+use crate::object::ownership::{Ownership, Unique};
 use crate::object::NewRef;
 use crate::private::get_api;
 use crate::sys;
@@ -45,6 +46,7 @@ use std::str;
 /// * you want a standard type for interoperability with third-party code (e.g. `regex` crate)
 /// * you have a large number of method calls per string instance (which are more expensive due to indirectly calling into Godot)
 /// * you need UTF-8 encoding (`GodotString`'s encoding is platform-dependent and unspecified)
+#[repr(transparent)]
 pub struct GodotString(pub(crate) sys::godot_string);
 
 macro_rules! impl_methods {
@@ -201,6 +203,224 @@ impl GodotString {
         unsafe { (get_api().godot_string_find_last)(&self.0, what.0) }
     }
 
+    #[inline]
+    pub fn findn(&self, what: &GodotString) -> i32 {
+        unsafe { (get_api().godot_string_findn)(&self.0, what.0) }
+    }
+
+    /// Returns an iterator over the substrings separated by `delimiter`. If `allow_empty` is
+    /// `false`, empty substrings are skipped.
+    ///
+    /// ```no_run
+    /// # use gdnative::prelude::*;
+    /// let csv = GodotString::from("a,b,,c");
+    /// let fields = csv.split(&",".into(), false).map(|s| s.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(fields, ["a", "b", "c"]);
+    /// ```
+    #[inline]
+    pub fn split(&self, delimiter: &GodotString, allow_empty: bool) -> Split {
+        unsafe {
+            let array = if allow_empty {
+                (get_api().godot_string_split_allow_empty)(&self.0, &delimiter.0)
+            } else {
+                (get_api().godot_string_split)(&self.0, &delimiter.0)
+            };
+            Split::from_array(VariantArray::<Unique>::from_sys(array))
+        }
+    }
+
+    /// Returns an iterator over the substrings separated by `delimiter`, splitting from the
+    /// end of the string. At most `max_split` splits are performed, unless it is `0`, in
+    /// which case there is no limit. If `allow_empty` is `false`, empty substrings are skipped.
+    ///
+    /// Substrings are still returned in their original order.
+    #[inline]
+    pub fn rsplit(&self, delimiter: &GodotString, allow_empty: bool, max_split: i32) -> Split {
+        unsafe {
+            let array =
+                (get_api().godot_string_rsplit)(&self.0, &delimiter.0, allow_empty, max_split);
+            Split::from_pool_array(PoolArray::from_sys(array))
+        }
+    }
+
+    /// Returns an iterator over the words of the string, split by whitespace.
+    #[inline]
+    pub fn words(&self) -> Split {
+        unsafe {
+            let array = (get_api().godot_string_split_spaces)(&self.0);
+            Split::from_array(VariantArray::<Unique>::from_sys(array))
+        }
+    }
+
+    /// Returns an iterator over the characters of the string, in Godot's native encoding.
+    ///
+    /// See the `Index` implementation for caveats regarding encoding.
+    #[inline]
+    pub fn chars(&self) -> Chars<'_> {
+        Chars {
+            string: self,
+            range: 0..self.len(),
+        }
+    }
+
+    /// Replaces all occurrences of `what` with `with`.
+    #[inline]
+    pub fn replace(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_replace)(&self.0, what.0, with.0)) }
+    }
+
+    /// Replaces all **case-insensitive** occurrences of `what` with `with`.
+    ///
+    /// Note that unlike `str::replacen`, the `n` stands for "no case", as in GDScript.
+    #[inline]
+    pub fn replacen(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_replacen)(&self.0, what.0, with.0)) }
+    }
+
+    /// Replaces the first occurrence of `what` with `with`.
+    #[inline]
+    pub fn replace_first(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_replace_first)(
+                &self.0, what.0, with.0,
+            ))
+        }
+    }
+
+    /// Returns a copy of the string stripped of any non-printable character (including
+    /// tabulations, spaces and line breaks) at the beginning and/or the end.
+    #[inline]
+    pub fn strip_edges(&self, left: bool, right: bool) -> Self {
+        unsafe { GodotString((get_api().godot_string_strip_edges)(&self.0, left, right)) }
+    }
+
+    /// Removes any characters contained in `chars` from the end of the string.
+    #[inline]
+    pub fn rstrip(&self, chars: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_rstrip)(&self.0, &chars.0)) }
+    }
+
+    /// Removes the given prefix from the string, if it starts with it.
+    #[inline]
+    pub fn trim_prefix(&self, prefix: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_trim_prefix)(&self.0, &prefix.0)) }
+    }
+
+    /// Removes the given suffix from the string, if it ends with it.
+    #[inline]
+    pub fn trim_suffix(&self, suffix: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_trim_suffix)(&self.0, &suffix.0)) }
+    }
+
+    /// Pads the string on the left with spaces, up to `min_length` characters.
+    #[inline]
+    pub fn lpad(&self, min_length: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_lpad)(&self.0, min_length)) }
+    }
+
+    /// Pads the string on the right with spaces, up to `min_length` characters.
+    #[inline]
+    pub fn rpad(&self, min_length: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_rpad)(&self.0, min_length)) }
+    }
+
+    /// Formats a number to have exactly `digits` digits after the decimal point.
+    #[inline]
+    pub fn pad_decimals(&self, digits: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_pad_decimals)(&self.0, digits)) }
+    }
+
+    /// Formats a number to have exactly `digits` digits before the decimal point.
+    #[inline]
+    pub fn pad_zeros(&self, digits: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_pad_zeros)(&self.0, digits)) }
+    }
+
+    /// Returns the similarity index of the text compared to `other`, between `0.0`
+    /// (no similarity) and `1.0` (equal strings), based on the Sorensen-Dice coefficient.
+    #[inline]
+    pub fn similarity(&self, other: &GodotString) -> f32 {
+        unsafe { (get_api().godot_string_similarity)(&self.0, &other.0) }
+    }
+
+    /// Does a simple case-sensitive expression match, where `*` matches zero or more
+    /// arbitrary characters and `?` matches any single character except a period.
+    #[inline]
+    pub fn matches(&self, pattern: &GodotString) -> bool {
+        unsafe { (get_api().godot_string_match)(&self.0, &pattern.0) }
+    }
+
+    /// Does a simple case-insensitive expression match, where `*` matches zero or more
+    /// arbitrary characters and `?` matches any single character except a period.
+    #[inline]
+    pub fn matchn(&self, pattern: &GodotString) -> bool {
+        unsafe { (get_api().godot_string_matchn)(&self.0, &pattern.0) }
+    }
+
+    /// Formats the string with `values`, like GDScript's `%` operator, which supports
+    /// placeholders such as `%s`, `%d` or `%.2f`.
+    ///
+    /// # Errors
+    ///
+    /// Returns Godot's error message if the placeholders don't match `values`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use gdnative::prelude::*;
+    /// let values = VariantArray::new();
+    /// values.push("HP");
+    /// values.push(12.5);
+    ///
+    /// let formatted = GodotString::from("%s: %.1f").sprintf(&values).unwrap();
+    /// assert_eq!(formatted, GodotString::from("HP: 12.5"));
+    /// ```
+    #[inline]
+    pub fn sprintf<Own: Ownership>(&self, values: &VariantArray<Own>) -> Result<Self, Self> {
+        let mut error = false;
+        let result = unsafe {
+            GodotString((get_api().godot_string_sprintf)(
+                &self.0,
+                values.sys(),
+                &mut error,
+            ))
+        };
+
+        if error {
+            Err(result)
+        } else {
+            Ok(result)
+        }
+    }
+
+    /// Returns the string encoded as UTF-8, without a null terminator.
+    #[inline]
+    pub fn to_utf8_buffer(&self) -> PoolArray<u8> {
+        PoolArray::from_slice(self.to_utf8().as_bytes())
+    }
+
+    /// Returns the string encoded as ASCII, without a null terminator. Characters outside
+    /// of the ASCII range are not preserved.
+    #[inline]
+    pub fn to_ascii_buffer(&self) -> PoolArray<u8> {
+        unsafe {
+            let ascii = Utf8String((get_api().godot_string_ascii)(&self.0));
+            PoolArray::from_slice(ascii.as_bytes())
+        }
+    }
+
+    /// Returns the MD5 hash of the string's UTF-8 representation as raw bytes.
+    #[inline]
+    pub fn md5_buffer(&self) -> PoolArray<u8> {
+        unsafe { PoolArray::from_sys((get_api().godot_string_md5_buffer)(&self.0)) }
+    }
+
+    /// Returns the SHA-256 hash of the string's UTF-8 representation as raw bytes.
+    #[inline]
+    pub fn sha256_buffer(&self) -> PoolArray<u8> {
+        unsafe { PoolArray::from_sys((get_api().godot_string_sha256_buffer)(&self.0)) }
+    }
+
     /// Formats the string by replacing all occurrences of a key in the string with the
     /// corresponding value. The method can handle arrays or dictionaries for the key/value pairs.
     ///
@@ -453,6 +673,94 @@ impl Index<usize> for GodotString {
     }
 }
 
+/// Iterator over the substrings of a `GodotString`.
+///
+/// This struct is created by the `split`, `rsplit` and `words` methods on `GodotString`.
+#[derive(Debug)]
+pub struct Split {
+    iter: std::vec::IntoIter<GodotString>,
+}
+
+impl Split {
+    fn from_array(array: VariantArray<Unique>) -> Self {
+        let strings = array
+            .into_iter()
+            .map(|item| item.coerce_to::<GodotString>())
+            .collect::<Vec<_>>();
+
+        Split {
+            iter: strings.into_iter(),
+        }
+    }
+
+    fn from_pool_array(array: PoolArray<GodotString>) -> Self {
+        Split {
+            iter: array.to_vec().into_iter(),
+        }
+    }
+}
+
+impl Iterator for Split {
+    type Item = GodotString;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Split {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl ExactSizeIterator for Split {}
+
+/// Iterator over the characters of a `GodotString`, in Godot's native encoding.
+///
+/// This struct is created by the `chars` method on `GodotString`.
+#[derive(Debug)]
+pub struct Chars<'a> {
+    string: &'a GodotString,
+    range: Range<usize>,
+}
+
+impl<'a> Chars<'a> {
+    fn get(&self, idx: usize) -> GodotChar {
+        unsafe { GodotChar((get_api().godot_string_ord_at)(&self.string.0, idx as i32)) }
+    }
+}
+
+impl<'a> Iterator for Chars<'a> {
+    type Item = GodotChar;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|idx| self.get(idx))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Chars<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|idx| self.get(idx))
+    }
+}
+
+impl<'a> ExactSizeIterator for Chars<'a> {}
+
 // TODO(#993): Is it useful to expose this type?
 // Could just make it an internal detail of how to convert to a rust string.
 #[doc(hidden)]
@@ -631,6 +939,15 @@ impl fmt::Debug for StringName {
     }
 }
 
+/// Converts the name with [`StringName::to_godot_string()`], which calls into the engine each
+/// time.
+impl From<&StringName> for GodotString {
+    #[inline]
+    fn from(name: &StringName) -> GodotString {
+        name.to_godot_string()
+    }
+}
+
 impl<S> From<S> for GodotString
 where
     S: AsRef<str>,
@@ -757,6 +1074,49 @@ godot_test!(test_string {
     assert_eq!(fmt_string2, GodotString::from("{0} {1}"));
 });
 
+godot_test!(test_string_manipulation {
+    use crate::core_types::{GodotString, VariantArray};
+
+    let s = |s: &str| GodotString::from(s);
+
+    assert_eq!(s("a,b,,c").split(&s(","), false).collect::<Vec<_>>(), vec![s("a"), s("b"), s("c")]);
+    assert_eq!(s("a,b,,c").split(&s(","), true).count(), 4);
+    assert_eq!(
+        s("a,b,c").rsplit(&s(","), true, 1).collect::<Vec<_>>(),
+        vec![s("a,b"), s("c")],
+    );
+    assert_eq!(s(" hello  world\t").words().collect::<Vec<_>>(), vec![s("hello"), s("world")]);
+
+    assert_eq!(s("FooBar").chars().collect::<Vec<_>>().len(), 6);
+    assert!(s("FooBar").chars().rev().next().map_or(false, |c| c == 'r'));
+
+    assert_eq!(s("foo foo").replace(&s("foo"), &s("bar")), s("bar bar"));
+    assert_eq!(s("Foo foo").replacen(&s("foo"), &s("bar")), s("bar bar"));
+    assert_eq!(s("foo foo").replace_first(&s("foo"), &s("bar")), s("bar foo"));
+    assert_eq!(s("  foo  ").strip_edges(true, true), s("foo"));
+    assert_eq!(s("  foo  ").strip_edges(false, true), s("  foo"));
+    assert_eq!(s("foo").lpad(5), s("  foo"));
+    assert_eq!(s("foo").rpad(5), s("foo  "));
+    assert_eq!(s("1.5").pad_decimals(2), s("1.50"));
+    assert_eq!(s("15").pad_zeros(4), s("0015"));
+
+    assert!((s("foo").similarity(&s("foo")) - 1.0).abs() < f32::EPSILON);
+    assert!(s("config.cfg").matches(&s("*.cfg")));
+    assert!(!s("config.CFG").matches(&s("*.cfg")));
+    assert!(s("config.CFG").matchn(&s("*.cfg")));
+
+    let values = VariantArray::new();
+    values.push("HP");
+    values.push(12.5);
+    assert_eq!(s("%s: %.1f").sprintf(&values), Ok(s("HP: 12.5")));
+    assert!(s("%s %s %s").sprintf(&values).is_err());
+
+    assert_eq!(s("foo").to_utf8_buffer().to_vec(), b"foo".to_vec());
+    assert_eq!(s("foo").to_ascii_buffer().to_vec(), b"foo".to_vec());
+    assert_eq!(s("foo").md5_buffer().len(), 16);
+    assert_eq!(s("foo").sha256_buffer().len(), 32);
+});
+
 godot_test!(test_string_name_eq {
     use crate::core_types::{GodotString, StringName};
 
@@ -800,3 +1160,22 @@ godot_test!(test_string_name_ord {
     assert_eq!(a_c, c_a.reverse());
     assert_eq!(b_c, c_b.reverse());
 });
+
+godot_test!(test_string_name_macro {
+    use crate::core_types::{GodotString, StringName, ToVariant};
+
+    fn cached() -> &'static StringName {
+        string_name!("body_entered")
+    }
+
+    let first = cached();
+    let second = cached();
+    assert!(std::ptr::eq(first, second));
+    assert_eq!(&StringName::from_str("body_entered"), first);
+    assert_eq!(GodotString::from("body_entered"), GodotString::from(first));
+
+    let mut variant = GodotString::from("foo").to_variant();
+    assert!(variant.has_method(string_name!("to_upper")));
+    let upper = unsafe { variant.call(string_name!("to_upper"), &[]) };
+    assert_eq!(Ok("FOO".to_variant()), upper);
+});
@@ -0,0 +1,426 @@
+use std::fmt;
+use std::iter::{Extend, FromIterator};
+use std::marker::PhantomData;
+
+use crate::core_types::{
+    FromVariant, FromVariantError, OwnedToVariant, ToVariant, Variant, VariantArray,
+};
+use crate::object::NewRef;
+
+use crate::object::ownership::*;
+
+/// A `VariantArray` whose elements are known to be convertible to `T`.
+///
+/// Element types are checked when the array is created from a `VariantArray` or a `Variant`,
+/// and elements are converted on access. As a result, this is a thin wrapper that can be
+/// passed to and from Godot without copying its contents into a `Vec`.
+///
+/// Since the underlying array is reference-counted, it can still be modified through other
+/// references, including ones in GDScript. Element access is thus checked as well, returning
+/// `FromVariantError::InvalidItem` for elements that are no longer valid.
+///
+/// # Example
+///
+/// ```ignore
+/// #[method]
+/// fn total_damage(&self, hits: TypedArray<i64>) -> i64 {
+///     hits.iter().map(|hit| hit.unwrap_or(0)).sum()
+/// }
+/// ```
+pub struct TypedArray<T, Own: Ownership = Shared> {
+    array: VariantArray<Own>,
+    _marker: PhantomData<T>,
+}
+
+/// Operations allowed on all typed arrays at any point in time.
+impl<T, Own: Ownership> TypedArray<T, Own> {
+    /// Wraps `array`, checking that all of its elements can be converted to `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FromVariantError::InvalidItem` with the index of the first element that
+    /// can't be converted.
+    #[inline]
+    pub fn from_variant_array(array: VariantArray<Own>) -> Result<Self, FromVariantError>
+    where
+        T: FromVariant,
+    {
+        for (index, item) in array.iter().enumerate() {
+            T::from_variant(&item).map_err(|error| FromVariantError::InvalidItem {
+                index,
+                error: Box::new(error),
+            })?;
+        }
+
+        Ok(TypedArray {
+            array,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Wraps `array` without checking its elements.
+    ///
+    /// Elements are still checked on access, so this can't lead to undefined behavior,
+    /// but access to invalid elements will return errors.
+    #[inline]
+    pub fn from_variant_array_unchecked(array: VariantArray<Own>) -> Self {
+        TypedArray {
+            array,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the underlying `VariantArray`.
+    #[inline]
+    pub fn as_variant_array(&self) -> &VariantArray<Own> {
+        &self.array
+    }
+
+    /// Returns the underlying `VariantArray`.
+    #[inline]
+    pub fn into_variant_array(self) -> VariantArray<Own> {
+        self.array
+    }
+
+    /// Returns `true` if the array contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+
+    /// Returns the number of elements in the array.
+    #[inline]
+    pub fn len(&self) -> i32 {
+        self.array.len()
+    }
+
+    /// Returns a copy of the element at the given offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FromVariantError::InvalidItem` if the element can't be converted to `T`.
+    ///
+    /// # Panics
+    ///
+    /// If `idx` is out of bounds.
+    #[inline]
+    pub fn get(&self, idx: i32) -> Result<T, FromVariantError>
+    where
+        T: FromVariant,
+    {
+        convert_item(idx, &self.array.get(idx))
+    }
+
+    /// Sets the value of the element at the given offset.
+    ///
+    /// # Panics
+    ///
+    /// If `idx` is out of bounds.
+    #[inline]
+    pub fn set(&self, idx: i32, val: T)
+    where
+        T: OwnedToVariant,
+    {
+        self.array.set(idx, val)
+    }
+
+    /// Returns true if the array contains the specified value.
+    #[inline]
+    pub fn contains(&self, what: &T) -> bool
+    where
+        T: ToVariant,
+    {
+        self.array.contains(what)
+    }
+
+    /// Searches the array for a value and returns its index.
+    /// Pass an initial search index as the second argument.
+    /// Returns `-1` if value is not found.
+    #[inline]
+    pub fn find(&self, what: &T, from: i32) -> i32
+    where
+        T: ToVariant,
+    {
+        self.array.find(what, from)
+    }
+
+    /// Create a copy of the array.
+    ///
+    /// This creates a new array and is **not** a cheap reference count
+    /// increment.
+    #[inline]
+    pub fn duplicate(&self) -> TypedArray<T, Unique> {
+        TypedArray::from_variant_array_unchecked(self.array.duplicate())
+    }
+
+    /// Returns an iterator through all elements in the array, converted to `T`.
+    ///
+    /// `TypedArray` is reference-counted and have interior mutability in Rust parlance.
+    /// Modifying the same underlying collection while observing the safety assumptions will
+    /// not violate memory safely, but may lead to surprising behavior in the iterator.
+    #[inline]
+    pub fn iter(&self) -> TypedArrayIter<'_, T, Own> {
+        self.into_iter()
+    }
+}
+
+/// Operations allowed on typed arrays that can only be referenced to from the current thread.
+impl<T, Own: LocalThreadOwnership> TypedArray<T, Own> {
+    /// Clears the array, resizing to 0.
+    #[inline]
+    pub fn clear(&self) {
+        self.array.clear()
+    }
+
+    /// Removes the element at `idx`.
+    #[inline]
+    pub fn remove(&self, idx: i32) {
+        self.array.remove(idx)
+    }
+
+    /// Appends an element at the end of the array.
+    #[inline]
+    pub fn push(&self, val: T)
+    where
+        T: OwnedToVariant,
+    {
+        self.array.push(val)
+    }
+
+    /// Appends an element to the front of the array.
+    #[inline]
+    pub fn push_front(&self, val: T)
+    where
+        T: OwnedToVariant,
+    {
+        self.array.push_front(val)
+    }
+
+    /// Inserts a new element at a given position in the array.
+    #[inline]
+    pub fn insert(&self, at: i32, val: T)
+    where
+        T: OwnedToVariant,
+    {
+        self.array.insert(at, val)
+    }
+
+    /// Removes an element at the end of the array, returning `None` if the array is empty.
+    #[inline]
+    pub fn pop(&self) -> Option<Result<T, FromVariantError>>
+    where
+        T: FromVariant,
+    {
+        let idx = self.array.len() - 1;
+        (idx >= 0).then(|| convert_item(idx, &self.array.pop()))
+    }
+}
+
+/// Operations allowed on non-unique typed arrays.
+impl<T, Own: NonUniqueOwnership> TypedArray<T, Own> {
+    /// Assume that this is the only reference to this array, on which
+    /// operations that change the container size can be safely performed.
+    ///
+    /// # Safety
+    ///
+    /// See [`VariantArray::assume_unique`].
+    #[inline]
+    pub unsafe fn assume_unique(self) -> TypedArray<T, Unique> {
+        TypedArray::from_variant_array_unchecked(self.array.assume_unique())
+    }
+}
+
+/// Operations allowed on unique typed arrays.
+impl<T> TypedArray<T, Unique> {
+    /// Creates an empty `TypedArray`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_variant_array_unchecked(VariantArray::new())
+    }
+
+    /// Put this array under the "shared" access type.
+    #[inline]
+    pub fn into_shared(self) -> TypedArray<T, Shared> {
+        TypedArray::from_variant_array_unchecked(self.array.into_shared())
+    }
+
+    /// Put this array under the "thread-local" access type.
+    #[inline]
+    pub fn into_thread_local(self) -> TypedArray<T, ThreadLocal> {
+        TypedArray::from_variant_array_unchecked(self.array.into_thread_local())
+    }
+}
+
+/// Operations allowed on typed arrays that might be shared between different threads.
+impl<T> TypedArray<T, Shared> {
+    /// Create a new shared typed array.
+    #[inline]
+    pub fn new_shared() -> Self {
+        TypedArray::<T, Unique>::new().into_shared()
+    }
+}
+
+/// Operations allowed on typed arrays that may only be shared on the current thread.
+impl<T> TypedArray<T, ThreadLocal> {
+    /// Create a new thread-local typed array.
+    #[inline]
+    pub fn new_thread_local() -> Self {
+        TypedArray::<T, Unique>::new().into_thread_local()
+    }
+}
+
+fn convert_item<T: FromVariant>(idx: i32, item: &Variant) -> Result<T, FromVariantError> {
+    T::from_variant(item).map_err(|error| FromVariantError::InvalidItem {
+        index: idx as usize,
+        error: Box::new(error),
+    })
+}
+
+impl<T> Default for TypedArray<T, Unique> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Default for TypedArray<T, Shared> {
+    #[inline]
+    fn default() -> Self {
+        Self::new_shared()
+    }
+}
+
+impl<T> Default for TypedArray<T, ThreadLocal> {
+    #[inline]
+    fn default() -> Self {
+        Self::new_thread_local()
+    }
+}
+
+impl<T, Own: NonUniqueOwnership> NewRef for TypedArray<T, Own> {
+    #[inline]
+    fn new_ref(&self) -> Self {
+        Self::from_variant_array_unchecked(self.array.new_ref())
+    }
+}
+
+impl<T, Own: Ownership> fmt::Debug for TypedArray<T, Own> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.array.fmt(f)
+    }
+}
+
+impl<T> ToVariant for TypedArray<T, Shared> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.array.to_variant()
+    }
+}
+
+impl<T> OwnedToVariant for TypedArray<T, Unique> {
+    #[inline]
+    fn owned_to_variant(self) -> Variant {
+        self.array.owned_to_variant()
+    }
+}
+
+impl<T: FromVariant> FromVariant for TypedArray<T, Shared> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        VariantArray::from_variant(variant).and_then(Self::from_variant_array)
+    }
+}
+
+impl<T: OwnedToVariant> FromIterator<T> for TypedArray<T, Unique> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut arr = Self::new();
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<T: OwnedToVariant, Own: LocalThreadOwnership> Extend<T> for TypedArray<T, Own> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+/// Iterator through all elements of a `TypedArray`, converted to `T`.
+///
+/// This struct is created by the `iter` method on `TypedArray`.
+pub struct TypedArrayIter<'a, T, Own: Ownership> {
+    arr: &'a TypedArray<T, Own>,
+    range: std::ops::Range<i32>,
+}
+
+impl<'a, T: FromVariant, Own: Ownership> Iterator for TypedArrayIter<'a, T, Own> {
+    type Item = Result<T, FromVariantError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|idx| self.arr.get(idx))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, T: FromVariant, Own: Ownership> IntoIterator for &'a TypedArray<T, Own> {
+    type Item = Result<T, FromVariantError>;
+    type IntoIter = TypedArrayIter<'a, T, Own>;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        TypedArrayIter {
+            range: 0..self.len(),
+            arr: self,
+        }
+    }
+}
+
+godot_test!(test_typed_array {
+    let array = TypedArray::<i64, Unique>::new(); // []
+    assert!(array.is_empty());
+
+    array.push(42); // [42]
+    array.push(1337); // [42, 1337]
+    array.insert(0, 512); // [512, 42, 1337]
+
+    assert_eq!(array.len(), 3);
+    assert_eq!(array.get(1), Ok(42));
+    assert!(array.contains(&1337));
+    assert_eq!(array.find(&1337, 0), 2);
+    assert_eq!(
+        array.iter().collect::<Result<Vec<_>, _>>(),
+        Ok(vec![512, 42, 1337]),
+    );
+
+    let variant = array.owned_to_variant();
+    let shared = TypedArray::<i64>::from_variant(&variant).expect("should be all integers");
+    assert_eq!(shared.get(2), Ok(1337));
+    assert!(TypedArray::<String>::from_variant(&variant).is_err());
+
+    let mixed = VariantArray::new();
+    mixed.push(1);
+    mixed.push("two");
+    match TypedArray::<i64, _>::from_variant_array(mixed) {
+        Err(FromVariantError::InvalidItem { index, .. }) => assert_eq!(index, 1),
+        other => panic!("expected InvalidItem, got {other:?}"),
+    }
+
+    let unchecked = TypedArray::<i64, _>::from_variant_array_unchecked(
+        vec![Variant::new(1), Variant::new("two")].into_iter().collect::<VariantArray<Unique>>(),
+    );
+    assert_eq!(unchecked.pop().map(|r| r.is_err()), Some(true));
+    assert_eq!(unchecked.pop(), Some(Ok(1)));
+    assert_eq!(unchecked.pop(), None);
+
+    let collected = (1..=3).collect::<TypedArray<i64, Unique>>();
+    assert_eq!(collected.get(2), Ok(3));
+});
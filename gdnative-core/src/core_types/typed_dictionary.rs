@@ -0,0 +1,373 @@
+use std::fmt;
+use std::iter::{Extend, FromIterator};
+use std::marker::PhantomData;
+
+use crate::core_types::{
+    Dictionary, FromVariant, FromVariantError, IterAs, OwnedToVariant, ToVariant,
+    ToVariantDictionary, ToVariantEq, Variant,
+};
+use crate::object::NewRef;
+
+use crate::object::ownership::*;
+
+/// A `Dictionary` whose keys and values are known to be convertible to `K` and `V`.
+///
+/// Entry types are checked when the dictionary is created from a `Dictionary` or a `Variant`,
+/// and entries are converted on access. As a result, this is a thin wrapper that can be
+/// passed to and from Godot without copying its contents into a `HashMap`.
+///
+/// Since the underlying dictionary is reference-counted, it can still be modified through
+/// other references. Entry access is thus checked as well. Errors for invalid entries are
+/// reported as `FromVariantError::InvalidItem`, with the index of the entry in iteration order,
+/// or as `FromVariantError::InvalidEntry` with the key when looked up by key.
+pub struct TypedDictionary<K, V, Own: Ownership = Shared> {
+    dict: Dictionary<Own>,
+    _marker: PhantomData<(K, V)>,
+}
+
+/// Operations allowed on all typed dictionaries at any point in time.
+impl<K, V, Own: Ownership> TypedDictionary<K, V, Own> {
+    /// Wraps `dict`, checking that all of its keys and values can be converted to `K` and `V`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FromVariantError::InvalidItem` with the index of the first entry that
+    /// can't be converted.
+    #[inline]
+    pub fn from_dictionary(dict: Dictionary<Own>) -> Result<Self, FromVariantError>
+    where
+        K: FromVariant,
+        V: FromVariant,
+    {
+        for entry in dict.iter_as::<K, V>() {
+            entry?;
+        }
+
+        Ok(Self::from_dictionary_unchecked(dict))
+    }
+
+    /// Wraps `dict` without checking its entries.
+    ///
+    /// Entries are still checked on access, so this can't lead to undefined behavior,
+    /// but access to invalid entries will return errors.
+    #[inline]
+    pub fn from_dictionary_unchecked(dict: Dictionary<Own>) -> Self {
+        TypedDictionary {
+            dict,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the underlying `Dictionary`.
+    #[inline]
+    pub fn as_dictionary(&self) -> &Dictionary<Own> {
+        &self.dict
+    }
+
+    /// Returns the underlying `Dictionary`.
+    #[inline]
+    pub fn into_dictionary(self) -> Dictionary<Own> {
+        self.dict
+    }
+
+    /// Returns `true` if the dictionary contains no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.dict.is_empty()
+    }
+
+    /// Returns the number of entries in the dictionary.
+    #[inline]
+    pub fn len(&self) -> i32 {
+        self.dict.len()
+    }
+
+    /// Returns true if the dictionary contains the specified key.
+    #[inline]
+    pub fn contains(&self, key: &K) -> bool
+    where
+        K: ToVariant + ToVariantEq,
+    {
+        self.dict.contains(key)
+    }
+
+    /// Returns a copy of the value corresponding to the key if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FromVariantError::InvalidEntry` with the key if the value can't be converted
+    /// to `V`.
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<Result<V, FromVariantError>>
+    where
+        K: ToVariant + ToVariantEq,
+        V: FromVariant,
+    {
+        self.dict.get(key).map(|value| {
+            V::from_variant(&value).map_err(|error| FromVariantError::InvalidEntry {
+                key: key.to_variant().to_string(),
+                error: Box::new(error),
+            })
+        })
+    }
+
+    /// Update an existing value corresponding to the key.
+    ///
+    /// # Panics
+    ///
+    /// If the key does not exist.
+    #[inline]
+    pub fn update(&self, key: &K, val: V)
+    where
+        K: ToVariant + ToVariantEq,
+        V: OwnedToVariant,
+    {
+        self.dict.update(key, val)
+    }
+
+    /// Create a copy of the dictionary.
+    ///
+    /// This creates a new dictionary and is **not** a cheap reference count
+    /// increment.
+    #[inline]
+    pub fn duplicate(&self) -> TypedDictionary<K, V, Unique> {
+        TypedDictionary::from_dictionary_unchecked(self.dict.duplicate())
+    }
+
+    /// Returns an iterator through all entries in the dictionary, converted to `(K, V)`.
+    ///
+    /// `TypedDictionary` is reference-counted and have interior mutability in Rust parlance.
+    /// Modifying the same underlying collection while observing the safety assumptions will
+    /// not violate memory safely, but may lead to surprising behavior in the iterator.
+    #[inline]
+    pub fn iter(&self) -> IterAs<'_, K, V, Own>
+    where
+        K: FromVariant,
+        V: FromVariant,
+    {
+        self.dict.iter_as()
+    }
+}
+
+/// Operations allowed on typed dictionaries that can only be referenced to from the current
+/// thread.
+impl<K, V, Own: LocalThreadOwnership> TypedDictionary<K, V, Own> {
+    /// Inserts or updates the value of the entry corresponding to the key.
+    #[inline]
+    pub fn insert(&self, key: K, val: V)
+    where
+        K: OwnedToVariant + ToVariantEq,
+        V: OwnedToVariant,
+    {
+        self.dict.insert(key, val)
+    }
+
+    /// Erase an entry in the dictionary by the specified key.
+    #[inline]
+    pub fn erase(&self, key: &K)
+    where
+        K: ToVariant + ToVariantEq,
+    {
+        self.dict.erase(key)
+    }
+
+    /// Clears the dictionary, removing all entries.
+    #[inline]
+    pub fn clear(&self) {
+        self.dict.clear()
+    }
+}
+
+/// Operations allowed on non-unique typed dictionaries.
+impl<K, V, Own: NonUniqueOwnership> TypedDictionary<K, V, Own> {
+    /// Assume that this is the only reference to this dictionary, on which
+    /// operations that change the container size can be safely performed.
+    ///
+    /// # Safety
+    ///
+    /// See [`Dictionary::assume_unique`].
+    #[inline]
+    pub unsafe fn assume_unique(self) -> TypedDictionary<K, V, Unique> {
+        TypedDictionary::from_dictionary_unchecked(self.dict.assume_unique())
+    }
+}
+
+/// Operations allowed on unique typed dictionaries.
+impl<K, V> TypedDictionary<K, V, Unique> {
+    /// Creates an empty `TypedDictionary`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_dictionary_unchecked(Dictionary::new())
+    }
+
+    /// Put this dictionary under the "shared" access type.
+    #[inline]
+    pub fn into_shared(self) -> TypedDictionary<K, V, Shared> {
+        TypedDictionary::from_dictionary_unchecked(self.dict.into_shared())
+    }
+
+    /// Put this dictionary under the "thread-local" access type.
+    #[inline]
+    pub fn into_thread_local(self) -> TypedDictionary<K, V, ThreadLocal> {
+        TypedDictionary::from_dictionary_unchecked(self.dict.into_thread_local())
+    }
+}
+
+/// Operations allowed on typed dictionaries that might be shared between different threads.
+impl<K, V> TypedDictionary<K, V, Shared> {
+    /// Create a new shared typed dictionary.
+    #[inline]
+    pub fn new_shared() -> Self {
+        TypedDictionary::<K, V, Unique>::new().into_shared()
+    }
+}
+
+/// Operations allowed on typed dictionaries that may only be shared on the current thread.
+impl<K, V> TypedDictionary<K, V, ThreadLocal> {
+    /// Create a new thread-local typed dictionary.
+    #[inline]
+    pub fn new_thread_local() -> Self {
+        TypedDictionary::<K, V, Unique>::new().into_thread_local()
+    }
+}
+
+impl<K, V> Default for TypedDictionary<K, V, Unique> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Default for TypedDictionary<K, V, Shared> {
+    #[inline]
+    fn default() -> Self {
+        Self::new_shared()
+    }
+}
+
+impl<K, V> Default for TypedDictionary<K, V, ThreadLocal> {
+    #[inline]
+    fn default() -> Self {
+        Self::new_thread_local()
+    }
+}
+
+impl<K, V, Own: NonUniqueOwnership> NewRef for TypedDictionary<K, V, Own> {
+    #[inline]
+    fn new_ref(&self) -> Self {
+        Self::from_dictionary_unchecked(self.dict.new_ref())
+    }
+}
+
+impl<K, V, Own: Ownership> fmt::Debug for TypedDictionary<K, V, Own> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.dict.fmt(f)
+    }
+}
+
+impl<K, V> ToVariant for TypedDictionary<K, V, Shared> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.dict.to_variant()
+    }
+}
+
+impl<K, V> OwnedToVariant for TypedDictionary<K, V, Unique> {
+    #[inline]
+    fn owned_to_variant(self) -> Variant {
+        self.dict.owned_to_variant()
+    }
+}
+
+impl<K, V, Own: Ownership> ToVariantDictionary for TypedDictionary<K, V, Own> {}
+
+impl<K: FromVariant, V: FromVariant> FromVariant for TypedDictionary<K, V, Shared> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Dictionary::from_variant(variant).and_then(Self::from_dictionary)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for TypedDictionary<K, V, Unique>
+where
+    K: OwnedToVariant + ToVariantEq,
+    V: OwnedToVariant,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut dict = Self::new();
+        dict.extend(iter);
+        dict
+    }
+}
+
+impl<K, V, Own: LocalThreadOwnership> Extend<(K, V)> for TypedDictionary<K, V, Own>
+where
+    K: OwnedToVariant + ToVariantEq,
+    V: OwnedToVariant,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K: FromVariant, V: FromVariant, Own: Ownership> IntoIterator
+    for &'a TypedDictionary<K, V, Own>
+{
+    type Item = Result<(K, V), FromVariantError>;
+    type IntoIter = IterAs<'a, K, V, Own>;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+godot_test!(test_typed_dictionary {
+    let dict = TypedDictionary::<String, i64, Unique>::new();
+    assert!(dict.is_empty());
+
+    dict.insert("foo".to_string(), 42);
+    dict.insert("bar".to_string(), 1337);
+
+    assert_eq!(dict.len(), 2);
+    assert!(dict.contains(&"foo".to_string()));
+    assert_eq!(dict.get(&"bar".to_string()), Some(Ok(1337)));
+    assert_eq!(dict.get(&"baz".to_string()), None);
+
+    let mut entries = dict.iter().collect::<Result<Vec<_>, _>>().expect("should be valid");
+    entries.sort();
+    assert_eq!(entries, vec![("bar".to_string(), 1337), ("foo".to_string(), 42)]);
+
+    dict.erase(&"bar".to_string());
+    assert!(!dict.contains(&"bar".to_string()));
+
+    let variant = dict.owned_to_variant();
+    let shared = TypedDictionary::<String, i64>::from_variant(&variant).expect("should be valid");
+    assert_eq!(shared.get(&"foo".to_string()), Some(Ok(42)));
+    assert!(TypedDictionary::<i64, i64>::from_variant(&variant).is_err());
+
+    let mixed = Dictionary::new();
+    mixed.insert("one", 1);
+    mixed.insert("two", "two");
+    match TypedDictionary::<String, i64, _>::from_dictionary(mixed) {
+        Err(FromVariantError::InvalidItem { index, .. }) => assert_eq!(index, 1),
+        other => panic!("expected InvalidItem, got {other:?}"),
+    }
+
+    let changed = TypedDictionary::<String, i64, Unique>::new();
+    changed.insert("one".to_string(), 1);
+    changed.as_dictionary().insert("two", "two");
+    match changed.get(&"two".to_string()) {
+        Some(Err(FromVariantError::InvalidEntry { key, .. })) => assert_eq!(key, "two"),
+        other => panic!("expected InvalidEntry, got {other:?}"),
+    }
+
+    let collected = vec![(1, 2), (3, 4)]
+        .into_iter()
+        .collect::<TypedDictionary<i64, i64, Unique>>();
+    assert_eq!(collected.get(&3), Some(Ok(4)));
+});
@@ -1,20 +1,52 @@
 use crate::*;
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::default::Default;
 use std::fmt;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
 use std::mem::{forget, transmute};
+use std::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::path::{Path, PathBuf};
 use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use indexmap::{IndexMap, IndexSet};
 
 use crate::core_types::*;
 use crate::object::ownership::*;
 use crate::object::*;
 use crate::private::{get_api, ManuallyManagedClassPlaceholder};
 
+pub mod binary;
+mod borrowed;
+pub mod config;
+pub mod json;
+mod key;
+pub mod scene;
+pub mod text;
+mod value;
+
+pub use borrowed::*;
+pub use key::*;
+pub use value::*;
+
+#[cfg(feature = "serde")]
+mod de;
+#[cfg(feature = "serde")]
+mod ser;
 #[cfg(feature = "serde")]
 mod serialize;
 
+#[cfg(feature = "serde")]
+pub use de::{from_variant, VariantDeserializer};
+#[cfg(feature = "serde")]
+pub use ser::{to_variant, SerializeError, VariantSerializer};
+
 /// A `Variant` can represent all Godot values (core types or `Object` class instances).
 ///
 /// The underlying data is either stored inline or reference-counted on the heap,
@@ -23,7 +55,8 @@ mod serialize;
 /// If you compile godot-rust with the `serde` feature enabled, you will have
 /// access to serialization/deserialization support: the traits `Serialize`
 /// and `Deserialize` will be automatically implemented on [`VariantDispatch`]
-/// as well as most of the types in [`core_types`].
+/// as well as most of the types in [`core_types`]. Any `Serialize` type can also be
+/// converted to and from a `Variant` directly, using [`to_variant`] and [`from_variant`].
 pub struct Variant(pub(crate) sys::godot_variant);
 
 macro_rules! impl_coerce_from_variant_inner {
@@ -498,6 +531,15 @@ impl Variant {
         CallError::from_sys(err.error).map(|_| Variant::from_sys(variant))
     }
 
+    /// Returns `true` if `self` and `other` are equal when used as keys of a `Dictionary`.
+    ///
+    /// Unlike `==`, this never considers values of different types equal, and treats NaN
+    /// floats as equal to each other, including in arrays. See also [`VariantKey`].
+    #[inline]
+    pub fn hash_compare(&self, other: &Variant) -> bool {
+        unsafe { (get_api().godot_variant_hash_compare)(&self.0, &other.0) }
+    }
+
     /// Evaluates a variant operator on `self` and `rhs` and returns the result on success.
     ///
     /// # Errors
@@ -526,6 +568,130 @@ impl Variant {
         }
     }
 
+    /// Returns `self + rhs`, with the same semantics as in GDScript.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if the operation is not valid for the types of the operands.
+    #[inline]
+    pub fn checked_add(&self, rhs: &Variant) -> Result<Variant, InvalidOp> {
+        self.evaluate(VariantOperator::Add, rhs)
+    }
+
+    /// Returns `self - rhs`, with the same semantics as in GDScript.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if the operation is not valid for the types of the operands.
+    #[inline]
+    pub fn checked_sub(&self, rhs: &Variant) -> Result<Variant, InvalidOp> {
+        self.evaluate(VariantOperator::Subtract, rhs)
+    }
+
+    /// Returns `self * rhs`, with the same semantics as in GDScript.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if the operation is not valid for the types of the operands.
+    #[inline]
+    pub fn checked_mul(&self, rhs: &Variant) -> Result<Variant, InvalidOp> {
+        self.evaluate(VariantOperator::Multiply, rhs)
+    }
+
+    /// Returns `self / rhs`, with the same semantics as in GDScript.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if the operation is not valid for the types of the operands,
+    /// or on integer division by zero.
+    #[inline]
+    pub fn checked_div(&self, rhs: &Variant) -> Result<Variant, InvalidOp> {
+        self.evaluate(VariantOperator::Divide, rhs)
+    }
+
+    /// Returns `self % rhs`, with the same semantics as in GDScript. For strings, this is
+    /// formatting with the values in `rhs`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if the operation is not valid for the types of the operands,
+    /// or on integer division by zero.
+    #[inline]
+    pub fn checked_rem(&self, rhs: &Variant) -> Result<Variant, InvalidOp> {
+        self.evaluate(VariantOperator::Module, rhs)
+    }
+
+    /// Returns `-self`, with the same semantics as in GDScript.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if the operation is not valid for the type of `self`.
+    #[inline]
+    pub fn checked_neg(&self) -> Result<Variant, InvalidOp> {
+        self.evaluate(VariantOperator::Negate, &Variant::nil())
+    }
+
+    /// Returns `self < rhs`, with the same semantics as in GDScript.
+    ///
+    /// Unlike the `Ord` implementation of `Variant`, this does not order values of different
+    /// types, but compares numbers of different types by value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if the values can't be compared.
+    #[inline]
+    pub fn checked_lt(&self, rhs: &Variant) -> Result<bool, InvalidOp> {
+        self.evaluate_bool(VariantOperator::Less, rhs)
+    }
+
+    /// Returns `self <= rhs`, with the same semantics as in GDScript.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if the values can't be compared.
+    #[inline]
+    pub fn checked_le(&self, rhs: &Variant) -> Result<bool, InvalidOp> {
+        self.evaluate_bool(VariantOperator::LessEqual, rhs)
+    }
+
+    /// Returns `self > rhs`, with the same semantics as in GDScript.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if the values can't be compared.
+    #[inline]
+    pub fn checked_gt(&self, rhs: &Variant) -> Result<bool, InvalidOp> {
+        self.evaluate_bool(VariantOperator::Greater, rhs)
+    }
+
+    /// Returns `self >= rhs`, with the same semantics as in GDScript.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if the values can't be compared.
+    #[inline]
+    pub fn checked_ge(&self, rhs: &Variant) -> Result<bool, InvalidOp> {
+        self.evaluate_bool(VariantOperator::GreaterEqual, rhs)
+    }
+
+    /// Returns `self in container`, with the same semantics as in GDScript: whether `self`
+    /// is an element of an array, a key of a dictionary, a substring of a string, or a
+    /// property of an object.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(InvalidOp)` if `container` can't contain `self`.
+    #[inline]
+    pub fn in_(&self, container: &Variant) -> Result<bool, InvalidOp> {
+        self.evaluate_bool(VariantOperator::In, container)
+    }
+
+    #[inline]
+    fn evaluate_bool(&self, op: VariantOperator, rhs: &Variant) -> Result<bool, InvalidOp> {
+        self.evaluate(op, rhs)
+            .and_then(|result| result.to::<bool>().ok_or(InvalidOp))
+    }
+
     /// Get a reference to a `godot-rust` Variant from a raw sys::pointer.
     ///
     /// # Safety
@@ -623,6 +789,95 @@ impl_basic_traits_as_sys!(
 
 impl Eq for Variant {}
 
+macro_rules! impl_variant_binary_op {
+    ($($Trait:ident :: $method:ident => $checked:ident;)*) => {
+        $(
+            #[doc = concat!(
+                "Evaluates the operator like GDScript. Use [`Variant::",
+                stringify!($checked),
+                "`] for a non-panicking version.\n\n# Panics\n\n",
+                "If the operation is not valid for the types of the operands."
+            )]
+            impl<'a, 'b> std::ops::$Trait<&'b Variant> for &'a Variant {
+                type Output = Variant;
+
+                #[inline]
+                #[track_caller]
+                fn $method(self, rhs: &'b Variant) -> Variant {
+                    match self.$checked(rhs) {
+                        Ok(result) => result,
+                        Err(InvalidOp) => panic!(
+                            "invalid operands for {}: {:?} and {:?}",
+                            stringify!($method),
+                            self.get_type(),
+                            rhs.get_type(),
+                        ),
+                    }
+                }
+            }
+
+            #[doc = concat!(
+                "Evaluates the operator like GDScript. Use [`Variant::",
+                stringify!($checked),
+                "`] for a non-panicking version.\n\n# Panics\n\n",
+                "If the operation is not valid for the types of the operands."
+            )]
+            impl std::ops::$Trait for Variant {
+                type Output = Variant;
+
+                #[inline]
+                #[track_caller]
+                fn $method(self, rhs: Variant) -> Variant {
+                    std::ops::$Trait::$method(&self, &rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_binary_op! {
+    Add::add => checked_add;
+    Sub::sub => checked_sub;
+    Mul::mul => checked_mul;
+    Div::div => checked_div;
+    Rem::rem => checked_rem;
+}
+
+/// Evaluates the operator like GDScript. Use [`Variant::checked_neg`] for a non-panicking
+/// version.
+///
+/// # Panics
+///
+/// If the operation is not valid for the type of the operand.
+impl<'a> std::ops::Neg for &'a Variant {
+    type Output = Variant;
+
+    #[inline]
+    #[track_caller]
+    fn neg(self) -> Variant {
+        match self.checked_neg() {
+            Ok(result) => result,
+            Err(InvalidOp) => panic!("invalid operand for neg: {:?}", self.get_type()),
+        }
+    }
+}
+
+/// Evaluates the operator like GDScript. Use [`Variant::checked_neg`] for a non-panicking
+/// version.
+///
+/// # Panics
+///
+/// If the operation is not valid for the type of the operand.
+impl std::ops::Neg for Variant {
+    type Output = Variant;
+
+    #[inline]
+    #[track_caller]
+    fn neg(self) -> Variant {
+        -&self
+    }
+}
+
 impl fmt::Display for Variant {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -739,6 +994,35 @@ godot_test!(
 /// Only applicable to field-less enums with a explicit primitive `#[repr]` type. Variants of
 /// types annotated with this attribute are represented as their primitive integral values.
 ///
+/// - `#[variant(tag = "type")]`
+///
+/// Variants are represented as internally tagged `Dictionary`s, with the name of the variant
+/// stored under the given key alongside the fields: `{ "type": "Hit", "damage": 3 }` for
+/// `Enum::Hit { damage: 3 }`. Unit variants are represented as `{ "type": "Variant" }`.
+/// Newtype variants merge the entries of their value, whose type must implement
+/// [`ToVariantDictionary`], into the `Dictionary`. Tuple variants are not supported, and
+/// neither are fields whose name is the same as the tag. The tag is inserted last, so entries
+/// of newtype values or flattened fields under the same key are replaced by it.
+///
+/// - `#[variant(tag = "t", content = "c")]`
+///
+/// Variants are represented as adjacently tagged `Dictionary`s, with the name of the variant
+/// and its value stored under separate keys: `{ "t": "Variant", "c": [a, b, c] }`. Unit
+/// variants are represented without the content key.
+///
+/// - `#[variant(untagged)]`
+///
+/// Variants are represented as their values alone, without any tag: `[a, b, c]` for
+/// `Enum::Variant(a, b, c)`. Unit variants are represented as `Nil`. When converting from
+/// `Variant`, each variant is tried in declaration order, and the first one that succeeds is
+/// returned.
+///
+/// - `#[variant(rename_all = "camelCase")]`
+///
+/// Rename all fields of a struct, or all variants of an enum, according to the given case
+/// convention. Supported values are `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`,
+/// `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"` and `"SCREAMING-KEBAB-CASE"`.
+///
 /// ### Field attributes
 ///
 /// - `#[variant(to_variant_with = "path::to::func")]`
@@ -769,6 +1053,29 @@ godot_test!(
 /// - `#[variant(skip)]`
 ///
 /// Convenience attribute that sets `skip_to_variant` and `skip_from_variant`.
+///
+/// - `#[variant(rename = "name")]`
+///
+/// Use the given name as the key of the field in the `Dictionary` representation, instead of
+/// the name of the field in Rust. Takes precedence over `rename_all`.
+///
+/// - `#[variant(default)]`
+///
+/// Use `Default::default()` for the field if its key is missing when converting from
+/// `Variant`. A key with a `Nil` value is not considered missing.
+///
+/// - `#[variant(default = "path::to::func")]`
+///
+/// Same as `#[variant(default)]`, but call the given function instead. The function's
+/// signature is expected to be `fn() -> T`. This is also used for fields skipped with
+/// `skip_from_variant`.
+///
+/// - `#[variant(flatten)]`
+///
+/// Merge the entries of the field, whose type must implement [`ToVariantDictionary`], into the
+/// `Dictionary` of the parent struct. When converting from `Variant`, the field is converted
+/// from a `Dictionary` containing the entries that are not claimed by other fields of the
+/// parent. Cannot be combined with `rename`, `default` or `to_variant_with`.
 pub trait ToVariant {
     fn to_variant(&self) -> Variant;
 }
@@ -817,6 +1124,25 @@ pub trait OwnedToVariant {
 /// ```
 pub trait ToVariantEq: Eq {}
 
+/// Trait for types that are always converted to a `Dictionary` by `ToVariant` or
+/// `OwnedToVariant`.
+///
+/// This is required for `#[variant(flatten)]` fields and for the values of newtype variants
+/// in internally tagged enums, whose entries are merged into another `Dictionary` when they are
+/// converted. Like [`ToVariantEq`], this can't be checked by the compiler, and has no methods.
+///
+/// ## Implementing `ToVariantDictionary`
+///
+/// The trait is implemented by the derive macros for structs with named fields, unit structs,
+/// and enums that are represented as externally, internally or adjacently tagged
+/// `Dictionary`s. Types with manual implementations of `ToVariant` that always return a
+/// `Dictionary` can implement it with an empty `impl`:
+///
+/// ```ignore
+/// impl ToVariantDictionary for MySettings {}
+/// ```
+pub trait ToVariantDictionary {}
+
 /// Types that can be converted from a `Variant`. Conversions are performed in Rust, and can be
 /// implemented for custom types.
 ///
@@ -926,12 +1252,32 @@ pub enum FromVariantError {
         index: usize,
         error: Box<FromVariantError>,
     },
+    /// Dictionary contains an invalid value for a key. The key is given in its string form.
+    InvalidEntry {
+        key: String,
+        error: Box<FromVariantError>,
+    },
+
+    /// Error indicating that the value does not match any variant of an untagged enum.
+    ///
+    /// Variants are tried in declaration order. `errors` contains the name of each variant that
+    /// was tried, along with the reason why it did not match.
+    NoMatchingEnumVariant {
+        errors: Vec<(&'static str, FromVariantError)>,
+    },
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 #[non_exhaustive]
 pub enum VariantEnumRepr {
+    /// `{ "Variant": value }`
     ExternallyTagged,
+    /// `{ "tag": "Variant", "field": value, ... }`
+    InternallyTagged,
+    /// `{ "tag": "Variant", "content": value }`
+    AdjacentlyTagged,
+    /// `value`
+    Untagged,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -1013,6 +1359,10 @@ impl fmt::Display for FromVariantError {
                             write!(f, "[{index}]")?;
                             next_error = error.as_ref();
                         }
+                        E::InvalidEntry { key, error } => {
+                            write!(f, "[{key:?}]")?;
+                            next_error = error.as_ref();
+                        }
                         _ => {
                             write!(f, ": {next_error}")?;
                             return Ok(());
@@ -1023,6 +1373,23 @@ impl fmt::Display for FromVariantError {
             E::InvalidItem { index, error } => {
                 write!(f, "invalid value for item at index {index}: {error}")
             }
+            E::InvalidEntry { key, error } => {
+                write!(f, "invalid value for key {key:?}: {error}")
+            }
+            E::NoMatchingEnumVariant { errors } => {
+                write!(f, "value does not match any variant of the untagged enum")?;
+                let mut first = true;
+                for (variant, error) in errors {
+                    if first {
+                        write!(f, ", tried: ")?;
+                        first = false;
+                    } else {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{variant} ({error})")?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -1068,6 +1435,7 @@ where
     }
 }
 impl<'a, T> ToVariantEq for &'a T where T: ToVariantEq + ?Sized {}
+impl<'a, T> ToVariantDictionary for &'a T where T: ToVariantDictionary + ?Sized {}
 
 impl ToVariantEq for Variant {}
 
@@ -1081,6 +1449,7 @@ where
     }
 }
 impl<'a, T> ToVariantEq for &'a mut T where T: ToVariantEq + ?Sized {}
+impl<'a, T> ToVariantDictionary for &'a mut T where T: ToVariantDictionary + ?Sized {}
 
 impl<T: GodotObject> ToVariant for Ref<T, Shared> {
     #[inline]
@@ -1310,6 +1679,8 @@ impl OwnedToVariant for Dictionary<Unique> {
     }
 }
 
+impl<Own: Ownership> ToVariantDictionary for Dictionary<Own> {}
+
 impl OwnedToVariant for VariantArray<Unique> {
     #[inline]
     fn owned_to_variant(self) -> Variant {
@@ -1599,7 +1970,7 @@ impl<T: FromVariant, E: FromVariant> FromVariant for Result<T, E> {
     }
 }
 
-impl<T: ToVariant> ToVariant for &[T] {
+impl<T: ToVariant> ToVariant for [T] {
     #[inline]
     fn to_variant(&self) -> Variant {
         let array = VariantArray::new();
@@ -1666,6 +2037,8 @@ impl<K: ToVariant + Hash + ToVariantEq, V: ToVariant> ToVariant for HashMap<K, V
     }
 }
 
+impl<K, V> ToVariantDictionary for HashMap<K, V> {}
+
 /// Expects a `Variant` populated with a `Dictionary` and tries to convert it into a `HashMap`.
 ///
 /// Since Rust's `HashMap` is unordered, there is no guarantee about the resulting element order.
@@ -1731,6 +2104,378 @@ impl<T: FromVariant + Eq + Hash> FromVariant for HashSet<T> {
     }
 }
 
+/// Converts the elements of `arr` with `T::from_variant`, reporting the index of the first
+/// element that fails.
+fn items_from_array<T: FromVariant, C: FromIterator<T>>(
+    arr: &VariantArray,
+) -> Result<C, FromVariantError> {
+    arr.iter()
+        .enumerate()
+        .map(|(index, item)| {
+            T::from_variant(&item).map_err(|e| FromVariantError::InvalidItem {
+                index,
+                error: Box::new(e),
+            })
+        })
+        .collect()
+}
+
+/// Converts the map to a `Dictionary`, wrapped in a `Variant`. The entries are inserted in key
+/// order.
+impl<K: ToVariant + ToVariantEq, V: ToVariant> ToVariant for BTreeMap<K, V> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let dict = Dictionary::new();
+        for (key, value) in self {
+            dict.insert(key, value);
+        }
+        dict.owned_to_variant()
+    }
+}
+
+impl<K, V> ToVariantDictionary for BTreeMap<K, V> {}
+
+/// Expects a `Variant` populated with a `Dictionary` and tries to convert it into a `BTreeMap`.
+impl<K: FromVariant + Ord, V: FromVariant> FromVariant for BTreeMap<K, V> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let dictionary = Dictionary::from_variant(variant)?;
+        let mut map = BTreeMap::new();
+        for (key, value) in dictionary.iter() {
+            map.insert(K::from_variant(&key)?, V::from_variant(&value)?);
+        }
+        Ok(map)
+    }
+}
+
+/// Converts the set to a `VariantArray` in ascending order, wrapped in a `Variant`.
+impl<T: ToVariant> ToVariant for BTreeSet<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let array = VariantArray::new();
+        for value in self {
+            array.push(value.to_variant());
+        }
+        array.owned_to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `VariantArray` and tries to convert it into a
+/// `BTreeSet`. Duplicate elements are allowed, and only kept once.
+impl<T: FromVariant + Ord> FromVariant for BTreeSet<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        items_from_array(&VariantArray::from_variant(variant)?)
+    }
+}
+
+/// Converts the deque to a `VariantArray` from front to back, wrapped in a `Variant`.
+impl<T: ToVariant> ToVariant for VecDeque<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let array = VariantArray::new();
+        for value in self {
+            array.push(value.to_variant());
+        }
+        array.owned_to_variant()
+    }
+}
+
+impl<T: FromVariant> FromVariant for VecDeque<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        items_from_array(&VariantArray::from_variant(variant)?)
+    }
+}
+
+impl<T: ToVariant, const N: usize> ToVariant for [T; N] {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_slice().to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `VariantArray` of exactly `N` elements.
+impl<T: FromVariant, const N: usize> FromVariant for [T; N] {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let arr = VariantArray::from_variant(variant)?;
+        let len = arr.len() as usize;
+        if len != N {
+            return Err(FromVariantError::InvalidLength { expected: N, len });
+        }
+
+        let vec: Vec<T> = items_from_array(&arr)?;
+        vec.try_into()
+            .map_err(|vec: Vec<T>| FromVariantError::InvalidLength {
+                expected: N,
+                len: vec.len(),
+            })
+    }
+}
+
+impl<T: ToVariant + ?Sized> ToVariant for Box<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        T::to_variant(self)
+    }
+}
+impl<T: ToVariantEq + ?Sized> ToVariantEq for Box<T> {}
+impl<T: ToVariantDictionary + ?Sized> ToVariantDictionary for Box<T> {}
+
+impl<T: FromVariant> FromVariant for Box<T> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        T::from_variant(variant).map(Box::new)
+    }
+}
+
+impl<T: FromVariant> FromVariant for Box<[T]> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        items_from_array(&VariantArray::from_variant(variant)?)
+    }
+}
+
+impl FromVariant for Box<str> {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        String::from_variant(variant).map(String::into_boxed_str)
+    }
+}
+
+/// Converts the shared value, like `T` itself. There is no `FromVariant` counterpart, since the
+/// conversion would always create a new, unshared value.
+impl<T: ToVariant + ?Sized> ToVariant for Rc<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        T::to_variant(self)
+    }
+}
+impl<T: ToVariantEq + ?Sized> ToVariantEq for Rc<T> {}
+impl<T: ToVariantDictionary + ?Sized> ToVariantDictionary for Rc<T> {}
+
+/// Converts the shared value, like `T` itself. There is no `FromVariant` counterpart, since the
+/// conversion would always create a new, unshared value.
+impl<T: ToVariant + ?Sized> ToVariant for Arc<T> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        T::to_variant(self)
+    }
+}
+impl<T: ToVariantEq + ?Sized> ToVariantEq for Arc<T> {}
+impl<T: ToVariantDictionary + ?Sized> ToVariantDictionary for Arc<T> {}
+
+impl<'a, B: ToVariant + ToOwned + ?Sized> ToVariant for Cow<'a, B> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        B::to_variant(self)
+    }
+}
+impl<'a, B: ToVariantEq + ToOwned + ?Sized> ToVariantEq for Cow<'a, B> {}
+
+/// Always returns `Cow::Owned`, since the value can't be borrowed from the `Variant`.
+impl<'a, B: ToOwned + ?Sized> FromVariant for Cow<'a, B>
+where
+    B::Owned: FromVariant,
+{
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        B::Owned::from_variant(variant).map(Cow::Owned)
+    }
+}
+
+/// Converts the character to a `GodotString` of length 1, since GDScript has no character type.
+impl ToVariant for char {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.encode_utf8(&mut [0; 4]).to_variant()
+    }
+}
+impl ToVariantEq for char {}
+
+/// Expects a `Variant` populated with a `GodotString` consisting of exactly one character.
+impl FromVariant for char {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let s = String::from_variant(variant)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(FromVariantError::InvalidLength {
+                expected: 1,
+                len: s.chars().count(),
+            }),
+        }
+    }
+}
+
+macro_rules! impl_variant_for_non_zero {
+    (
+        $($ty:ty : $int:ty)*
+    ) => {
+        $(
+            impl ToVariant for $ty {
+                #[inline]
+                fn to_variant(&self) -> Variant {
+                    self.get().to_variant()
+                }
+            }
+            impl ToVariantEq for $ty {}
+
+            /// Expects a `Variant` populated with a non-zero integer.
+            impl FromVariant for $ty {
+                #[inline]
+                fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                    let value = <$int>::from_variant(variant)?;
+                    <$ty>::new(value).ok_or_else(|| {
+                        FromVariantError::custom(concat!(
+                            "expected non-zero value for ",
+                            stringify!($ty),
+                            ", got 0"
+                        ))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_for_non_zero!(
+    NonZeroI8: i8
+    NonZeroI16: i16
+    NonZeroI32: i32
+    NonZeroI64: i64
+    NonZeroIsize: isize
+    NonZeroU8: u8
+    NonZeroU16: u16
+    NonZeroU32: u32
+    NonZeroU64: u64
+    NonZeroUsize: usize
+);
+
+/// Converts the duration to a floating-point number of seconds, which is how durations are
+/// represented in the Godot API (e.g. `Timer.wait_time`).
+impl ToVariant for Duration {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_secs_f64().to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a floating-point number of seconds. Negative, infinite and
+/// NaN values are rejected.
+impl FromVariant for Duration {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let secs = f64::from_variant(variant)?;
+
+        // `u64::MAX as f64` rounds up, so the comparison has to be strict.
+        if (0.0..u64::MAX as f64).contains(&secs) {
+            Ok(Duration::from_secs_f64(secs))
+        } else {
+            Err(FromVariantError::custom(format!(
+                "expected non-negative, finite number of seconds, got {secs}"
+            )))
+        }
+    }
+}
+
+/// Converts the path to a `GodotString`. Paths that are not valid Unicode are converted lossily,
+/// replacing invalid sequences with `U+FFFD REPLACEMENT CHARACTER`.
+impl ToVariant for Path {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.to_string_lossy().to_variant()
+    }
+}
+impl ToVariantEq for Path {}
+
+/// Converts the path to a `GodotString`, like `Path`.
+impl ToVariant for PathBuf {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_path().to_variant()
+    }
+}
+impl ToVariantEq for PathBuf {}
+
+/// Expects a `Variant` populated with a `GodotString`. Godot paths like `res://` are kept as-is.
+impl FromVariant for PathBuf {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        String::from_variant(variant).map(PathBuf::from)
+    }
+}
+
+/// Converts the map to a `Dictionary`, wrapped in a `Variant`. Since Godot dictionaries preserve
+/// insertion order, the entries keep the order of the `IndexMap`.
+impl<K, V, S> ToVariant for IndexMap<K, V, S>
+where
+    K: ToVariant + ToVariantEq,
+    V: ToVariant,
+{
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let dict = Dictionary::new();
+        for (key, value) in self {
+            dict.insert(key, value);
+        }
+        dict.owned_to_variant()
+    }
+}
+
+impl<K, V, S> ToVariantDictionary for IndexMap<K, V, S> {}
+
+/// Expects a `Variant` populated with a `Dictionary` and tries to convert it into an
+/// `IndexMap`, keeping the order of the entries.
+impl<K, V, S> FromVariant for IndexMap<K, V, S>
+where
+    K: FromVariant + Hash + Eq,
+    V: FromVariant,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let dictionary = Dictionary::from_variant(variant)?;
+        let len: usize = dictionary
+            .len()
+            .try_into()
+            .expect("Dictionary length should fit in usize");
+
+        let mut map = IndexMap::with_capacity_and_hasher(len, S::default());
+        for (key, value) in dictionary.iter() {
+            map.insert(K::from_variant(&key)?, V::from_variant(&value)?);
+        }
+        Ok(map)
+    }
+}
+
+/// Converts the set to a `VariantArray` in the order of the `IndexSet`, wrapped in a `Variant`.
+impl<T: ToVariant, S> ToVariant for IndexSet<T, S> {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        let array = VariantArray::new();
+        for value in self {
+            array.push(value.to_variant());
+        }
+        array.owned_to_variant()
+    }
+}
+
+/// Expects a `Variant` populated with a `VariantArray` and tries to convert it into an
+/// `IndexSet`. Duplicate elements are only kept at the position of their first occurrence.
+impl<T, S> FromVariant for IndexSet<T, S>
+where
+    T: FromVariant + Hash + Eq,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        items_from_array(&VariantArray::from_variant(variant)?)
+    }
+}
+
 macro_rules! tuple_length {
     () => { 0usize };
     ($_x:ident, $($xs:ident,)*) => {
@@ -1992,6 +2737,91 @@ godot_test!(
         );
     }
 
+    test_variant_std_types {
+        use std::num::NonZeroU32;
+
+        let map = BTreeMap::from([("b".to_string(), 2), ("a".to_string(), 1)]);
+        let variant = map.to_variant();
+        let dict = variant.try_to::<Dictionary>().expect("should be dictionary");
+        assert_eq!(Ok("a".to_string()), dict.keys().get(0).try_to::<String>());
+        assert_eq!(Ok(map), variant.try_to::<BTreeMap<String, i64>>());
+
+        let set = BTreeSet::from([3, 1, 2]);
+        let variant = set.to_variant();
+        assert_eq!(Ok(vec![1, 2, 3]), variant.try_to::<Vec<i64>>());
+        assert_eq!(Ok(set), variant.try_to::<BTreeSet<i64>>());
+
+        let deque = VecDeque::from([1, 2, 3]);
+        assert_eq!(Ok(deque.clone()), deque.to_variant().try_to::<VecDeque<i64>>());
+
+        let variant = [1, 2, 3].to_variant();
+        assert_eq!(Ok([1, 2, 3]), variant.try_to::<[i64; 3]>());
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { expected: 2, len: 3 }),
+            variant.try_to::<[i64; 2]>(),
+        );
+
+        let boxed: Box<[i64]> = Box::new([4, 5]);
+        assert_eq!(Ok(boxed.clone()), boxed.to_variant().try_to::<Box<[i64]>>());
+        assert_eq!(Ok(Box::new(42)), Box::new(42).to_variant().try_to::<Box<i64>>());
+        assert_eq!(Ok("foo".into()), Rc::<str>::from("foo").to_variant().try_to::<Box<str>>());
+        assert_eq!(Ok(42), Arc::new(42).to_variant().try_to::<i64>());
+
+        let cow: Cow<str> = Cow::Borrowed("foo");
+        assert_eq!(Ok(cow.clone()), cow.to_variant().try_to::<Cow<str>>());
+
+        assert_eq!(Ok('ß'), 'ß'.to_variant().try_to::<char>());
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { expected: 1, len: 2 }),
+            "ab".to_variant().try_to::<char>(),
+        );
+
+        let non_zero = NonZeroU32::new(7).unwrap();
+        assert_eq!(Ok(7), non_zero.to_variant().try_to::<i64>());
+        assert_eq!(Ok(non_zero), 7.to_variant().try_to::<NonZeroU32>());
+        assert!(0.to_variant().try_to::<NonZeroU32>().is_err());
+
+        let duration = Duration::from_millis(1500);
+        assert_eq!(Ok(1.5), duration.to_variant().try_to::<f64>());
+        assert_eq!(Ok(duration), 1.5.to_variant().try_to::<Duration>());
+        assert!((-1.0).to_variant().try_to::<Duration>().is_err());
+        assert!(f64::NAN.to_variant().try_to::<Duration>().is_err());
+
+        let path = PathBuf::from("res://icon.png");
+        assert_eq!(Ok("res://icon.png".to_string()), path.to_variant().try_to::<String>());
+        assert_eq!(Ok(path), "res://icon.png".to_variant().try_to::<PathBuf>());
+    }
+
+    test_variant_index_map {
+        use indexmap::{IndexMap, IndexSet};
+
+        let map = IndexMap::<String, i64>::from([
+            ("zeta".to_string(), 1),
+            ("alpha".to_string(), 2),
+            ("mu".to_string(), 3),
+        ]);
+        let variant = map.to_variant();
+        let dict = variant.try_to::<Dictionary>().expect("should be dictionary");
+        assert_eq!(
+            Ok(vec!["zeta".to_string(), "alpha".to_string(), "mu".to_string()]),
+            dict.keys().owned_to_variant().try_to::<Vec<String>>(),
+        );
+
+        let round_trip = variant.try_to::<IndexMap<String, i64>>().expect("should be map");
+        assert!(map.iter().eq(round_trip.iter()));
+
+        let set = IndexSet::from([3, 1, 2]);
+        let variant = set.to_variant();
+        assert_eq!(Ok(vec![3, 1, 2]), variant.try_to::<Vec<i64>>());
+
+        let round_trip = variant.try_to::<IndexSet<i64>>().expect("should be set");
+        assert!(set.iter().eq(round_trip.iter()));
+
+        let with_duplicates = [2, 1, 2, 3].to_variant();
+        let deduplicated = with_duplicates.try_to::<IndexSet<i64>>().expect("should be set");
+        assert!(deduplicated.iter().eq([2, 1, 3].iter()));
+    }
+
     test_variant_tuple {
         let variant = (42i64, 54i64).to_variant();
         let arr = variant.try_to::<VariantArray>().expect("should be array");
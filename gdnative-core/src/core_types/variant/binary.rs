@@ -0,0 +1,946 @@
+//! Encoder and decoder for Godot's binary serialization format, as used by `var2bytes`,
+//! `bytes2var`, `PacketPeer` and `File::store_var`.
+//!
+//! The functions in this module are implemented in pure Rust and produce output that is
+//! byte-compatible with the engine's own implementation in `marshalls.cpp`. The
+//! [`encode_value`] and [`decode_value`] functions operate on [`VariantValue`]s and do not
+//! require a running engine, so they can be used from worker threads and offline tools.
+//!
+//! Note that `PacketPeer::put_var` and `File::store_var` prefix the encoded value with its
+//! length as a 32-bit integer, which is not included in the output of these functions.
+//!
+//! ## Objects
+//!
+//! Objects are encoded as their instance IDs by default, which is what `var2bytes` does
+//! unless `full_objects` is set. An [`ObjectValue::Full`] is encoded as a full object with its
+//! class name and properties instead. Decoding an object never instantiates it: objects are
+//! decoded into [`ObjectValue`]s, and converted to `Nil` when decoded as `Variant`s. Like in the
+//! engine, null objects are encoded as `Nil`. So are resource references, which only exist in
+//! text formats.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use gdnative::core_types::binary;
+//! use gdnative::core_types::{Vector2, VariantValue};
+//!
+//! let value = VariantValue::VariantArray(vec![
+//!     VariantValue::I64(42),
+//!     VariantValue::Vector2(Vector2::new(1.0, 2.0)),
+//! ]);
+//!
+//! let bytes = binary::encode_value(&value);
+//! let (decoded, len) = binary::decode_value(&bytes).unwrap();
+//! assert_eq!(value, decoded);
+//! assert_eq!(bytes.len(), len);
+//! ```
+
+use std::fmt;
+
+use super::value::MAX_DEPTH;
+use super::*;
+
+const TYPE_NIL: u32 = 0;
+const TYPE_BOOL: u32 = 1;
+const TYPE_INT: u32 = 2;
+const TYPE_REAL: u32 = 3;
+const TYPE_STRING: u32 = 4;
+const TYPE_VECTOR2: u32 = 5;
+const TYPE_RECT2: u32 = 6;
+const TYPE_VECTOR3: u32 = 7;
+const TYPE_TRANSFORM2D: u32 = 8;
+const TYPE_PLANE: u32 = 9;
+const TYPE_QUAT: u32 = 10;
+const TYPE_AABB: u32 = 11;
+const TYPE_BASIS: u32 = 12;
+const TYPE_TRANSFORM: u32 = 13;
+const TYPE_COLOR: u32 = 14;
+const TYPE_NODE_PATH: u32 = 15;
+const TYPE_RID: u32 = 16;
+const TYPE_OBJECT: u32 = 17;
+const TYPE_DICTIONARY: u32 = 18;
+const TYPE_ARRAY: u32 = 19;
+const TYPE_POOL_BYTE_ARRAY: u32 = 20;
+const TYPE_POOL_INT_ARRAY: u32 = 21;
+const TYPE_POOL_REAL_ARRAY: u32 = 22;
+const TYPE_POOL_STRING_ARRAY: u32 = 23;
+const TYPE_POOL_VECTOR2_ARRAY: u32 = 24;
+const TYPE_POOL_VECTOR3_ARRAY: u32 = 25;
+const TYPE_POOL_COLOR_ARRAY: u32 = 26;
+
+const ENCODE_MASK: u32 = 0xFF;
+const ENCODE_FLAG_64: u32 = 1 << 16;
+const ENCODE_FLAG_OBJECT_AS_ID: u32 = 1 << 16;
+
+const NODE_PATH_NEW_FORMAT: u32 = 0x8000_0000;
+const NODE_PATH_FLAG_ABSOLUTE: u32 = 1;
+const NODE_PATH_FLAG_PROPERTY: u32 = 2;
+
+const COLLECTION_SIZE_MASK: u32 = 0x7FFF_FFFF;
+
+/// Error that can occur when decoding a value from Godot's binary serialization format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input ended before the value was complete.
+    UnexpectedEof { offset: usize },
+    /// The type ID in a header is unknown.
+    InvalidType { offset: usize, type_id: u32 },
+    /// A string is not valid UTF-8.
+    InvalidUtf8 { offset: usize },
+    /// A length is larger than the remaining input.
+    InvalidLength { offset: usize, len: u32 },
+    /// Collections are nested deeper than Godot allows.
+    TooDeep { offset: usize },
+}
+
+impl fmt::Display for DecodeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use DecodeError as E;
+
+        match self {
+            E::UnexpectedEof { offset } => write!(f, "unexpected end of input at offset {offset}"),
+            E::InvalidType { offset, type_id } => {
+                write!(f, "invalid type ID {type_id} at offset {offset}")
+            }
+            E::InvalidUtf8 { offset } => write!(f, "invalid UTF-8 string at offset {offset}"),
+            E::InvalidLength { offset, len } => {
+                write!(f, "length {len} at offset {offset} exceeds the input size")
+            }
+            E::TooDeep { offset } => write!(
+                f,
+                "maximum nesting depth of {MAX_DEPTH} exceeded at offset {offset}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Error that can occur when encoding a `Variant` in Godot's binary serialization format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EncodeError {
+    /// Collections are nested deeper than Godot allows, e.g. an array contains itself.
+    TooDeep,
+}
+
+impl fmt::Display for EncodeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncodeError::TooDeep => write!(
+                f,
+                "maximum nesting depth of {MAX_DEPTH} exceeded, the value might contain a cycle"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Encodes a `Variant` in Godot's binary serialization format.
+///
+/// This is equivalent to `var2bytes(variant, false)`. Objects are encoded as their instance IDs.
+///
+/// # Errors
+///
+/// Returns an error if the `Variant` contains collections nested deeper than Godot allows,
+/// e.g. an array that contains itself.
+#[inline]
+pub fn encode_variant(variant: &Variant) -> Result<Vec<u8>, EncodeError> {
+    // Nesting depth is the only reason for the conversion to fail.
+    let value = VariantValue::from_variant(variant).map_err(|_| EncodeError::TooDeep)?;
+    Ok(encode_value(&value))
+}
+
+/// Decodes a `Variant` from Godot's binary serialization format, returning the value and the
+/// number of bytes read.
+///
+/// This is equivalent to `bytes2var(bytes, false)`, except that objects are always decoded as
+/// `Nil`. Use [`decode_value`] to inspect encoded objects.
+#[inline]
+pub fn decode_variant(bytes: &[u8]) -> Result<(Variant, usize), DecodeError> {
+    decode_value(bytes).map(|(value, len)| (value.to_variant(), len))
+}
+
+/// Encodes a `VariantValue` in Godot's binary serialization format.
+#[inline]
+pub fn encode_value(value: &VariantValue) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode_value_into(value, &mut buf);
+    buf
+}
+
+/// Encodes a `VariantValue` in Godot's binary serialization format, appending the output to
+/// `buf`.
+pub fn encode_value_into(value: &VariantValue, buf: &mut Vec<u8>) {
+    let start = buf.len();
+    let mut enc = Encoder { buf, start };
+    enc.value(value);
+}
+
+/// Decodes a `VariantValue` from Godot's binary serialization format, returning the value and
+/// the number of bytes read.
+///
+/// Trailing bytes after the value are ignored.
+#[inline]
+pub fn decode_value(bytes: &[u8]) -> Result<(VariantValue, usize), DecodeError> {
+    let mut dec = Decoder { bytes, pos: 0 };
+    let value = dec.value(0)?;
+    Ok((value, dec.pos))
+}
+
+struct Encoder<'a> {
+    buf: &'a mut Vec<u8>,
+    start: usize,
+}
+
+impl<'a> Encoder<'a> {
+    fn u32(&mut self, v: u32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn f32(&mut self, v: f32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        let len = u32::try_from(len).expect("collection should not exceed u32::MAX elements");
+        self.u32(len);
+    }
+
+    fn pad(&mut self) {
+        while (self.buf.len() - self.start) % 4 != 0 {
+            self.buf.push(0);
+        }
+    }
+
+    fn string(&mut self, s: &str) {
+        self.len(s.len());
+        self.buf.extend_from_slice(s.as_bytes());
+        self.pad();
+    }
+
+    fn vector2(&mut self, v: Vector2) {
+        self.f32(v.x);
+        self.f32(v.y);
+    }
+
+    fn vector3(&mut self, v: Vector3) {
+        self.f32(v.x);
+        self.f32(v.y);
+        self.f32(v.z);
+    }
+
+    fn color(&mut self, v: Color) {
+        self.f32(v.r);
+        self.f32(v.g);
+        self.f32(v.b);
+        self.f32(v.a);
+    }
+
+    fn basis(&mut self, v: &Basis) {
+        for row in &v.elements {
+            self.vector3(*row);
+        }
+    }
+
+    fn value(&mut self, value: &VariantValue) {
+        use VariantValue as V;
+
+        match value {
+            V::Nil => self.u32(TYPE_NIL),
+            V::Bool(v) => {
+                self.u32(TYPE_BOOL);
+                self.u32(*v as u32);
+            }
+            V::I64(v) => match i32::try_from(*v) {
+                Ok(v) => {
+                    self.u32(TYPE_INT);
+                    self.buf.extend_from_slice(&v.to_le_bytes());
+                }
+                Err(_) => {
+                    self.u32(TYPE_INT | ENCODE_FLAG_64);
+                    self.buf.extend_from_slice(&v.to_le_bytes());
+                }
+            },
+            V::F64(v) => {
+                if (*v as f32) as f64 == *v {
+                    self.u32(TYPE_REAL);
+                    self.f32(*v as f32);
+                } else {
+                    self.u32(TYPE_REAL | ENCODE_FLAG_64);
+                    self.buf.extend_from_slice(&v.to_le_bytes());
+                }
+            }
+            V::GodotString(v) => {
+                self.u32(TYPE_STRING);
+                self.string(v);
+            }
+            V::Vector2(v) => {
+                self.u32(TYPE_VECTOR2);
+                self.vector2(*v);
+            }
+            V::Rect2(v) => {
+                self.u32(TYPE_RECT2);
+                self.vector2(v.position);
+                self.vector2(v.size);
+            }
+            V::Vector3(v) => {
+                self.u32(TYPE_VECTOR3);
+                self.vector3(*v);
+            }
+            V::Transform2D(v) => {
+                self.u32(TYPE_TRANSFORM2D);
+                self.vector2(v.a);
+                self.vector2(v.b);
+                self.vector2(v.origin);
+            }
+            V::Plane(v) => {
+                self.u32(TYPE_PLANE);
+                self.vector3(v.normal);
+                self.f32(v.d);
+            }
+            V::Quat(v) => {
+                self.u32(TYPE_QUAT);
+                self.f32(v.x);
+                self.f32(v.y);
+                self.f32(v.z);
+                self.f32(v.w);
+            }
+            V::Aabb(v) => {
+                self.u32(TYPE_AABB);
+                self.vector3(v.position);
+                self.vector3(v.size);
+            }
+            V::Basis(v) => {
+                self.u32(TYPE_BASIS);
+                self.basis(v);
+            }
+            V::Transform(v) => {
+                self.u32(TYPE_TRANSFORM);
+                self.basis(&v.basis);
+                self.vector3(v.origin);
+            }
+            V::Color(v) => {
+                self.u32(TYPE_COLOR);
+                self.color(*v);
+            }
+            V::NodePath(v) => {
+                self.u32(TYPE_NODE_PATH);
+                let path = ParsedNodePath::parse(v);
+                self.u32(path.names.len() as u32 | NODE_PATH_NEW_FORMAT);
+                self.len(path.subnames.len());
+                self.u32(if path.absolute {
+                    NODE_PATH_FLAG_ABSOLUTE
+                } else {
+                    0
+                });
+                for name in path.names.iter().chain(&path.subnames) {
+                    self.string(name);
+                }
+            }
+            V::Rid => self.u32(TYPE_RID),
+            V::Object(ObjectValue::Id(id) | ObjectValue::Instance { id, .. }) if *id != 0 => {
+                self.u32(TYPE_OBJECT | ENCODE_FLAG_OBJECT_AS_ID);
+                self.buf.extend_from_slice(&id.to_le_bytes());
+            }
+            V::Object(ObjectValue::Full { class, properties }) => {
+                self.u32(TYPE_OBJECT);
+                self.string(class);
+                self.len(properties.len());
+                for (name, value) in properties {
+                    self.string(name);
+                    self.value(value);
+                }
+            }
+            V::Object(_) => {
+                // Null objects are written as `Nil` by the engine. Resource references are only
+                // meaningful in text formats.
+                self.u32(TYPE_NIL);
+            }
+            V::Dictionary(entries) => {
+                self.u32(TYPE_DICTIONARY);
+                self.len(entries.len());
+                for (key, value) in entries {
+                    self.value(key);
+                    self.value(value);
+                }
+            }
+            V::VariantArray(items) => {
+                self.u32(TYPE_ARRAY);
+                self.len(items.len());
+                for item in items {
+                    self.value(item);
+                }
+            }
+            V::ByteArray(v) => {
+                self.u32(TYPE_POOL_BYTE_ARRAY);
+                self.len(v.len());
+                self.buf.extend_from_slice(v);
+                self.pad();
+            }
+            V::Int32Array(v) => {
+                self.u32(TYPE_POOL_INT_ARRAY);
+                self.len(v.len());
+                for i in v {
+                    self.buf.extend_from_slice(&i.to_le_bytes());
+                }
+            }
+            V::Float32Array(v) => {
+                self.u32(TYPE_POOL_REAL_ARRAY);
+                self.len(v.len());
+                for f in v {
+                    self.f32(*f);
+                }
+            }
+            V::StringArray(v) => {
+                self.u32(TYPE_POOL_STRING_ARRAY);
+                self.len(v.len());
+                for s in v {
+                    // Unlike other strings, these include the null terminator.
+                    self.len(s.len() + 1);
+                    self.buf.extend_from_slice(s.as_bytes());
+                    self.buf.push(0);
+                    self.pad();
+                }
+            }
+            V::Vector2Array(v) => {
+                self.u32(TYPE_POOL_VECTOR2_ARRAY);
+                self.len(v.len());
+                for v in v {
+                    self.vector2(*v);
+                }
+            }
+            V::Vector3Array(v) => {
+                self.u32(TYPE_POOL_VECTOR3_ARRAY);
+                self.len(v.len());
+                for v in v {
+                    self.vector3(*v);
+                }
+            }
+            V::ColorArray(v) => {
+                self.u32(TYPE_POOL_COLOR_ARRAY);
+                self.len(v.len());
+                for v in v {
+                    self.color(*v);
+                }
+            }
+        }
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(DecodeError::UnexpectedEof { offset: self.pos })?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Result<f32, DecodeError> {
+        self.array().map(f32::from_le_bytes)
+    }
+
+    /// Reads an element count, checking it against the remaining input so that corrupted data
+    /// cannot cause huge allocations.
+    fn len(&mut self, mask: u32, min_elem_size: usize) -> Result<usize, DecodeError> {
+        let offset = self.pos;
+        let len = self.u32()? & mask;
+        let remaining = self.bytes.len() - self.pos;
+        if (len as usize).saturating_mul(min_elem_size) > remaining {
+            return Err(DecodeError::InvalidLength { offset, len });
+        }
+        Ok(len as usize)
+    }
+
+    fn skip_pad(&mut self, len: usize) -> Result<(), DecodeError> {
+        let pad = (4 - len % 4) % 4;
+        self.take(pad).map(|_| ())
+    }
+
+    fn str(&mut self, len: usize) -> Result<String, DecodeError> {
+        let offset = self.pos;
+        let bytes = self.take(len)?;
+        let string = std::str::from_utf8(bytes)
+            .map_err(|_| DecodeError::InvalidUtf8 { offset })?
+            .to_owned();
+        self.skip_pad(len)?;
+        Ok(string)
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let len = self.len(u32::MAX, 1)?;
+        self.str(len)
+    }
+
+    fn vector2(&mut self) -> Result<Vector2, DecodeError> {
+        Ok(Vector2::new(self.f32()?, self.f32()?))
+    }
+
+    fn vector3(&mut self) -> Result<Vector3, DecodeError> {
+        Ok(Vector3::new(self.f32()?, self.f32()?, self.f32()?))
+    }
+
+    fn color(&mut self) -> Result<Color, DecodeError> {
+        Ok(Color::from_rgba(
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+        ))
+    }
+
+    fn basis(&mut self) -> Result<Basis, DecodeError> {
+        Ok(Basis {
+            elements: [self.vector3()?, self.vector3()?, self.vector3()?],
+        })
+    }
+
+    fn value(&mut self, depth: usize) -> Result<VariantValue, DecodeError> {
+        let offset = self.pos;
+        if depth > MAX_DEPTH {
+            return Err(DecodeError::TooDeep { offset });
+        }
+
+        let header = self.u32()?;
+        let flags = header & !ENCODE_MASK;
+
+        // Collections are decoded in separate functions to keep the stack frames of this one
+        // small, since it is called recursively for each level of nesting.
+        match header & ENCODE_MASK {
+            TYPE_OBJECT if flags & ENCODE_FLAG_OBJECT_AS_ID != 0 => {
+                match u64::from_le_bytes(self.array()?) {
+                    0 => Ok(VariantValue::Object(ObjectValue::Null)),
+                    id => Ok(VariantValue::Object(ObjectValue::Id(id))),
+                }
+            }
+            TYPE_OBJECT => self.object(depth),
+            TYPE_DICTIONARY => self.dictionary(depth),
+            TYPE_ARRAY => self.variant_array(depth),
+            type_id => self.leaf(offset, type_id, flags),
+        }
+    }
+
+    #[inline(never)]
+    fn object(&mut self, depth: usize) -> Result<VariantValue, DecodeError> {
+        let class = self.string()?;
+        if class.is_empty() {
+            return Ok(VariantValue::Object(ObjectValue::Null));
+        }
+
+        let count = self.len(u32::MAX, 8)?;
+        let mut properties = Vec::with_capacity(count);
+        for _ in 0..count {
+            let name = self.string()?;
+            let value = self.value(depth + 1)?;
+            properties.push((name, value));
+        }
+        Ok(VariantValue::Object(ObjectValue::Full {
+            class,
+            properties,
+        }))
+    }
+
+    #[inline(never)]
+    fn dictionary(&mut self, depth: usize) -> Result<VariantValue, DecodeError> {
+        let count = self.len(COLLECTION_SIZE_MASK, 8)?;
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            let key = self.value(depth + 1)?;
+            let value = self.value(depth + 1)?;
+            entries.push((key, value));
+        }
+        Ok(VariantValue::Dictionary(entries))
+    }
+
+    #[inline(never)]
+    fn variant_array(&mut self, depth: usize) -> Result<VariantValue, DecodeError> {
+        let count = self.len(COLLECTION_SIZE_MASK, 4)?;
+        let mut items = Vec::with_capacity(count);
+        for _ in 0..count {
+            items.push(self.value(depth + 1)?);
+        }
+        Ok(VariantValue::VariantArray(items))
+    }
+
+    #[inline(never)]
+    fn leaf(
+        &mut self,
+        offset: usize,
+        type_id: u32,
+        flags: u32,
+    ) -> Result<VariantValue, DecodeError> {
+        use VariantValue as V;
+
+        let value = match type_id {
+            TYPE_NIL => V::Nil,
+            TYPE_BOOL => V::Bool(self.u32()? != 0),
+            TYPE_INT if flags & ENCODE_FLAG_64 != 0 => V::I64(i64::from_le_bytes(self.array()?)),
+            TYPE_INT => V::I64(i32::from_le_bytes(self.array()?).into()),
+            TYPE_REAL if flags & ENCODE_FLAG_64 != 0 => V::F64(f64::from_le_bytes(self.array()?)),
+            TYPE_REAL => V::F64(self.f32()?.into()),
+            TYPE_STRING => V::GodotString(self.string()?),
+            TYPE_VECTOR2 => V::Vector2(self.vector2()?),
+            TYPE_RECT2 => V::Rect2(Rect2 {
+                position: self.vector2()?,
+                size: self.vector2()?,
+            }),
+            TYPE_VECTOR3 => V::Vector3(self.vector3()?),
+            TYPE_TRANSFORM2D => V::Transform2D(Transform2D {
+                a: self.vector2()?,
+                b: self.vector2()?,
+                origin: self.vector2()?,
+            }),
+            TYPE_PLANE => V::Plane(Plane {
+                normal: self.vector3()?,
+                d: self.f32()?,
+            }),
+            TYPE_QUAT => V::Quat(Quat::new(
+                self.f32()?,
+                self.f32()?,
+                self.f32()?,
+                self.f32()?,
+            )),
+            TYPE_AABB => V::Aabb(Aabb {
+                position: self.vector3()?,
+                size: self.vector3()?,
+            }),
+            TYPE_BASIS => V::Basis(self.basis()?),
+            TYPE_TRANSFORM => V::Transform(Transform {
+                basis: self.basis()?,
+                origin: self.vector3()?,
+            }),
+            TYPE_COLOR => V::Color(self.color()?),
+            TYPE_NODE_PATH => {
+                let name_count = self.u32()?;
+                if name_count & NODE_PATH_NEW_FORMAT == 0 {
+                    // Old format, which is just a string.
+                    self.pos -= 4;
+                    V::NodePath(self.string()?)
+                } else {
+                    let name_count = (name_count & COLLECTION_SIZE_MASK) as usize;
+                    let mut subname_count = self.u32()? as usize;
+                    let flags = self.u32()?;
+                    if flags & NODE_PATH_FLAG_PROPERTY != 0 {
+                        // Old format, in which the property was stored separately.
+                        subname_count += 1;
+                    }
+
+                    let total = name_count.saturating_add(subname_count);
+                    if total.saturating_mul(4) > self.bytes.len() - self.pos {
+                        return Err(DecodeError::InvalidLength {
+                            offset: offset + 4,
+                            len: total as u32,
+                        });
+                    }
+
+                    let mut path = ParsedNodePath {
+                        absolute: flags & NODE_PATH_FLAG_ABSOLUTE != 0,
+                        names: Vec::with_capacity(name_count),
+                        subnames: Vec::with_capacity(subname_count),
+                    };
+                    for _ in 0..name_count {
+                        path.names.push(self.string()?);
+                    }
+                    for _ in 0..subname_count {
+                        path.subnames.push(self.string()?);
+                    }
+
+                    V::NodePath(path.to_string())
+                }
+            }
+            TYPE_RID => V::Rid,
+            TYPE_POOL_BYTE_ARRAY => {
+                let len = self.len(u32::MAX, 1)?;
+                let bytes = self.take(len)?.to_vec();
+                self.skip_pad(len)?;
+                V::ByteArray(bytes)
+            }
+            TYPE_POOL_INT_ARRAY => {
+                let len = self.len(u32::MAX, 4)?;
+                let mut v = Vec::with_capacity(len);
+                for _ in 0..len {
+                    v.push(i32::from_le_bytes(self.array()?));
+                }
+                V::Int32Array(v)
+            }
+            TYPE_POOL_REAL_ARRAY => {
+                let len = self.len(u32::MAX, 4)?;
+                let mut v = Vec::with_capacity(len);
+                for _ in 0..len {
+                    v.push(self.f32()?);
+                }
+                V::Float32Array(v)
+            }
+            TYPE_POOL_STRING_ARRAY => {
+                let len = self.len(u32::MAX, 4)?;
+                let mut v = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let mut s = self.string()?;
+                    if s.ends_with('\0') {
+                        s.pop();
+                    }
+                    v.push(s);
+                }
+                V::StringArray(v)
+            }
+            TYPE_POOL_VECTOR2_ARRAY => {
+                let len = self.len(u32::MAX, 8)?;
+                let mut v = Vec::with_capacity(len);
+                for _ in 0..len {
+                    v.push(self.vector2()?);
+                }
+                V::Vector2Array(v)
+            }
+            TYPE_POOL_VECTOR3_ARRAY => {
+                let len = self.len(u32::MAX, 12)?;
+                let mut v = Vec::with_capacity(len);
+                for _ in 0..len {
+                    v.push(self.vector3()?);
+                }
+                V::Vector3Array(v)
+            }
+            TYPE_POOL_COLOR_ARRAY => {
+                let len = self.len(u32::MAX, 16)?;
+                let mut v = Vec::with_capacity(len);
+                for _ in 0..len {
+                    v.push(self.color()?);
+                }
+                V::ColorArray(v)
+            }
+            type_id => return Err(DecodeError::InvalidType { offset, type_id }),
+        };
+
+        Ok(value)
+    }
+}
+
+/// Components of a node path, split the same way as Godot's `NodePath` constructor.
+struct ParsedNodePath {
+    absolute: bool,
+    names: Vec<String>,
+    subnames: Vec<String>,
+}
+
+impl ParsedNodePath {
+    fn parse(path: &str) -> Self {
+        let absolute = path.starts_with('/');
+        let (names, subnames) = match path.find(':') {
+            Some(index) => (&path[..index], Some(&path[index + 1..])),
+            None => (path, None),
+        };
+
+        ParsedNodePath {
+            absolute,
+            names: names
+                .split('/')
+                .filter(|name| !name.is_empty())
+                .map(str::to_owned)
+                .collect(),
+            subnames: subnames
+                .into_iter()
+                .flat_map(|subnames| subnames.split(':'))
+                .filter(|name| !name.is_empty())
+                .map(str::to_owned)
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for ParsedNodePath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.absolute {
+            f.write_str("/")?;
+        }
+        f.write_str(&self.names.join("/"))?;
+        for subname in &self.subnames {
+            write!(f, ":{subname}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(value: VariantValue) -> Vec<u8> {
+        let bytes = encode_value(&value);
+        let (decoded, len) = decode_value(&bytes).expect("should decode");
+        assert_eq!(value, decoded);
+        assert_eq!(bytes.len(), len);
+        assert_eq!(0, bytes.len() % 4);
+        bytes
+    }
+
+    #[test]
+    fn encode_scalars() {
+        assert_eq!(vec![0, 0, 0, 0], round_trip(VariantValue::Nil));
+        assert_eq!(vec![1, 0, 0, 0, 1, 0, 0, 0], round_trip(true.into()));
+        assert_eq!(
+            vec![2, 0, 0, 0, 255, 255, 255, 255],
+            round_trip((-1).into())
+        );
+        assert_eq!(
+            vec![2, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0],
+            round_trip(VariantValue::I64(1 << 40)),
+        );
+        assert_eq!(vec![3, 0, 0, 0, 0, 0, 0, 0x3f], round_trip(0.5.into()));
+        assert_eq!(12, round_trip(0.1.into()).len());
+        assert_eq!(
+            vec![4, 0, 0, 0, 3, 0, 0, 0, b'a', b'b', b'c', 0],
+            round_trip("abc".into()),
+        );
+    }
+
+    #[test]
+    fn encode_objects() {
+        assert_eq!(
+            vec![17, 0, 1, 0, 7, 0, 0, 0, 0, 0, 0, 0],
+            round_trip(ObjectValue::Id(7).into()),
+        );
+        assert_eq!(
+            encode_value(&ObjectValue::Id(7).into()),
+            encode_value(
+                &ObjectValue::Instance {
+                    class: "Node".into(),
+                    id: 7
+                }
+                .into()
+            ),
+        );
+        assert_eq!(vec![0, 0, 0, 0], encode_value(&ObjectValue::Null.into()));
+        assert_eq!(
+            vec![0, 0, 0, 0],
+            encode_value(&ObjectValue::Resource("res://icon.png".into()).into()),
+        );
+
+        // Null objects written with an ID of 0 are still accepted
+        let (value, _) = decode_value(&[17, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(VariantValue::Object(ObjectValue::Null), value);
+        round_trip(
+            ObjectValue::Full {
+                class: "Resource".into(),
+                properties: vec![("resource_name".into(), "foo".into())],
+            }
+            .into(),
+        );
+    }
+
+    #[test]
+    fn encode_node_paths() {
+        let bytes = round_trip(VariantValue::NodePath("/root/Main:position:x".into()));
+        assert_eq!(
+            [15, 0, 0, 0, 2, 0, 0, 0x80, 2, 0, 0, 0, 1, 0, 0, 0],
+            bytes[..16],
+        );
+        round_trip(VariantValue::NodePath("Player/Sprite".into()));
+        round_trip(VariantValue::NodePath(":modulate".into()));
+
+        // Old format, in which node paths are stored as strings
+        let (value, _) = decode_value(&[15, 0, 0, 0, 1, 0, 0, 0, b'a', 0, 0, 0]).unwrap();
+        assert_eq!(VariantValue::NodePath("a".into()), value);
+    }
+
+    #[test]
+    fn encode_collections() {
+        round_trip(VariantValue::Dictionary(vec![
+            (
+                "key".into(),
+                VariantValue::VariantArray(vec![VariantValue::Nil, VariantValue::Rid]),
+            ),
+            (42.into(), Vector2::new(1.0, 2.0).into()),
+        ]));
+        round_trip(VariantValue::ByteArray(vec![1, 2, 3, 4, 5]));
+        round_trip(VariantValue::Int32Array(vec![-5, 6]));
+        round_trip(VariantValue::Float32Array(vec![1.5]));
+        round_trip(VariantValue::Vector2Array(vec![Vector2::new(1.0, 2.0)]));
+        round_trip(VariantValue::Vector3Array(vec![]));
+        round_trip(VariantValue::ColorArray(vec![Color::from_rgba(
+            1.0, 0.0, 0.0, 1.0,
+        )]));
+        assert_eq!(
+            vec![23, 0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, b'a', b'b', b'c', 0, 1, 0, 0, 0, 0, 0, 0, 0,],
+            round_trip(VariantValue::StringArray(vec!["abc".into(), "".into()])),
+        );
+
+        // The high bit is set for shared collections
+        let (value, _) = decode_value(&[19, 0, 0, 0, 0, 0, 0, 0x80]).unwrap();
+        assert_eq!(VariantValue::VariantArray(vec![]), value);
+    }
+
+    #[test]
+    fn encode_geometry() {
+        round_trip(
+            Transform {
+                basis: Basis::IDENTITY,
+                origin: Vector3::new(4.0, 5.0, 6.0),
+            }
+            .into(),
+        );
+        round_trip(Transform2D::IDENTITY.into());
+        round_trip(Quat::new(1.0, 2.0, 3.0, 4.0).into());
+        round_trip(Plane::new(Vector3::new(1.0, 0.0, 0.0), 2.0).into());
+        round_trip(
+            Aabb {
+                position: Vector3::ZERO,
+                size: Vector3::ONE,
+            }
+            .into(),
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert_eq!(
+            Err(DecodeError::UnexpectedEof { offset: 0 }),
+            decode_value(&[2, 0, 0]),
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidType {
+                offset: 0,
+                type_id: 99
+            }),
+            decode_value(&[99, 0, 0, 0]),
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidLength {
+                offset: 4,
+                len: 0x7FFF_FFFF
+            }),
+            decode_value(&[19, 0, 0, 0, 255, 255, 255, 127]),
+        );
+
+        let mut nested = Vec::new();
+        for _ in 0..=MAX_DEPTH {
+            nested.extend_from_slice(&[19, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        nested.extend_from_slice(&[0, 0, 0, 0]);
+        assert!(matches!(
+            decode_value(&nested),
+            Err(DecodeError::TooDeep { .. })
+        ));
+    }
+}
@@ -0,0 +1,359 @@
+use std::mem::size_of;
+use std::ptr;
+
+use crate::core_types::*;
+use crate::object::NewRef;
+use crate::sys;
+
+/// Offset of the value inside a `godot_variant`. The engine stores the type tag first, followed
+/// by a 16-byte union that is aligned to 8 bytes on all platforms.
+///
+/// Values that don't fit into the union (`Transform2D`, `Aabb`, `Basis` and `Transform`) are
+/// allocated on the heap, and the union holds a pointer to them.
+const DATA_OFFSET: usize = 8;
+
+const _: () = assert!(size_of::<sys::godot_variant>() == DATA_OFFSET + 16);
+
+/// Borrowing counterpart of [`VariantDispatch`], returned by [`Variant::as_ref`].
+///
+/// Reference-counted types are borrowed from the `Variant`, so matching on the type doesn't
+/// touch any reference counts. Small `Copy` types are stored by value.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug)]
+pub enum VariantRef<'a> {
+    Nil,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    GodotString(&'a GodotString),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    NodePath(&'a NodePath),
+    Rid(Rid),
+    Object(&'a Variant),
+    Dictionary(&'a Dictionary),
+    VariantArray(&'a VariantArray),
+    ByteArray(&'a PoolArray<u8>),
+    Int32Array(&'a PoolArray<i32>),
+    Float32Array(&'a PoolArray<f32>),
+    StringArray(&'a PoolArray<GodotString>),
+    Vector2Array(&'a PoolArray<Vector2>),
+    Vector3Array(&'a PoolArray<Vector3>),
+    ColorArray(&'a PoolArray<Color>),
+}
+
+/// Mutably borrowing counterpart of [`VariantDispatch`], returned by [`Variant::as_mut`].
+///
+/// Reference-counted types are borrowed mutably from the `Variant`, and can be modified in
+/// place. Small `Copy` types are stored by value: to change them, assign a new value to the
+/// `Variant` instead.
+///
+/// The `Object` arm borrows the whole `Variant`, since objects can't be stored in the `Variant`
+/// independently of their memory management.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum VariantMut<'a> {
+    Nil,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    GodotString(&'a mut GodotString),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    NodePath(&'a mut NodePath),
+    Rid(Rid),
+    Object(&'a mut Variant),
+    Dictionary(&'a mut Dictionary),
+    VariantArray(&'a mut VariantArray),
+    ByteArray(&'a mut PoolArray<u8>),
+    Int32Array(&'a mut PoolArray<i32>),
+    Float32Array(&'a mut PoolArray<f32>),
+    StringArray(&'a mut PoolArray<GodotString>),
+    Vector2Array(&'a mut PoolArray<Vector2>),
+    Vector3Array(&'a mut PoolArray<Vector3>),
+    ColorArray(&'a mut PoolArray<Color>),
+}
+
+impl Variant {
+    /// Borrows the value of this variant depending on its type. This is a cheaper alternative
+    /// to [`dispatch()`][Self::dispatch] when the value is only inspected, since no reference
+    /// counts are changed and nothing is copied except for small `Copy` types.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let variant = GodotString::from("foo").to_variant();
+    /// let len = match variant.as_ref() {
+    ///     VariantRef::GodotString(s) => s.len(),
+    ///     VariantRef::VariantArray(arr) => arr.len() as usize,
+    ///     _ => 0,
+    /// };
+    /// assert_eq!(3, len);
+    /// ```
+    #[inline]
+    pub fn as_ref(&self) -> VariantRef<'_> {
+        // SAFETY: the type tag determines which union field is initialized. The borrowed types
+        // have the same layout as their engine counterparts, and an alignment of 1.
+        unsafe {
+            match self.get_type() {
+                VariantType::Nil => VariantRef::Nil,
+                VariantType::Bool => VariantRef::Bool(self.read_inline()),
+                VariantType::I64 => VariantRef::I64(self.read_inline()),
+                VariantType::F64 => VariantRef::F64(self.read_inline()),
+                VariantType::GodotString => VariantRef::GodotString(self.borrow_inline()),
+                VariantType::Vector2 => VariantRef::Vector2(self.read_inline()),
+                VariantType::Rect2 => VariantRef::Rect2(self.read_inline()),
+                VariantType::Vector3 => VariantRef::Vector3(self.read_inline()),
+                VariantType::Transform2D => VariantRef::Transform2D(self.read_boxed()),
+                VariantType::Plane => VariantRef::Plane(self.read_inline()),
+                VariantType::Quat => VariantRef::Quat(self.read_inline()),
+                VariantType::Aabb => VariantRef::Aabb(self.read_boxed()),
+                VariantType::Basis => VariantRef::Basis(self.read_boxed()),
+                VariantType::Transform => VariantRef::Transform(self.read_boxed()),
+                VariantType::Color => VariantRef::Color(self.read_inline()),
+                VariantType::NodePath => VariantRef::NodePath(self.borrow_inline()),
+                VariantType::Rid => VariantRef::Rid(self.read_inline()),
+                VariantType::Object => VariantRef::Object(self),
+                VariantType::Dictionary => VariantRef::Dictionary(self.borrow_inline()),
+                VariantType::VariantArray => VariantRef::VariantArray(self.borrow_inline()),
+                VariantType::ByteArray => VariantRef::ByteArray(self.borrow_inline()),
+                VariantType::Int32Array => VariantRef::Int32Array(self.borrow_inline()),
+                VariantType::Float32Array => VariantRef::Float32Array(self.borrow_inline()),
+                VariantType::StringArray => VariantRef::StringArray(self.borrow_inline()),
+                VariantType::Vector2Array => VariantRef::Vector2Array(self.borrow_inline()),
+                VariantType::Vector3Array => VariantRef::Vector3Array(self.borrow_inline()),
+                VariantType::ColorArray => VariantRef::ColorArray(self.borrow_inline()),
+            }
+        }
+    }
+
+    /// Mutably borrows the value of this variant depending on its type. Reference-counted
+    /// values can be modified in place, without taking them out of the variant.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let mut variant = PoolArray::from_vec(vec![1, 2, 3]).to_variant();
+    /// if let VariantMut::Int32Array(arr) = variant.as_mut() {
+    ///     arr.push(4);
+    /// }
+    /// assert_eq!(Some(4), variant.to::<PoolArray<i32>>().map(|arr| arr.len()));
+    /// ```
+    #[inline]
+    pub fn as_mut(&mut self) -> VariantMut<'_> {
+        // SAFETY: see `as_ref`. Replacing a borrowed value through the mutable reference drops
+        // the old value, which is what the engine does when assigning to the union field.
+        unsafe {
+            match self.get_type() {
+                VariantType::Nil => VariantMut::Nil,
+                VariantType::Bool => VariantMut::Bool(self.read_inline()),
+                VariantType::I64 => VariantMut::I64(self.read_inline()),
+                VariantType::F64 => VariantMut::F64(self.read_inline()),
+                VariantType::GodotString => VariantMut::GodotString(self.borrow_inline_mut()),
+                VariantType::Vector2 => VariantMut::Vector2(self.read_inline()),
+                VariantType::Rect2 => VariantMut::Rect2(self.read_inline()),
+                VariantType::Vector3 => VariantMut::Vector3(self.read_inline()),
+                VariantType::Transform2D => VariantMut::Transform2D(self.read_boxed()),
+                VariantType::Plane => VariantMut::Plane(self.read_inline()),
+                VariantType::Quat => VariantMut::Quat(self.read_inline()),
+                VariantType::Aabb => VariantMut::Aabb(self.read_boxed()),
+                VariantType::Basis => VariantMut::Basis(self.read_boxed()),
+                VariantType::Transform => VariantMut::Transform(self.read_boxed()),
+                VariantType::Color => VariantMut::Color(self.read_inline()),
+                VariantType::NodePath => VariantMut::NodePath(self.borrow_inline_mut()),
+                VariantType::Rid => VariantMut::Rid(self.read_inline()),
+                VariantType::Object => VariantMut::Object(self),
+                VariantType::Dictionary => VariantMut::Dictionary(self.borrow_inline_mut()),
+                VariantType::VariantArray => VariantMut::VariantArray(self.borrow_inline_mut()),
+                VariantType::ByteArray => VariantMut::ByteArray(self.borrow_inline_mut()),
+                VariantType::Int32Array => VariantMut::Int32Array(self.borrow_inline_mut()),
+                VariantType::Float32Array => VariantMut::Float32Array(self.borrow_inline_mut()),
+                VariantType::StringArray => VariantMut::StringArray(self.borrow_inline_mut()),
+                VariantType::Vector2Array => VariantMut::Vector2Array(self.borrow_inline_mut()),
+                VariantType::Vector3Array => VariantMut::Vector3Array(self.borrow_inline_mut()),
+                VariantType::ColorArray => VariantMut::ColorArray(self.borrow_inline_mut()),
+            }
+        }
+    }
+
+    #[inline]
+    fn data_ptr(&self) -> *const u8 {
+        unsafe { (self.sys() as *const u8).add(DATA_OFFSET) }
+    }
+
+    #[inline]
+    fn data_ptr_mut(&mut self) -> *mut u8 {
+        unsafe { (self.sys_mut() as *mut u8).add(DATA_OFFSET) }
+    }
+
+    /// Copies a value stored in the union. `godot_variant` has an alignment of 1 on the Rust
+    /// side, so the read has to be unaligned.
+    #[inline]
+    unsafe fn read_inline<T: Copy>(&self) -> T {
+        ptr::read_unaligned(self.data_ptr() as *const T)
+    }
+
+    /// Copies a value stored on the heap, through the pointer in the union.
+    #[inline]
+    unsafe fn read_boxed<T: Copy>(&self) -> T {
+        let ptr = ptr::read_unaligned(self.data_ptr() as *const *const T);
+        ptr::read(ptr)
+    }
+
+    #[inline]
+    unsafe fn borrow_inline<T>(&self) -> &T {
+        &*(self.data_ptr() as *const T)
+    }
+
+    #[inline]
+    unsafe fn borrow_inline_mut<T>(&mut self) -> &mut T {
+        &mut *(self.data_ptr_mut() as *mut T)
+    }
+}
+
+impl<'a> VariantRef<'a> {
+    /// Returns the type of the borrowed value.
+    #[inline]
+    pub fn get_type(&self) -> VariantType {
+        match self {
+            VariantRef::Nil => VariantType::Nil,
+            VariantRef::Bool(_) => VariantType::Bool,
+            VariantRef::I64(_) => VariantType::I64,
+            VariantRef::F64(_) => VariantType::F64,
+            VariantRef::GodotString(_) => VariantType::GodotString,
+            VariantRef::Vector2(_) => VariantType::Vector2,
+            VariantRef::Rect2(_) => VariantType::Rect2,
+            VariantRef::Vector3(_) => VariantType::Vector3,
+            VariantRef::Transform2D(_) => VariantType::Transform2D,
+            VariantRef::Plane(_) => VariantType::Plane,
+            VariantRef::Quat(_) => VariantType::Quat,
+            VariantRef::Aabb(_) => VariantType::Aabb,
+            VariantRef::Basis(_) => VariantType::Basis,
+            VariantRef::Transform(_) => VariantType::Transform,
+            VariantRef::Color(_) => VariantType::Color,
+            VariantRef::NodePath(_) => VariantType::NodePath,
+            VariantRef::Rid(_) => VariantType::Rid,
+            VariantRef::Object(_) => VariantType::Object,
+            VariantRef::Dictionary(_) => VariantType::Dictionary,
+            VariantRef::VariantArray(_) => VariantType::VariantArray,
+            VariantRef::ByteArray(_) => VariantType::ByteArray,
+            VariantRef::Int32Array(_) => VariantType::Int32Array,
+            VariantRef::Float32Array(_) => VariantType::Float32Array,
+            VariantRef::StringArray(_) => VariantType::StringArray,
+            VariantRef::Vector2Array(_) => VariantType::Vector2Array,
+            VariantRef::Vector3Array(_) => VariantType::Vector3Array,
+            VariantRef::ColorArray(_) => VariantType::ColorArray,
+        }
+    }
+}
+
+impl<'a> From<VariantRef<'a>> for VariantDispatch {
+    #[inline]
+    fn from(v: VariantRef<'a>) -> Self {
+        match v {
+            VariantRef::Nil => VariantDispatch::Nil,
+            VariantRef::Bool(v) => VariantDispatch::Bool(v),
+            VariantRef::I64(v) => VariantDispatch::I64(v),
+            VariantRef::F64(v) => VariantDispatch::F64(v),
+            VariantRef::GodotString(v) => VariantDispatch::GodotString(v.clone()),
+            VariantRef::Vector2(v) => VariantDispatch::Vector2(v),
+            VariantRef::Rect2(v) => VariantDispatch::Rect2(v),
+            VariantRef::Vector3(v) => VariantDispatch::Vector3(v),
+            VariantRef::Transform2D(v) => VariantDispatch::Transform2D(v),
+            VariantRef::Plane(v) => VariantDispatch::Plane(v),
+            VariantRef::Quat(v) => VariantDispatch::Quat(v),
+            VariantRef::Aabb(v) => VariantDispatch::Aabb(v),
+            VariantRef::Basis(v) => VariantDispatch::Basis(v),
+            VariantRef::Transform(v) => VariantDispatch::Transform(v),
+            VariantRef::Color(v) => VariantDispatch::Color(v),
+            VariantRef::NodePath(v) => VariantDispatch::NodePath(v.new_ref()),
+            VariantRef::Rid(v) => VariantDispatch::Rid(v),
+            VariantRef::Object(v) => VariantDispatch::Object(v.clone()),
+            VariantRef::Dictionary(v) => VariantDispatch::Dictionary(v.new_ref()),
+            VariantRef::VariantArray(v) => VariantDispatch::VariantArray(v.new_ref()),
+            VariantRef::ByteArray(v) => VariantDispatch::ByteArray(v.clone()),
+            VariantRef::Int32Array(v) => VariantDispatch::Int32Array(v.clone()),
+            VariantRef::Float32Array(v) => VariantDispatch::Float32Array(v.clone()),
+            VariantRef::StringArray(v) => VariantDispatch::StringArray(v.clone()),
+            VariantRef::Vector2Array(v) => VariantDispatch::Vector2Array(v.clone()),
+            VariantRef::Vector3Array(v) => VariantDispatch::Vector3Array(v.clone()),
+            VariantRef::ColorArray(v) => VariantDispatch::ColorArray(v.clone()),
+        }
+    }
+}
+
+godot_test!(test_variant_ref {
+    let variant = GodotString::from("foo").to_variant();
+    match variant.as_ref() {
+        VariantRef::GodotString(s) => assert_eq!("foo", s.to_string()),
+        other => panic!("incorrect borrowed type: {:?}", other.get_type()),
+    }
+
+    for value in [
+        Variant::nil(),
+        true.to_variant(),
+        (-42).to_variant(),
+        1.5.to_variant(),
+        Vector2::new(1.0, 2.0).to_variant(),
+        Vector3::new(1.0, 2.0, 3.0).to_variant(),
+        Transform2D::IDENTITY.to_variant(),
+        Transform::IDENTITY.to_variant(),
+        Color::from_rgb(0.5, 0.25, 1.0).to_variant(),
+        NodePath::from_str("Parent/Child").to_variant(),
+        Dictionary::new().owned_to_variant(),
+        PoolArray::from_vec(vec![1.0f32, 2.0]).to_variant(),
+    ] {
+        let borrowed = value.as_ref();
+        assert_eq!(value.get_type(), borrowed.get_type());
+        assert_eq!(value, Variant::from(&VariantDispatch::from(borrowed)));
+    }
+
+    let dict = Dictionary::new();
+    dict.insert("foo", 1);
+    let variant = dict.owned_to_variant();
+    match variant.as_ref() {
+        VariantRef::Dictionary(borrowed) => {
+            assert_eq!(Some(1), borrowed.get("foo").and_then(|v| v.to::<i64>()));
+        }
+        other => panic!("incorrect borrowed type: {:?}", other.get_type()),
+    }
+
+    let mut variant = PoolArray::from_vec(vec![1, 2, 3]).to_variant();
+    match variant.as_mut() {
+        VariantMut::Int32Array(arr) => arr.push(4),
+        _ => panic!("incorrect borrowed type"),
+    }
+    assert_eq!(
+        Some(vec![1, 2, 3, 4]),
+        variant.to::<PoolArray<i32>>().map(|arr| arr.to_vec()),
+    );
+
+    let mut variant = GodotString::from("foo").to_variant();
+    if let VariantMut::GodotString(s) = variant.as_mut() {
+        *s = GodotString::from("bar");
+    }
+    assert_eq!(Some("bar".to_string()), variant.to::<String>());
+
+    let mut variant = 1.to_variant();
+    match variant.as_mut() {
+        VariantMut::I64(i) => variant = (i + 1).to_variant(),
+        _ => panic!("incorrect borrowed type"),
+    }
+    assert_eq!(Some(2), variant.to::<i64>());
+});
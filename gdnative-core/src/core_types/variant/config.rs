@@ -0,0 +1,655 @@
+//! Reading and writing documents in the format of Godot's `ConfigFile`, which is also used by
+//! `project.godot`, `override.cfg` and `export_presets.cfg`.
+//!
+//! Unlike `ConfigFile`, a [`ConfigDocument`] is implemented in pure Rust, and keeps the
+//! comments and the order of sections and keys of the parsed text. Entries that are not
+//! modified are written back exactly as they were read, so that the document can be updated
+//! from build scripts and tools without producing unrelated changes.
+//!
+//! Values are read and written in Godot's text format, as described in the [`text`][super::text]
+//! module.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use gdnative::core_types::config::ConfigDocument;
+//!
+//! let mut doc = ConfigDocument::parse(
+//!     "; Engine configuration file.\n\
+//!      config_version=4\n\
+//!      \n\
+//!      [application]\n\
+//!      \n\
+//!      config/name=\"Demo\" ; shown in the project manager\n",
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(Some(&"Demo".into()), doc.get("application", "config/name"));
+//!
+//! doc.set("application", "config/name", "Renamed");
+//! doc.set("display", "window/size/width", 1280);
+//!
+//! assert_eq!(
+//!     "; Engine configuration file.\n\
+//!      config_version=4\n\
+//!      \n\
+//!      [application]\n\
+//!      \n\
+//!      config/name=\"Renamed\" ; shown in the project manager\n\
+//!      \n\
+//!      [display]\n\
+//!      window/size/width=1280\n",
+//!     doc.to_string(),
+//! );
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::{mem, slice};
+
+use super::text::{self, ParseError, ParseErrorKind, Parser};
+use super::*;
+
+/// A document in the format of Godot's `ConfigFile`, consisting of sections containing keys
+/// with values.
+///
+/// Keys before the first section header belong to the section with an empty name, which is
+/// how `ConfigFile` represents them too.
+///
+/// The document is written back to text through its `Display` implementation.
+#[derive(Clone, Debug)]
+pub struct ConfigDocument {
+    /// All sections in document order. The first one is always the unnamed section.
+    sections: Vec<Section>,
+}
+
+#[derive(Clone, Debug)]
+struct Section {
+    name: String,
+    /// Comment and blank lines before the header.
+    leading: Vec<String>,
+    /// The text of the header line.
+    header: String,
+    lines: Vec<Line>,
+}
+
+#[derive(Clone, Debug)]
+enum Line {
+    /// A comment or blank line, stored verbatim.
+    Comment(String),
+    Entry(Entry),
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    key: String,
+    value: VariantValue,
+    /// The original text of the entry up to the end of the value, which is written instead of
+    /// the value as long as it isn't changed.
+    source: Option<String>,
+    /// The rest of the line after the value, e.g. a comment.
+    trailing: String,
+}
+
+impl Section {
+    fn new(name: String, leading: Vec<String>) -> Self {
+        let header = format!("[{name}]");
+        Section {
+            name,
+            leading,
+            header,
+            lines: Vec::new(),
+        }
+    }
+
+    fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.lines.iter().filter_map(|line| match line {
+            Line::Entry(entry) => Some(entry),
+            Line::Comment(_) => None,
+        })
+    }
+
+    fn entry_mut(&mut self, key: &str) -> Option<&mut Entry> {
+        self.lines.iter_mut().find_map(|line| match line {
+            Line::Entry(entry) if entry.key == key => Some(entry),
+            _ => None,
+        })
+    }
+
+    /// Adds an entry after the last existing one, so that it stays before comments at the end
+    /// of the section, or replaces the value of an entry with the same key.
+    fn insert(&mut self, entry: Entry) -> Option<VariantValue> {
+        if let Some(existing) = self.entry_mut(&entry.key) {
+            let old = mem::replace(existing, entry);
+            return Some(old.value);
+        }
+
+        let index = self
+            .lines
+            .iter()
+            .rposition(|line| matches!(line, Line::Entry(_)))
+            .map_or(self.lines.len(), |i| i + 1);
+        self.lines.insert(index, Line::Entry(entry));
+        None
+    }
+}
+
+impl ConfigDocument {
+    /// Creates an empty document.
+    #[inline]
+    pub fn new() -> Self {
+        ConfigDocument {
+            sections: vec![Section::new(String::new(), Vec::new())],
+        }
+    }
+
+    /// Parses a document, like `ConfigFile.parse`.
+    #[inline]
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        DocumentParser::new(text).parse()
+    }
+
+    /// Returns `true` if the document contains no sections and no keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sections.len() == 1 && self.sections[0].entries().next().is_none()
+    }
+
+    /// Returns an iterator over the names of the sections, in document order.
+    ///
+    /// The unnamed section is included only if it contains keys.
+    #[inline]
+    pub fn sections(&self) -> Sections<'_> {
+        Sections {
+            iter: self.sections.iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys and values of a section, in document order.
+    ///
+    /// The iterator is empty if the section doesn't exist.
+    #[inline]
+    pub fn entries(&self, section: &str) -> Entries<'_> {
+        let lines = self.section(section).map_or(&[][..], |s| &s.lines[..]);
+        Entries { iter: lines.iter() }
+    }
+
+    /// Returns `true` if the document contains a section with the given name.
+    #[inline]
+    pub fn has_section(&self, section: &str) -> bool {
+        self.sections().any(|name| name == section)
+    }
+
+    /// Returns `true` if the document contains the given key in the given section.
+    #[inline]
+    pub fn has_section_key(&self, section: &str, key: &str) -> bool {
+        self.get(section, key).is_some()
+    }
+
+    /// Returns the value of a key, or `None` if the key or section doesn't exist.
+    #[inline]
+    pub fn get(&self, section: &str, key: &str) -> Option<&VariantValue> {
+        self.section(section)?
+            .entries()
+            .find(|entry| entry.key == key)
+            .map(|entry| &entry.value)
+    }
+
+    /// Sets the value of a key, returning the old value if the key already existed.
+    ///
+    /// Existing keys keep their position and comments. New keys are added after the last key
+    /// of the section, and new sections are added at the end of the document.
+    #[inline]
+    pub fn set(
+        &mut self,
+        section: &str,
+        key: &str,
+        value: impl Into<VariantValue>,
+    ) -> Option<VariantValue> {
+        let entry = Entry {
+            key: key.to_owned(),
+            value: value.into(),
+            source: None,
+            trailing: String::new(),
+        };
+
+        if let Some(existing) = self.section_mut(section).and_then(|s| s.entry_mut(key)) {
+            existing.source = None;
+            return Some(mem::replace(&mut existing.value, entry.value));
+        }
+
+        let is_empty = self.is_empty();
+        let section = match self.sections.iter().position(|s| s.name == section) {
+            Some(0) => {
+                // Keys added to the unnamed section after other sections were created need to
+                // be separated from the first of them with a blank line as well.
+                let root_is_empty = self.sections[0].entries().next().is_none();
+                if let Some(first) = self.sections.get_mut(1) {
+                    if root_is_empty && first.leading.is_empty() {
+                        first.leading.push(String::new());
+                    }
+                }
+                &mut self.sections[0]
+            }
+            Some(index) => &mut self.sections[index],
+            None => {
+                // Like `ConfigFile`, separate sections with a blank line.
+                let leading = if is_empty {
+                    Vec::new()
+                } else {
+                    vec![String::new()]
+                };
+                self.sections
+                    .push(Section::new(section.to_owned(), leading));
+                self.sections.last_mut().unwrap()
+            }
+        };
+        section.insert(entry)
+    }
+
+    /// Removes a key, returning its value if it existed. Comments around the key are kept.
+    #[inline]
+    pub fn erase(&mut self, section: &str, key: &str) -> Option<VariantValue> {
+        let lines = &mut self.section_mut(section)?.lines;
+        let index = lines
+            .iter()
+            .position(|line| matches!(line, Line::Entry(entry) if entry.key == key))?;
+        match lines.remove(index) {
+            Line::Entry(entry) => Some(entry.value),
+            Line::Comment(_) => unreachable!("index should point to an entry"),
+        }
+    }
+
+    /// Removes a section with all its keys and comments, returning `true` if it existed.
+    ///
+    /// Erasing the unnamed section removes its keys, but keeps the comments at the start of
+    /// the document.
+    #[inline]
+    pub fn erase_section(&mut self, section: &str) -> bool {
+        if section.is_empty() {
+            let lines = &mut self.sections[0].lines;
+            let len = lines.len();
+            lines.retain(|line| matches!(line, Line::Comment(_)));
+            return lines.len() != len;
+        }
+
+        match self.sections.iter().position(|s| s.name == section) {
+            Some(index) => {
+                self.sections.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    fn section_mut(&mut self, name: &str) -> Option<&mut Section> {
+        self.sections.iter_mut().find(|s| s.name == name)
+    }
+}
+
+impl Default for ConfigDocument {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromStr for ConfigDocument {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for ConfigDocument {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, section) in self.sections.iter().enumerate() {
+            for line in &section.leading {
+                writeln!(f, "{line}")?;
+            }
+            if i > 0 {
+                writeln!(f, "{}", section.header)?;
+            }
+
+            for line in &section.lines {
+                match line {
+                    Line::Comment(comment) => writeln!(f, "{comment}")?,
+                    Line::Entry(entry) => {
+                        match &entry.source {
+                            Some(source) => f.write_str(source)?,
+                            None => {
+                                text::write_property_name(f, &entry.key)?;
+                                write!(f, "={}", entry.value)?;
+                            }
+                        }
+                        writeln!(f, "{}", entry.trailing)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the section names of a [`ConfigDocument`].
+#[derive(Clone, Debug)]
+pub struct Sections<'a> {
+    iter: slice::Iter<'a, Section>,
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .find(|s| !s.name.is_empty() || s.entries().next().is_some())
+            .map(|s| s.name.as_str())
+    }
+}
+
+/// Iterator over the keys and values of a section of a [`ConfigDocument`].
+#[derive(Clone, Debug)]
+pub struct Entries<'a> {
+    iter: slice::Iter<'a, Line>,
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = (&'a str, &'a VariantValue);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.find_map(|line| match line {
+            Line::Entry(entry) => Some((entry.key.as_str(), &entry.value)),
+            Line::Comment(_) => None,
+        })
+    }
+}
+
+struct DocumentParser<'a> {
+    src: &'a str,
+    parser: Parser<'a>,
+    document: ConfigDocument,
+    /// Comment and blank lines that are not yet assigned to a section.
+    pending: Vec<String>,
+}
+
+impl<'a> DocumentParser<'a> {
+    fn new(src: &'a str) -> Self {
+        DocumentParser {
+            src,
+            parser: Parser::new(src),
+            document: ConfigDocument::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn parse(mut self) -> Result<ConfigDocument, ParseError> {
+        while self.parser.position() < self.src.len() {
+            let start = self.parser.position();
+            let line = self.line_at(start);
+            let content = line.trim();
+
+            if content.is_empty() || content.starts_with(';') {
+                self.pending.push(line.to_owned());
+                self.next_line(start + line.len());
+            } else if content.starts_with('[') {
+                self.header(start, line)?;
+            } else {
+                self.entry(start)?;
+            }
+        }
+
+        let last = self.document.sections.last_mut().unwrap();
+        last.lines.extend(self.pending.drain(..).map(Line::Comment));
+        Ok(self.document)
+    }
+
+    /// Returns the line starting at `pos`, without the line break.
+    fn line_at(&self, pos: usize) -> &'a str {
+        let rest = &self.src[pos..];
+        &rest[..rest.find('\n').unwrap_or(rest.len())]
+    }
+
+    /// Moves to the start of the line after the one ending at `line_end`.
+    fn next_line(&mut self, line_end: usize) {
+        let next = if self.src[line_end..].starts_with('\n') {
+            line_end + 1
+        } else {
+            line_end
+        };
+        self.parser.set_position(next);
+    }
+
+    /// Checks that the rest of the line at `pos` contains only whitespace and comments.
+    fn end_of_line(&self, pos: usize) -> Result<&'a str, ParseError> {
+        let rest = self.line_at(pos);
+        let content = rest.trim_start();
+        if content.is_empty() || content.starts_with(';') {
+            Ok(rest)
+        } else {
+            let offset = rest.len() - content.len();
+            Err(self
+                .parser
+                .error_at(pos + offset, ParseErrorKind::TrailingCharacters))
+        }
+    }
+
+    fn header(&mut self, start: usize, line: &'a str) -> Result<(), ParseError> {
+        let open = line.find('[').expect("header should start with '['");
+        let close = line
+            .find(']')
+            .ok_or_else(|| self.expected(start + line.len(), "']'"))?;
+        self.end_of_line(start + close + 1)?;
+
+        let name = line[open + 1..close].trim().to_owned();
+        let leading = mem::take(&mut self.pending);
+        self.document.sections.push(Section {
+            name,
+            leading,
+            header: line.to_owned(),
+            lines: Vec::new(),
+        });
+
+        self.next_line(start + line.len());
+        Ok(())
+    }
+
+    fn entry(&mut self, start: usize) -> Result<(), ParseError> {
+        let line = self.line_at(start);
+        let key_start = start + (line.len() - line.trim_start().len());
+        self.parser.set_position(key_start);
+        let key = self.parser.property_name()?;
+
+        let value = self.parser.value()?;
+        let value_end = self.parser.position();
+        let trailing = self.end_of_line(value_end)?;
+
+        let section = self.document.sections.last_mut().unwrap();
+        section
+            .lines
+            .extend(self.pending.drain(..).map(Line::Comment));
+        // Like in `ConfigFile`, a repeated key replaces the value of the earlier one.
+        section.insert(Entry {
+            key,
+            value,
+            source: Some(self.src[start..value_end].to_owned()),
+            trailing: trailing.to_owned(),
+        });
+
+        self.next_line(value_end + trailing.len());
+        Ok(())
+    }
+
+    fn expected(&self, pos: usize, expected: &'static str) -> ParseError {
+        let kind = if pos == self.src.len() {
+            ParseErrorKind::UnexpectedEof
+        } else {
+            ParseErrorKind::Expected(expected)
+        };
+        self.parser.error_at(pos, kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = r#"; Engine configuration file.
+; Don't edit by hand.
+
+config_version=4
+
+[application]
+
+config/name="Demo"
+run/main_scene="res://Main.tscn" ; the entry point
+config/icon="res://icon.png"
+
+[input]
+
+ui_accept={
+"deadzone": 0.5,
+"events": [  ]
+}
+"key with spaces"=Vector2( 1, 2 )
+
+; trailing comment
+"#;
+
+    #[test]
+    fn round_trip() {
+        let doc = ConfigDocument::parse(PROJECT).unwrap();
+        assert_eq!(PROJECT, doc.to_string());
+
+        assert_eq!(
+            vec!["", "application", "input"],
+            doc.sections().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&4.into()), doc.get("", "config_version"));
+        assert_eq!(
+            Some(&"res://Main.tscn".into()),
+            doc.get("application", "run/main_scene")
+        );
+        assert_eq!(
+            Some(&Vector2::new(1.0, 2.0).into()),
+            doc.get("input", "key with spaces")
+        );
+        assert_eq!(
+            vec!["ui_accept", "key with spaces"],
+            doc.entries("input").map(|(k, _)| k).collect::<Vec<_>>()
+        );
+        assert!(doc.has_section_key("input", "ui_accept"));
+        assert!(!doc.has_section("display"));
+    }
+
+    #[test]
+    fn modify() {
+        let mut doc: ConfigDocument = PROJECT.parse().unwrap();
+
+        assert_eq!(
+            Some("res://Main.tscn".into()),
+            doc.set("application", "run/main_scene", "res://Title.tscn")
+        );
+        assert_eq!(None, doc.set("input", "a=b", true));
+        assert_eq!(None, doc.set("display", "window/size/width", 1280));
+        assert_eq!(
+            Some("res://icon.png".into()),
+            doc.erase("application", "config/icon")
+        );
+        assert!(doc.erase_section(""));
+
+        let expected = r#"; Engine configuration file.
+; Don't edit by hand.
+
+
+[application]
+
+config/name="Demo"
+run/main_scene="res://Title.tscn" ; the entry point
+
+[input]
+
+ui_accept={
+"deadzone": 0.5,
+"events": [  ]
+}
+"key with spaces"=Vector2( 1, 2 )
+"a=b"=true
+
+; trailing comment
+
+[display]
+window/size/width=1280
+"#;
+        assert_eq!(expected, doc.to_string());
+
+        assert!(doc.erase_section("input"));
+        assert!(!doc.erase_section("input"));
+        assert_eq!(
+            vec!["application", "display"],
+            doc.sections().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn new_document() {
+        let mut doc = ConfigDocument::new();
+        assert!(doc.is_empty());
+        assert_eq!("", doc.to_string());
+
+        doc.set("first", "a", 1);
+        doc.set("", "root", "value");
+        doc.set("second", "b", VariantValue::Nil);
+        assert_eq!(
+            "root=\"value\"\n\n[first]\na=1\n\n[second]\nb=null\n",
+            doc.to_string()
+        );
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let doc = ConfigDocument::parse("[a]\nx=1\ny=2\nx=3\n").unwrap();
+        assert_eq!(Some(&3.into()), doc.get("a", "x"));
+        assert_eq!("[a]\nx=3\ny=2\n", doc.to_string());
+    }
+
+    #[test]
+    fn errors() {
+        fn error(text: &str) -> (usize, usize, ParseErrorKind) {
+            let err = ConfigDocument::parse(text).unwrap_err();
+            (err.line(), err.column(), err.kind().clone())
+        }
+
+        assert_eq!(
+            (2, 9, ParseErrorKind::Expected("']'")),
+            error("a=1\n[section\nb=2\n")
+        );
+        assert_eq!(
+            (2, 4, ParseErrorKind::Expected("'='")),
+            error("[a]\nkey\nb=2")
+        );
+        assert_eq!((1, 7, ParseErrorKind::Expected("'='")), error("\"key\" 1"));
+        assert_eq!(
+            (1, 1, ParseErrorKind::Expected("property name")),
+            error("=1\n")
+        );
+        assert_eq!(
+            (2, 5, ParseErrorKind::TrailingCharacters),
+            error("[a]\nx=1 y=2\n")
+        );
+        assert_eq!((1, 5, ParseErrorKind::TrailingCharacters), error("[a] b"));
+        assert_eq!(
+            (2, 5, ParseErrorKind::UnknownIdentifier("Vector9".into())),
+            error("[a]\nkey=Vector9(1)\n")
+        );
+        assert_eq!((1, 3, ParseErrorKind::UnexpectedEof), error("a="));
+    }
+}
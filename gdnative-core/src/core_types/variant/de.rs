@@ -0,0 +1,455 @@
+use super::*;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+
+/// Deserializes a value from a `Variant` using its `Deserialize` implementation.
+///
+/// This accepts the representations produced by [`to_variant`], with the exception that enums
+/// can be deserialized from any representation that `serde` supports for self-describing
+/// formats. Godot's core types like `Vector2` and `Color` can be deserialized from their
+/// corresponding `Variant` types, as well as from dictionaries of their fields.
+/// `VariantDispatch` can be deserialized from any `Variant`, except for objects and `Rid`s,
+/// which return an error.
+///
+/// # Errors
+///
+/// Errors from `Deserialize` implementations are returned as [`FromVariantError::Custom`].
+/// Errors in collection items are wrapped in [`FromVariantError::InvalidItem`].
+///
+/// # Example
+///
+/// ```no_run
+/// use gdnative::core_types;
+/// use gdnative::prelude::*;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Player {
+///     name: String,
+///     position: Vector2,
+/// }
+///
+/// let dict = Dictionary::new();
+/// dict.insert("name", "Godette");
+/// dict.insert("position", Vector2::new(1.0, 2.0));
+///
+/// let player: Player = core_types::from_variant(&dict.owned_to_variant()).unwrap();
+/// assert_eq!(Vector2::new(1.0, 2.0), player.position);
+/// ```
+#[inline]
+pub fn from_variant<T>(variant: &Variant) -> Result<T, FromVariantError>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(VariantDeserializer::new(variant))
+}
+
+impl de::Error for FromVariantError {
+    #[inline]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        FromVariantError::custom(msg)
+    }
+}
+
+/// A `Deserializer` that reads values from `Variant`s. See [`from_variant`] for details.
+#[derive(Debug)]
+pub struct VariantDeserializer {
+    variant: Variant,
+    /// Whether the `Variant` is the contents of a `VariantDispatch`, in which case
+    /// dictionaries are read as sequences of `{ key, value }` entries.
+    dispatch: bool,
+}
+
+impl VariantDeserializer {
+    /// Creates a deserializer that reads from `variant`.
+    #[inline]
+    pub fn new(variant: &Variant) -> Self {
+        VariantDeserializer {
+            variant: variant.clone(),
+            dispatch: false,
+        }
+    }
+
+    fn invalid_type(&self, expected: &dyn de::Expected) -> FromVariantError {
+        de::Error::custom(format_args!(
+            "invalid variant type {:?}, expected {expected}",
+            self.variant.get_type()
+        ))
+    }
+}
+
+impl<'de> IntoDeserializer<'de, FromVariantError> for VariantDeserializer {
+    type Deserializer = Self;
+
+    #[inline]
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn deserializer(variant: Variant) -> VariantDeserializer {
+    VariantDeserializer {
+        variant,
+        dispatch: false,
+    }
+}
+
+fn items<T: ToVariant>(items: impl IntoIterator<Item = T>) -> Vec<Variant> {
+    items.into_iter().map(|item| item.to_variant()).collect()
+}
+
+fn fields<const N: usize>(fields: [(&'static str, Variant); N]) -> Vec<(Variant, Variant)> {
+    fields
+        .into_iter()
+        .map(|(key, value)| (key.to_variant(), value))
+        .collect()
+}
+
+/// Returns the fields of Godot's core types, in the form expected by their `Deserialize`
+/// implementations.
+fn core_type_fields(dispatch: &VariantDispatch) -> Option<Vec<(Variant, Variant)>> {
+    let fields = match dispatch {
+        VariantDispatch::Vector2(v) => fields([("x", v.x.to_variant()), ("y", v.y.to_variant())]),
+        VariantDispatch::Vector3(v) => fields([
+            ("x", v.x.to_variant()),
+            ("y", v.y.to_variant()),
+            ("z", v.z.to_variant()),
+        ]),
+        VariantDispatch::Color(v) => fields([
+            ("r", v.r.to_variant()),
+            ("g", v.g.to_variant()),
+            ("b", v.b.to_variant()),
+            ("a", v.a.to_variant()),
+        ]),
+        VariantDispatch::Quat(v) => fields([
+            ("x", v.x.to_variant()),
+            ("y", v.y.to_variant()),
+            ("z", v.z.to_variant()),
+            ("w", v.w.to_variant()),
+        ]),
+        VariantDispatch::Rect2(v) => fields([
+            ("position", v.position.to_variant()),
+            ("size", v.size.to_variant()),
+        ]),
+        VariantDispatch::Transform2D(v) => fields([
+            ("a", v.a.to_variant()),
+            ("b", v.b.to_variant()),
+            ("origin", v.origin.to_variant()),
+        ]),
+        VariantDispatch::Plane(v) => {
+            fields([("normal", v.normal.to_variant()), ("d", v.d.to_variant())])
+        }
+        VariantDispatch::Aabb(v) => fields([
+            ("position", v.position.to_variant()),
+            ("size", v.size.to_variant()),
+        ]),
+        VariantDispatch::Basis(v) => fields([("elements", (&v.elements[..]).to_variant())]),
+        VariantDispatch::Transform(v) => fields([
+            ("basis", v.basis.to_variant()),
+            ("origin", v.origin.to_variant()),
+        ]),
+        _ => return None,
+    };
+
+    Some(fields)
+}
+
+impl<'de> de::Deserializer<'de> for VariantDeserializer {
+    type Error = FromVariantError;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, FromVariantError>
+    where
+        V: Visitor<'de>,
+    {
+        let dispatch = self.variant.dispatch();
+        if let Some(fields) = core_type_fields(&dispatch) {
+            return visitor.visit_map(MapDeserializer::new(fields));
+        }
+
+        match dispatch {
+            VariantDispatch::Nil => visitor.visit_unit(),
+            VariantDispatch::Bool(v) => visitor.visit_bool(v),
+            VariantDispatch::I64(v) => visitor.visit_i64(v),
+            VariantDispatch::F64(v) => visitor.visit_f64(v),
+            VariantDispatch::GodotString(v) => visitor.visit_string(v.to_string()),
+            VariantDispatch::NodePath(v) => visitor.visit_string(v.to_string()),
+            VariantDispatch::Dictionary(dict) if self.dispatch => {
+                let entries = dict.iter().map(|(key, value)| {
+                    let entry = Dictionary::new();
+                    entry.insert("key", key);
+                    entry.insert("value", value);
+                    entry.owned_to_variant()
+                });
+                visitor.visit_seq(SeqDeserializer::new(entries.collect()))
+            }
+            VariantDispatch::Dictionary(dict) => {
+                visitor.visit_map(MapDeserializer::new(dict.iter().collect()))
+            }
+            VariantDispatch::VariantArray(v) => {
+                visitor.visit_seq(SeqDeserializer::new(v.iter().collect()))
+            }
+            VariantDispatch::ByteArray(v) => {
+                visitor.visit_seq(SeqDeserializer::new(items(v.read().iter())))
+            }
+            VariantDispatch::Int32Array(v) => {
+                visitor.visit_seq(SeqDeserializer::new(items(v.read().iter())))
+            }
+            VariantDispatch::Float32Array(v) => {
+                visitor.visit_seq(SeqDeserializer::new(items(v.read().iter())))
+            }
+            VariantDispatch::StringArray(v) => {
+                visitor.visit_seq(SeqDeserializer::new(items(v.read().iter())))
+            }
+            VariantDispatch::Vector2Array(v) => {
+                visitor.visit_seq(SeqDeserializer::new(items(v.read().iter())))
+            }
+            VariantDispatch::Vector3Array(v) => {
+                visitor.visit_seq(SeqDeserializer::new(items(v.read().iter())))
+            }
+            VariantDispatch::ColorArray(v) => {
+                visitor.visit_seq(SeqDeserializer::new(items(v.read().iter())))
+            }
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    #[inline]
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, FromVariantError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.variant.dispatch() {
+            VariantDispatch::ByteArray(v) => visitor.visit_bytes(&v.read()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, FromVariantError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, FromVariantError>
+    where
+        V: Visitor<'de>,
+    {
+        if self.variant.is_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, FromVariantError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, FromVariantError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FromVariantError>
+    where
+        V: Visitor<'de>,
+    {
+        if name == super::serialize::VARIANT_DISPATCH_NAME {
+            let variant_type = self.variant.get_type();
+            return visitor.visit_enum(EnumDeserializer {
+                variant: variant_type.name().to_variant(),
+                value: VariantDeserializer {
+                    variant: self.variant,
+                    dispatch: true,
+                },
+            });
+        }
+
+        match self.variant.dispatch() {
+            VariantDispatch::GodotString(s) => {
+                let variant: de::value::StringDeserializer<FromVariantError> =
+                    s.to_string().into_deserializer();
+                visitor.visit_enum(variant)
+            }
+            VariantDispatch::Dictionary(dict) if dict.len() == 1 => {
+                let (variant, value) = dict.iter().next().expect("length should be checked");
+                visitor.visit_enum(EnumDeserializer {
+                    variant,
+                    value: deserializer(value),
+                })
+            }
+            _ => Err(FromVariantError::InvalidEnumRepr {
+                expected: VariantEnumRepr::ExternallyTagged,
+                error: Box::new(self.invalid_type(&"a string or a dictionary with one entry")),
+            }),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct SeqDeserializer {
+    iter: std::iter::Enumerate<std::vec::IntoIter<Variant>>,
+    len: usize,
+}
+
+impl SeqDeserializer {
+    fn new(items: Vec<Variant>) -> Self {
+        SeqDeserializer {
+            len: items.len(),
+            iter: items.into_iter().enumerate(),
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer {
+    type Error = FromVariantError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, FromVariantError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((index, item)) => seed
+                .deserialize(deserializer(item))
+                .map(Some)
+                .map_err(|err| FromVariantError::InvalidItem {
+                    index,
+                    error: Box::new(err),
+                }),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+struct MapDeserializer {
+    iter: std::vec::IntoIter<(Variant, Variant)>,
+    value: Option<Variant>,
+    len: usize,
+}
+
+impl MapDeserializer {
+    fn new(entries: Vec<(Variant, Variant)>) -> Self {
+        MapDeserializer {
+            len: entries.len(),
+            iter: entries.into_iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = FromVariantError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, FromVariantError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(deserializer(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, FromVariantError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self
+            .value
+            .take()
+            .expect("next_key_seed should be called before next_value_seed");
+        seed.deserialize(deserializer(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+struct EnumDeserializer {
+    variant: Variant,
+    value: VariantDeserializer,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = FromVariantError;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, VariantDeserializer), FromVariantError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(deserializer(self.variant))?;
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = FromVariantError;
+
+    fn unit_variant(self) -> Result<(), FromVariantError> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, FromVariantError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, FromVariantError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FromVariantError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -0,0 +1,914 @@
+//! Conversion between values and JSON, following the rules of Godot's `JSON.print` and
+//! `JSON.parse`.
+//!
+//! The functions in this module are implemented in pure Rust. The [`write_value`] and
+//! [`parse_value`] functions operate on [`VariantValue`]s and do not require a running engine,
+//! so they can be used on worker threads, in unit tests and in offline tools.
+//!
+//! Like in Godot:
+//!
+//! - All numbers are parsed as `F64`. Integers are written without a decimal point, and floats
+//!   with at most 14 decimal places and without trailing zeros, so `2.0` is written as `2`.
+//! - `VariantArray`, `Int32Array`, `Float32Array` and `StringArray` are written as JSON arrays.
+//!   Dictionaries are written as JSON objects, with their keys converted to strings.
+//! - Other types are written as strings, in the format of GDScript's `str()`. For example, a
+//!   `Vector2` is written as `"(1, 2)"`.
+//! - Non-finite floats are written as `inf`, `-inf` and `nan`, which are not valid JSON.
+//! - Trailing commas in arrays and objects are accepted when parsing.
+//!
+//! Unlike Godot, the parser rejects input with trailing characters after the value, and errors
+//! report the column in addition to the line.
+//!
+//! ## Example
+//!
+//! ```
+//! use gdnative::core_types::json::{self, JsonOptions};
+//! use gdnative::core_types::VariantValue;
+//!
+//! let value = json::parse_value(r#"{ "name": "Godot", "version": 3 }"#).unwrap();
+//! assert_eq!(
+//!     VariantValue::Dictionary(vec![
+//!         ("name".into(), "Godot".into()),
+//!         ("version".into(), 3.0.into()),
+//!     ]),
+//!     value,
+//! );
+//!
+//! let options = JsonOptions::new().indent("\t").sort_keys(true);
+//! assert_eq!(
+//!     "{\n\t\"name\": \"Godot\",\n\t\"version\": 3\n}",
+//!     json::write_value(&value, &options),
+//! );
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::hash_map::{Entry, HashMap};
+use std::fmt::{self, Write};
+
+use super::value::MAX_DEPTH;
+use super::*;
+
+/// Options for writing JSON, corresponding to the optional arguments of `JSON.print`.
+///
+/// The default options produce compact output, with dictionary entries in insertion order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonOptions {
+    indent: String,
+    sort_keys: bool,
+}
+
+impl JsonOptions {
+    /// Returns the default options.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the string written for each level of indentation. If it is not empty, every array
+    /// element and dictionary entry is written on its own line, and a space is written after
+    /// each colon.
+    #[inline]
+    pub fn indent(mut self, indent: impl Into<String>) -> Self {
+        self.indent = indent.into();
+        self
+    }
+
+    /// Sets whether dictionary entries are sorted by key. Keys are ordered by type first, and
+    /// then by value, like `Array.sort()` orders them.
+    #[inline]
+    pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+}
+
+/// Error that can occur when parsing JSON, carrying the same information as Godot's
+/// `JSONParseResult`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonError {
+    line: usize,
+    column: usize,
+    kind: JsonErrorKind,
+}
+
+/// The kind of a [`JsonError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonErrorKind {
+    /// The input ended before the value was complete.
+    UnexpectedEof,
+    /// A character that cannot start a token was found.
+    UnexpectedChar(char),
+    /// Something else was found where the given token was expected.
+    Expected(&'static str),
+    /// A number literal is malformed.
+    InvalidNumber(String),
+    /// A string literal contains an invalid escape sequence.
+    InvalidEscape,
+    /// An identifier other than `true`, `false` or `null` was found.
+    UnknownIdentifier(String),
+    /// Arrays and objects are nested deeper than Godot allows.
+    TooDeep,
+    /// The input contains more characters after a complete value.
+    TrailingCharacters,
+}
+
+impl JsonError {
+    /// Returns the 1-based line number where the error occurred.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the 1-based column number, in characters, where the error occurred.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the kind of this error.
+    #[inline]
+    pub fn kind(&self) -> &JsonErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for JsonErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use JsonErrorKind as E;
+
+        match self {
+            E::UnexpectedEof => write!(f, "unexpected end of input"),
+            E::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+            E::Expected(expected) => write!(f, "expected {expected}"),
+            E::InvalidNumber(s) => write!(f, "invalid number {s:?}"),
+            E::InvalidEscape => write!(f, "invalid escape sequence"),
+            E::UnknownIdentifier(s) => {
+                write!(f, "expected 'true', 'false' or 'null', got {s:?}")
+            }
+            E::TooDeep => write!(f, "maximum nesting depth of {MAX_DEPTH} exceeded"),
+            E::TrailingCharacters => write!(f, "trailing characters after value"),
+        }
+    }
+}
+
+impl fmt::Display for JsonError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// Writes a `Variant` as compact JSON, like `JSON.print(variant)`.
+///
+/// # Errors
+///
+/// Returns an error if the `Variant` contains collections nested deeper than Godot allows,
+/// e.g. an array that contains itself.
+#[inline]
+pub fn to_string(variant: &Variant) -> Result<String, FromVariantError> {
+    to_string_with(variant, &JsonOptions::default())
+}
+
+/// Writes a `Variant` as JSON with the given options, like
+/// `JSON.print(variant, indent, sort_keys)`.
+///
+/// # Errors
+///
+/// Returns an error if the `Variant` contains collections nested deeper than Godot allows,
+/// e.g. an array that contains itself.
+#[inline]
+pub fn to_string_with(
+    variant: &Variant,
+    options: &JsonOptions,
+) -> Result<String, FromVariantError> {
+    VariantValue::from_variant(variant).map(|value| write_value(&value, options))
+}
+
+/// Parses a `Variant` from JSON, like `JSON.parse(text).result`.
+#[inline]
+pub fn parse(text: &str) -> Result<Variant, JsonError> {
+    parse_value(text).map(|value| value.to_variant())
+}
+
+/// Writes a `VariantValue` as JSON with the given options.
+#[inline]
+pub fn write_value(value: &VariantValue, options: &JsonOptions) -> String {
+    let mut out = String::new();
+    Writer { options }
+        .value(&mut out, value, 0)
+        .expect("writing to a String should not fail");
+    out
+}
+
+/// Parses a `VariantValue` from JSON.
+///
+/// The input must contain exactly one value, optionally surrounded by whitespace. Numbers are
+/// always parsed as `F64`, and objects as `Dictionary`s with `GodotString` keys.
+#[inline]
+pub fn parse_value(text: &str) -> Result<VariantValue, JsonError> {
+    let mut parser = Parser { src: text, pos: 0 };
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    if parser.peek().is_some() {
+        return Err(parser.error(JsonErrorKind::TrailingCharacters));
+    }
+    Ok(value)
+}
+
+struct Writer<'a> {
+    options: &'a JsonOptions,
+}
+
+impl<'a> Writer<'a> {
+    fn value<W: Write>(&self, w: &mut W, value: &VariantValue, depth: usize) -> fmt::Result {
+        use VariantValue as V;
+
+        match value {
+            V::Nil => w.write_str("null"),
+            V::Bool(b) => w.write_str(if *b { "true" } else { "false" }),
+            V::I64(i) => write!(w, "{i}"),
+            V::F64(f) => write_num(w, *f),
+            V::GodotString(s) => write_string(w, s),
+            V::VariantArray(items) => self.seq(w, '[', ']', items, depth, |w, item| {
+                self.value(w, item, depth + 1)
+            }),
+            V::Int32Array(items) => self.seq(w, '[', ']', items, depth, |w, i| write!(w, "{i}")),
+            V::Float32Array(items) => self.seq(w, '[', ']', items, depth, |w, f| {
+                write_num(w, f64::from(*f))
+            }),
+            V::StringArray(items) => self.seq(w, '[', ']', items, depth, |w, s| write_string(w, s)),
+            V::Dictionary(entries) => {
+                let mut entries: Vec<_> = entries.iter().collect();
+                if self.options.sort_keys {
+                    entries.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+                }
+
+                let colon = if self.options.indent.is_empty() {
+                    ":"
+                } else {
+                    ": "
+                };
+                self.seq(w, '{', '}', entries, depth, |w, (key, value)| {
+                    write_string(w, &godot_str(key))?;
+                    w.write_str(colon)?;
+                    self.value(w, value, depth + 1)
+                })
+            }
+            other => write_string(w, &godot_str(other)),
+        }
+    }
+
+    /// Writes a sequence like Godot does, including its formatting of empty collections with
+    /// indentation enabled, which is an empty line between the brackets.
+    fn seq<W: Write, I: IntoIterator>(
+        &self,
+        w: &mut W,
+        open: char,
+        close: char,
+        items: I,
+        depth: usize,
+        mut write_item: impl FnMut(&mut W, I::Item) -> fmt::Result,
+    ) -> fmt::Result {
+        let newline = if self.options.indent.is_empty() {
+            ""
+        } else {
+            "\n"
+        };
+
+        w.write_char(open)?;
+        w.write_str(newline)?;
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                w.write_char(',')?;
+                w.write_str(newline)?;
+            }
+            self.indent(w, depth + 1)?;
+            write_item(w, item)?;
+        }
+        w.write_str(newline)?;
+        self.indent(w, depth)?;
+        w.write_char(close)
+    }
+
+    fn indent<W: Write>(&self, w: &mut W, depth: usize) -> fmt::Result {
+        for _ in 0..depth {
+            w.write_str(&self.options.indent)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a quoted string, escaping characters like Godot's `String.json_escape()`. Other
+/// control characters are written as `\u` escapes, so that the output is valid JSON.
+fn write_string<W: Write>(w: &mut W, s: &str) -> fmt::Result {
+    w.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            '\u{8}' => w.write_str("\\b")?,
+            '\u{c}' => w.write_str("\\f")?,
+            '\n' => w.write_str("\\n")?,
+            '\r' => w.write_str("\\r")?,
+            '\t' => w.write_str("\\t")?,
+            c if c < ' ' => write!(w, "\\u{:04x}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
+
+/// Writes a float like Godot's `String::num` with the default precision: 14 decimal places,
+/// reduced by the number of integer digits above 2, without trailing zeros.
+fn write_num<W: Write>(w: &mut W, v: f64) -> fmt::Result {
+    if v.is_nan() {
+        return w.write_str("nan");
+    }
+    if v.is_infinite() {
+        return w.write_str(if v > 0.0 { "inf" } else { "-inf" });
+    }
+
+    let mut decimals = 14;
+    let abs = v.abs();
+    if abs > 10.0 {
+        decimals -= abs.log10().floor() as i32;
+    }
+    // Godot falls back to the default precision of `printf` for very large numbers.
+    let decimals = usize::try_from(decimals).unwrap_or(6);
+
+    let s = format!("{v:.decimals$}");
+    if s.contains('.') {
+        w.write_str(s.trim_end_matches('0').trim_end_matches('.'))
+    } else {
+        w.write_str(&s)
+    }
+}
+
+/// Writes a `f32` component of a math type. Godot writes these with the precision of its
+/// `real_t`, which the shortest representation that parses back to the same `f32` matches.
+fn write_real<W: Write>(w: &mut W, v: f32) -> fmt::Result {
+    if v.is_nan() {
+        w.write_str("nan")
+    } else if v.is_infinite() {
+        w.write_str(if v > 0.0 { "inf" } else { "-inf" })
+    } else {
+        write!(w, "{v}")
+    }
+}
+
+fn write_reals<W: Write>(w: &mut W, components: &[f32]) -> fmt::Result {
+    for (i, v) in components.iter().enumerate() {
+        if i > 0 {
+            w.write_str(", ")?;
+        }
+        write_real(w, *v)?;
+    }
+    Ok(())
+}
+
+fn write_vector2<W: Write>(w: &mut W, v: Vector2) -> fmt::Result {
+    w.write_char('(')?;
+    write_reals(w, &[v.x, v.y])?;
+    w.write_char(')')
+}
+
+fn write_vector3<W: Write>(w: &mut W, v: Vector3) -> fmt::Result {
+    w.write_char('(')?;
+    write_reals(w, &[v.x, v.y, v.z])?;
+    w.write_char(')')
+}
+
+fn write_basis<W: Write>(w: &mut W, basis: &Basis) -> fmt::Result {
+    let [x, y, z] = basis.elements;
+    write_reals(w, &[x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z])
+}
+
+fn write_list<W: Write, T>(
+    w: &mut W,
+    items: &[T],
+    mut write_item: impl FnMut(&mut W, &T) -> fmt::Result,
+) -> fmt::Result {
+    w.write_char('[')?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            w.write_str(", ")?;
+        }
+        write_item(w, item)?;
+    }
+    w.write_char(']')
+}
+
+/// Converts a value to a string like GDScript's `str()`, which is how Godot writes dictionary
+/// keys and values without a JSON representation.
+fn godot_str(value: &VariantValue) -> String {
+    let mut out = String::new();
+    write_godot_str(&mut out, value).expect("writing to a String should not fail");
+    out
+}
+
+fn write_godot_str<W: Write>(w: &mut W, value: &VariantValue) -> fmt::Result {
+    use VariantValue as V;
+
+    match value {
+        V::Nil => w.write_str("Null"),
+        V::Bool(b) => w.write_str(if *b { "True" } else { "False" }),
+        V::I64(i) => write!(w, "{i}"),
+        V::F64(f) => write_num(w, *f),
+        V::GodotString(s) | V::NodePath(s) => w.write_str(s),
+        V::Vector2(v) => write_vector2(w, *v),
+        V::Rect2(r) => {
+            write_vector2(w, r.position)?;
+            w.write_str(", ")?;
+            write_vector2(w, r.size)
+        }
+        V::Vector3(v) => write_vector3(w, *v),
+        V::Transform2D(t) => {
+            write_vector2(w, t.a)?;
+            w.write_str(", ")?;
+            write_vector2(w, t.b)?;
+            w.write_str(", ")?;
+            write_vector2(w, t.origin)
+        }
+        V::Plane(p) => {
+            write_vector3(w, p.normal)?;
+            w.write_str(", ")?;
+            write_real(w, p.d)
+        }
+        V::Quat(q) => write_reals(w, &[q.x, q.y, q.z, q.w]),
+        V::Aabb(aabb) => {
+            write_vector3(w, aabb.position)?;
+            w.write_str(" - ")?;
+            write_vector3(w, aabb.size)
+        }
+        V::Basis(basis) => write_basis(w, basis),
+        V::Transform(t) => {
+            write_basis(w, &t.basis)?;
+            w.write_str(" - ")?;
+            write_vector3(w, t.origin)
+        }
+        V::Color(c) => write_reals(w, &[c.r, c.g, c.b, c.a]),
+        V::Rid => w.write_str("[RID]"),
+        V::Object(ObjectValue::Null) => w.write_str("[Object:null]"),
+        V::Object(ObjectValue::Instance { class, id }) => write!(w, "[{class}:{id}]"),
+        // The class isn't known for objects decoded from their IDs.
+        V::Object(ObjectValue::Id(id)) => write!(w, "[Object:{id}]"),
+        V::Object(ObjectValue::Full { class, .. }) => write!(w, "[{class}]"),
+        V::Object(_) => w.write_str("[Resource]"),
+        V::Dictionary(entries) => {
+            // Godot sorts the entries by their string representation here.
+            let mut pairs: Vec<_> = entries
+                .iter()
+                .map(|(key, value)| (godot_str(key), godot_str(value)))
+                .collect();
+            pairs.sort();
+
+            w.write_char('{')?;
+            for (i, (key, value)) in pairs.iter().enumerate() {
+                if i > 0 {
+                    w.write_str(", ")?;
+                }
+                write!(w, "{key}:{value}")?;
+            }
+            w.write_char('}')
+        }
+        V::VariantArray(items) => write_list(w, items, write_godot_str),
+        V::ByteArray(items) => write_list(w, items, |w, i| write!(w, "{i}")),
+        V::Int32Array(items) => write_list(w, items, |w, i| write!(w, "{i}")),
+        V::Float32Array(items) => write_list(w, items, |w, f| write_num(w, f64::from(*f))),
+        V::StringArray(items) => write_list(w, items, |w, s| w.write_str(s)),
+        V::Vector2Array(items) => write_list(w, items, |w, v| write_vector2(w, *v)),
+        V::Vector3Array(items) => write_list(w, items, |w, v| write_vector3(w, *v)),
+        V::ColorArray(items) => write_list(w, items, |w, c| write_reals(w, &[c.r, c.g, c.b, c.a])),
+    }
+}
+
+/// Orders dictionary keys like Godot's `Variant::operator<`: by type first, then by value.
+fn compare_keys(a: &VariantValue, b: &VariantValue) -> Ordering {
+    use VariantValue as V;
+
+    let by_type = (a.get_type() as u32).cmp(&(b.get_type() as u32));
+    by_type.then_with(|| match (a, b) {
+        (V::Bool(a), V::Bool(b)) => a.cmp(b),
+        (V::I64(a), V::I64(b)) => a.cmp(b),
+        (V::F64(a), V::F64(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (V::GodotString(a), V::GodotString(b)) | (V::NodePath(a), V::NodePath(b)) => a.cmp(b),
+        _ => godot_str(a).cmp(&godot_str(b)),
+    })
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, kind: JsonErrorKind) -> JsonError {
+        self.error_at(self.pos, kind)
+    }
+
+    fn error_at(&self, pos: usize, kind: JsonErrorKind) -> JsonError {
+        let before = &self.src[..pos];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        JsonError { line, column, kind }
+    }
+
+    fn unexpected(&self, expected: &'static str) -> JsonError {
+        match self.peek() {
+            Some(_) => self.error(JsonErrorKind::Expected(expected)),
+            None => self.error(JsonErrorKind::UnexpectedEof),
+        }
+    }
+
+    /// Skips whitespace. Like Godot, all control characters count as whitespace.
+    fn skip_whitespace(&mut self) {
+        let len = self
+            .rest()
+            .find(|c: char| c > ' ')
+            .unwrap_or(self.rest().len());
+        self.pos += len;
+    }
+
+    /// Skips whitespace and consumes `expected` if it is the next character.
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<VariantValue, JsonError> {
+        self.skip_whitespace();
+        if depth > MAX_DEPTH {
+            return Err(self.error(JsonErrorKind::TooDeep));
+        }
+
+        match self.peek() {
+            Some('{') => self.object(depth),
+            Some('[') => self.array(depth),
+            Some('"') => self.string().map(VariantValue::GodotString),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => self.identifier(),
+            Some(c) => Err(self.error(JsonErrorKind::UnexpectedChar(c))),
+            None => Err(self.error(JsonErrorKind::UnexpectedEof)),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<VariantValue, JsonError> {
+        self.bump();
+        let mut entries: Vec<(VariantValue, VariantValue)> = Vec::new();
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        loop {
+            if self.eat('}') {
+                return Ok(VariantValue::Dictionary(entries));
+            }
+            if !entries.is_empty() && !self.eat(',') {
+                return Err(self.unexpected("'}' or ','"));
+            }
+            // Like in Godot, a trailing comma is allowed.
+            if self.eat('}') {
+                return Ok(VariantValue::Dictionary(entries));
+            }
+
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.unexpected("key"));
+            }
+            let key = self.string()?;
+
+            if !self.eat(':') {
+                return Err(self.unexpected("':'"));
+            }
+            let value = self.value(depth + 1)?;
+
+            // Like in a `Dictionary`, later values replace earlier ones in place.
+            match indices.entry(key) {
+                Entry::Occupied(entry) => entries[*entry.get()].1 = value,
+                Entry::Vacant(entry) => {
+                    entries.push((VariantValue::GodotString(entry.key().clone()), value));
+                    entry.insert(entries.len() - 1);
+                }
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<VariantValue, JsonError> {
+        self.bump();
+        let mut items = Vec::new();
+        loop {
+            if self.eat(']') {
+                return Ok(VariantValue::VariantArray(items));
+            }
+            if !items.is_empty() && !self.eat(',') {
+                return Err(self.unexpected("']' or ','"));
+            }
+            // Like in Godot, a trailing comma is allowed.
+            if self.eat(']') {
+                return Ok(VariantValue::VariantArray(items));
+            }
+            items.push(self.value(depth + 1)?);
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.bump();
+        let mut s = String::new();
+        loop {
+            let c = self
+                .bump()
+                .ok_or_else(|| self.error(JsonErrorKind::UnexpectedEof))?;
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let start = self.pos - 1;
+                    let escaped = match self.bump() {
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('u') => self.unicode_escape(start)?,
+                        None => return Err(self.error(JsonErrorKind::UnexpectedEof)),
+                        Some(_) => return Err(self.error_at(start, JsonErrorKind::InvalidEscape)),
+                    };
+                    s.push(escaped);
+                }
+                c => s.push(c),
+            }
+        }
+    }
+
+    /// Parses the digits of a `\u` escape, combining surrogate pairs.
+    fn unicode_escape(&mut self, start: usize) -> Result<char, JsonError> {
+        let invalid = |parser: &Self| parser.error_at(start, JsonErrorKind::InvalidEscape);
+
+        let high = self.hex4().ok_or_else(|| invalid(self))?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.rest().starts_with("\\u") {
+                return Err(invalid(self));
+            }
+            self.pos += 2;
+            let low = self.hex4().ok_or_else(|| invalid(self))?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(invalid(self));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| invalid(self))
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let digits = self.rest().get(..4)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.pos += 4;
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    fn number(&mut self) -> Result<VariantValue, JsonError> {
+        let start = self.pos;
+        let rest = self.rest();
+        let bytes = rest.as_bytes();
+
+        let digits_from = |mut i: usize| {
+            while matches!(bytes.get(i), Some(b) if b.is_ascii_digit()) {
+                i += 1;
+            }
+            i
+        };
+
+        let mut end = if bytes[0] == b'-' { 1 } else { 0 };
+        let int_end = digits_from(end);
+        let has_digits = int_end > end;
+        end = int_end;
+        if bytes.get(end) == Some(&b'.') {
+            end = digits_from(end + 1);
+        }
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let mut exp = end + 1;
+            if matches!(bytes.get(exp), Some(b'+' | b'-')) {
+                exp += 1;
+            }
+            end = digits_from(exp);
+        }
+
+        let literal = &rest[..end];
+        self.pos += end;
+        match literal.parse::<f64>() {
+            Ok(v) if has_digits => Ok(VariantValue::F64(v)),
+            _ => Err(self.error_at(start, JsonErrorKind::InvalidNumber(literal.to_owned()))),
+        }
+    }
+
+    fn identifier(&mut self) -> Result<VariantValue, JsonError> {
+        let start = self.pos;
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.pos += len;
+
+        match &rest[..len] {
+            "true" => Ok(VariantValue::Bool(true)),
+            "false" => Ok(VariantValue::Bool(false)),
+            "null" => Ok(VariantValue::Nil),
+            other => Err(self.error_at(start, JsonErrorKind::UnknownIdentifier(other.to_owned()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compact(value: &VariantValue) -> String {
+        write_value(value, &JsonOptions::new())
+    }
+
+    fn dict(entries: Vec<(VariantValue, VariantValue)>) -> VariantValue {
+        VariantValue::Dictionary(entries)
+    }
+
+    #[test]
+    fn write_scalars() {
+        assert_eq!("null", compact(&VariantValue::Nil));
+        assert_eq!("true", compact(&true.into()));
+        assert_eq!("-42", compact(&(-42).into()));
+        assert_eq!("2", compact(&2.0.into()));
+        assert_eq!("0.1", compact(&0.1.into()));
+        assert_eq!("-0.5", compact(&(-0.5).into()));
+        assert_eq!("0.33333333333333", compact(&(1.0 / 3.0).into()));
+        assert_eq!("123.456789012346", compact(&123.4567890123456.into()));
+        assert_eq!("100000000000000000000", compact(&1e20.into()));
+        assert_eq!("0", compact(&1e-20.into()));
+        assert_eq!("inf", compact(&f64::INFINITY.into()));
+        assert_eq!(
+            r#""quote\" backslash\\ newline\n tab\t bell\u0007 é""#,
+            compact(&"quote\" backslash\\ newline\n tab\t bell\u{7} é".into())
+        );
+    }
+
+    #[test]
+    fn write_collections() {
+        let value = dict(vec![
+            (
+                "b".into(),
+                VariantValue::VariantArray(vec![1.into(), "x".into()]),
+            ),
+            ("a".into(), dict(vec![])),
+            (2.into(), VariantValue::Float32Array(vec![0.5, 2.0])),
+        ]);
+        assert_eq!(r#"{"b":[1,"x"],"a":{},"2":[0.5,2]}"#, compact(&value));
+
+        let sorted = write_value(&value, &JsonOptions::new().sort_keys(true));
+        assert_eq!(r#"{"2":[0.5,2],"a":{},"b":[1,"x"]}"#, sorted);
+
+        let indented = write_value(&value, &JsonOptions::new().indent("  "));
+        assert_eq!(
+            "{\n  \"b\": [\n    1,\n    \"x\"\n  ],\n  \"a\": {\n\n  },\n  \"2\": [\n    0.5,\n    2\n  ]\n}",
+            indented
+        );
+    }
+
+    #[test]
+    fn write_other_types_as_strings() {
+        assert_eq!(r#""(1, 2.5)""#, compact(&Vector2::new(1.0, 2.5).into()));
+        assert_eq!(
+            r#""(0.1, 0, -3)""#,
+            compact(&Vector3::new(0.1, 0.0, -3.0).into())
+        );
+        assert_eq!(
+            r#""1, 0, 0.5, 1""#,
+            compact(&Color::from_rgba(1.0, 0.0, 0.5, 1.0).into())
+        );
+        assert_eq!(
+            r#""[1, 2, 255]""#,
+            compact(&VariantValue::ByteArray(vec![1, 2, 255]))
+        );
+        assert_eq!(
+            r#"{"(1, 2)":"Parent/Child"}"#,
+            compact(&dict(vec![(
+                Vector2::new(1.0, 2.0).into(),
+                VariantValue::NodePath("Parent/Child".into())
+            )]))
+        );
+        assert_eq!(
+            r#""[Node2D:1234]""#,
+            compact(
+                &ObjectValue::Instance {
+                    class: "Node2D".into(),
+                    id: 1234
+                }
+                .into()
+            )
+        );
+        assert_eq!(r#""[Object:null]""#, compact(&ObjectValue::Null.into()));
+    }
+
+    #[test]
+    fn parse_values() {
+        assert_eq!(
+            Ok(dict(vec![
+                ("name".into(), "Godot".into()),
+                ("version".into(), 3.0.into()),
+                (
+                    "tags".into(),
+                    VariantValue::VariantArray(vec![
+                        true.into(),
+                        VariantValue::Nil,
+                        (-1.5e3).into()
+                    ])
+                ),
+            ])),
+            parse_value(r#" { "name": "Godot", "version": 3, "tags": [true, null, -1.5E3] } "#)
+        );
+
+        assert_eq!(Ok(42.0.into()), parse_value("42"));
+        assert_eq!(
+            Ok("\"/\u{8}\u{c}\n\r\t\u{e9}\u{1f600}".into()),
+            parse_value(r#""\"\/\b\f\n\r\té😀""#)
+        );
+        assert_eq!(
+            Ok(VariantValue::VariantArray(vec![1.0.into(), 2.0.into()])),
+            parse_value("[1, 2,]")
+        );
+        assert_eq!(
+            Ok(dict(vec![
+                ("a".into(), 3.0.into()),
+                ("b".into(), 2.0.into())
+            ])),
+            parse_value(r#"{"a": 1, "b": 2, "a": 3,}"#)
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let value = dict(vec![
+            ("list".into(), VariantValue::VariantArray(vec![0.25.into()])),
+            ("text".into(), "line\nbreak \"quoted\"".into()),
+        ]);
+        let options = JsonOptions::new().indent("\t");
+        assert_eq!(
+            Ok(value.clone()),
+            parse_value(&write_value(&value, &options))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        fn error(text: &str) -> (usize, usize, JsonErrorKind) {
+            let err = parse_value(text).expect_err("should fail");
+            (err.line(), err.column(), err.kind().clone())
+        }
+
+        assert_eq!(
+            (2, 8, JsonErrorKind::UnknownIdentifier("tru".into())),
+            error("{\n  \"a\": tru\n}")
+        );
+        assert_eq!((1, 6, JsonErrorKind::UnexpectedEof), error("[1, 2"));
+        assert_eq!(
+            (1, 4, JsonErrorKind::Expected("']' or ','")),
+            error("[1 2]")
+        );
+        assert_eq!((1, 2, JsonErrorKind::Expected("key")), error("{1: 2}"));
+        assert_eq!((1, 6, JsonErrorKind::Expected("':'")), error(r#"{"a" 1}"#));
+        assert_eq!((1, 3, JsonErrorKind::TrailingCharacters), error("1 2"));
+        assert_eq!((1, 2, JsonErrorKind::InvalidEscape), error(r#""\x""#));
+        assert_eq!((1, 2, JsonErrorKind::InvalidEscape), error(r#""\ud83d""#));
+        assert_eq!((1, 1, JsonErrorKind::InvalidNumber("-".into())), error("-"));
+        assert_eq!((1, 1, JsonErrorKind::UnexpectedChar('\'')), error("'a'"));
+        assert_eq!(JsonErrorKind::TooDeep, error(&"[".repeat(2000)).2);
+    }
+}
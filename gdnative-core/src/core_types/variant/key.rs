@@ -0,0 +1,364 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem::transmute_copy;
+
+use crate::core_types::*;
+use crate::private::get_api;
+use crate::sys;
+
+/// A `Variant` that can be used as a key in `HashMap`s, `HashSet`s, `BTreeMap`s and similar
+/// collections.
+///
+/// Equality is defined like for the keys of a `Dictionary` (see [`Variant::hash_compare`]):
+/// values are only equal if they have the same type, floats compare equal if both are NaN, and
+/// arrays are compared element-wise using the same rules. This is stricter than the `PartialEq`
+/// implementation of `Variant`, for which `1 == 1.0`. Unlike `hash_compare`, NaNs in pool
+/// arrays also compare equal, so that equality is consistent with `Ord`.
+///
+/// `Hash` is consistent with this definition of equality.
+///
+/// `Ord` is a total ordering. Values are ordered by type first, like Godot's
+/// `Variant::operator<`, and then the same way as [`VariantArray::sort`] where Godot defines
+/// an order for the type. Values Godot can't order, like `Dictionary`s or `NodePath`s, are
+/// ordered in an unspecified but consistent way. Arrays are ordered lexicographically. NaN is
+/// greater than any other float.
+///
+/// Like any `Variant`, keys holding reference types like `VariantArray` can be modified
+/// through other references, which changes their hash. As with `RefCell` keys in Rust, doing so
+/// while they are in a collection will lead to unexpected behavior.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::collections::HashMap;
+/// use gdnative::prelude::*;
+/// use gdnative::core_types::VariantKey;
+///
+/// let mut cache = HashMap::new();
+/// cache.insert(VariantKey::new(42.to_variant()), "int");
+/// cache.insert(VariantKey::new(42.0.to_variant()), "float");
+/// assert_eq!(2, cache.len());
+/// ```
+#[derive(Clone, Default)]
+pub struct VariantKey(Variant);
+
+impl VariantKey {
+    /// Wraps `variant` for use as a key.
+    #[inline]
+    pub fn new(variant: Variant) -> Self {
+        VariantKey(variant)
+    }
+
+    /// Returns a reference to the wrapped `Variant`.
+    #[inline]
+    pub fn as_variant(&self) -> &Variant {
+        &self.0
+    }
+
+    /// Returns the wrapped `Variant`.
+    #[inline]
+    pub fn into_variant(self) -> Variant {
+        self.0
+    }
+}
+
+impl From<Variant> for VariantKey {
+    #[inline]
+    fn from(variant: Variant) -> Self {
+        VariantKey(variant)
+    }
+}
+
+impl From<VariantKey> for Variant {
+    #[inline]
+    fn from(key: VariantKey) -> Self {
+        key.0
+    }
+}
+
+impl fmt::Debug for VariantKey {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ToVariant for VariantKey {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.0.clone()
+    }
+}
+
+impl ToVariantEq for VariantKey {}
+
+impl FromVariant for VariantKey {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Ok(VariantKey(variant.clone()))
+    }
+}
+
+impl PartialEq for VariantKey {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // Defined in terms of `cmp`, since `hash_compare` doesn't treat NaNs in pool arrays as
+        // equal, while `cmp_float` does.
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for VariantKey {}
+
+impl Hash for VariantKey {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_variant(&self.0, state)
+    }
+}
+
+impl PartialOrd for VariantKey {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for VariantKey {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        let type_order = (self.0.get_type() as u32).cmp(&(other.0.get_type() as u32));
+        if type_order != Ordering::Equal {
+            return type_order;
+        }
+
+        match (self.0.dispatch(), other.0.dispatch()) {
+            (VariantDispatch::Bool(a), VariantDispatch::Bool(b)) => a.cmp(&b),
+            (VariantDispatch::I64(a), VariantDispatch::I64(b)) => a.cmp(&b),
+            (VariantDispatch::GodotString(a), VariantDispatch::GodotString(b)) => a.cmp(&b),
+            (VariantDispatch::NodePath(a), VariantDispatch::NodePath(b)) => {
+                a.to_string().cmp(&b.to_string())
+            }
+            (VariantDispatch::Rid(a), VariantDispatch::Rid(b)) => a.cmp(&b),
+            (VariantDispatch::Object(a), VariantDispatch::Object(b)) => {
+                object_identity(&a).cmp(&object_identity(&b))
+            }
+            (VariantDispatch::Dictionary(a), VariantDispatch::Dictionary(b)) => {
+                dictionary_identity(&a).cmp(&dictionary_identity(&b))
+            }
+            (VariantDispatch::VariantArray(a), VariantDispatch::VariantArray(b)) => {
+                a.iter().map(VariantKey).cmp(b.iter().map(VariantKey))
+            }
+            (VariantDispatch::ByteArray(a), VariantDispatch::ByteArray(b)) => {
+                a.read().as_slice().cmp(b.read().as_slice())
+            }
+            (VariantDispatch::Int32Array(a), VariantDispatch::Int32Array(b)) => {
+                a.read().as_slice().cmp(b.read().as_slice())
+            }
+            (VariantDispatch::StringArray(a), VariantDispatch::StringArray(b)) => {
+                a.read().as_slice().cmp(b.read().as_slice())
+            }
+            (a, b) => match (float_components(&a), float_components(&b)) {
+                (Some(a), Some(b)) => a
+                    .iter()
+                    .zip(&b)
+                    .map(|(a, b)| cmp_float(*a, *b))
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or_else(|| a.len().cmp(&b.len())),
+                // Only `Nil` is left, which has a single value.
+                _ => Ordering::Equal,
+            },
+        }
+    }
+}
+
+fn hash_variant<H: Hasher>(variant: &Variant, state: &mut H) {
+    variant.get_type().hash(state);
+
+    match variant.dispatch() {
+        VariantDispatch::Bool(v) => v.hash(state),
+        VariantDispatch::I64(v) => v.hash(state),
+        VariantDispatch::GodotString(v) => v.hash(state),
+        VariantDispatch::NodePath(v) => v.to_string().hash(state),
+        VariantDispatch::Rid(v) => v.to_u64().hash(state),
+        VariantDispatch::Object(v) => object_identity(&v).hash(state),
+        VariantDispatch::Dictionary(v) => dictionary_identity(&v).hash(state),
+        VariantDispatch::VariantArray(v) => {
+            v.len().hash(state);
+            for element in v.iter() {
+                hash_variant(&element, state);
+            }
+        }
+        VariantDispatch::ByteArray(v) => v.read().as_slice().hash(state),
+        VariantDispatch::Int32Array(v) => v.read().as_slice().hash(state),
+        VariantDispatch::StringArray(v) => v.read().as_slice().hash(state),
+        dispatch => {
+            if let Some(floats) = float_components(&dispatch) {
+                floats.len().hash(state);
+                for f in floats {
+                    hash_float(f, state);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the scalar components of float-based types, in the order in which Godot compares
+/// them.
+fn float_components(dispatch: &VariantDispatch) -> Option<Vec<f64>> {
+    fn floats(components: &[f32]) -> Option<Vec<f64>> {
+        Some(components.iter().copied().map(f64::from).collect())
+    }
+
+    match dispatch {
+        VariantDispatch::F64(v) => Some(vec![*v]),
+        VariantDispatch::Vector2(v) => floats(&[v.x, v.y]),
+        VariantDispatch::Rect2(v) => floats(&[v.position.x, v.position.y, v.size.x, v.size.y]),
+        VariantDispatch::Vector3(v) => floats(&[v.x, v.y, v.z]),
+        VariantDispatch::Transform2D(v) => {
+            floats(&[v.a.x, v.a.y, v.b.x, v.b.y, v.origin.x, v.origin.y])
+        }
+        VariantDispatch::Plane(v) => floats(&[v.normal.x, v.normal.y, v.normal.z, v.d]),
+        VariantDispatch::Quat(v) => floats(&[v.x, v.y, v.z, v.w]),
+        VariantDispatch::Aabb(v) => floats(&[
+            v.position.x,
+            v.position.y,
+            v.position.z,
+            v.size.x,
+            v.size.y,
+            v.size.z,
+        ]),
+        VariantDispatch::Basis(v) => floats(&basis_components(v)),
+        VariantDispatch::Transform(v) => {
+            let mut components = basis_components(&v.basis).to_vec();
+            components.extend([v.origin.x, v.origin.y, v.origin.z]);
+            floats(&components)
+        }
+        VariantDispatch::Color(v) => floats(&[v.r, v.g, v.b, v.a]),
+        VariantDispatch::Float32Array(v) => floats(&v.read()),
+        VariantDispatch::Vector2Array(v) => floats(v.read().as_flat_slice()),
+        VariantDispatch::Vector3Array(v) => floats(v.read().as_flat_slice()),
+        VariantDispatch::ColorArray(v) => floats(v.read().as_flat_slice()),
+        _ => None,
+    }
+}
+
+fn basis_components(basis: &Basis) -> [f32; 9] {
+    let [a, b, c] = basis.elements;
+    [a.x, a.y, a.z, b.x, b.y, b.z, c.x, c.y, c.z]
+}
+
+/// Compares floats like Godot, treating NaNs as equal to each other and greater than any
+/// other value.
+fn cmp_float(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+/// Hashes a float consistently with `cmp_float`.
+fn hash_float<H: Hasher>(f: f64, state: &mut H) {
+    let bits = if f.is_nan() {
+        f64::NAN.to_bits()
+    } else if f == 0.0 {
+        0
+    } else {
+        f.to_bits()
+    };
+    bits.hash(state)
+}
+
+/// Returns the address of the object in `variant`, which Godot uses to compare objects.
+fn object_identity(variant: &Variant) -> usize {
+    unsafe { (get_api().godot_variant_as_object)(variant.sys()) as usize }
+}
+
+/// Returns the address of the shared data of `dict`, which Godot uses to compare
+/// dictionaries.
+fn dictionary_identity(dict: &Dictionary) -> usize {
+    // `godot_dictionary` is an opaque wrapper around the single pointer contained in a
+    // `Dictionary`, defined as [u8; size_of::<*const ()>()], so reading it as `usize` is sound.
+    unsafe { transmute_copy::<sys::godot_dictionary, usize>(&*dict.sys()) }
+}
+
+godot_test!(test_variant_key {
+    use std::collections::{BTreeSet, HashMap, HashSet};
+
+    use crate::object::NewRef;
+
+    let key = |v: &dyn ToVariant| VariantKey::new(v.to_variant());
+
+    assert_eq!(1.to_variant(), 1.0.to_variant());
+    assert_ne!(key(&1), key(&1.0));
+    assert_eq!(key(&f64::NAN), key(&f64::NAN));
+    assert_eq!(key(&0.0), key(&-0.0));
+
+    let nan_vector = Vector2::new(f32::NAN, 1.0);
+    assert_eq!(key(&nan_vector), key(&nan_vector));
+
+    // Equality agrees with `Ord` for NaNs in pool arrays, which `hash_compare` treats as unequal
+    let nan_array = || PoolArray::from_vec(vec![1.0, f32::NAN]);
+    assert_eq!(key(&nan_array()), key(&nan_array()));
+    assert_eq!(Ordering::Equal, key(&nan_array()).cmp(&key(&nan_array())));
+    let nan_arrays = [key(&nan_array()), key(&nan_array())];
+    assert_eq!(1, nan_arrays.iter().collect::<HashSet<_>>().len());
+    assert_eq!(1, nan_arrays.iter().collect::<BTreeSet<_>>().len());
+
+    let mut map = HashMap::new();
+    map.insert(key(&1), "int");
+    map.insert(key(&1.0), "float");
+    map.insert(key(&"one"), "string");
+    map.insert(key(&f64::NAN), "nan");
+    map.insert(key(&vec![1, 2]), "array");
+    assert_eq!(5, map.len());
+    assert_eq!(Some(&"int"), map.get(&key(&1)));
+    assert_eq!(Some(&"float"), map.get(&key(&1.0)));
+    assert_eq!(Some(&"nan"), map.get(&key(&f64::NAN)));
+    assert_eq!(Some(&"array"), map.get(&key(&vec![1, 2])));
+    assert_eq!(None, map.get(&key(&vec![1, 2, 3])));
+
+    // Dictionaries are compared by identity, like in Godot
+    let dict = Dictionary::new().into_shared();
+    let other = Dictionary::new().into_shared();
+    assert_eq!(key(&dict), key(&dict.new_ref()));
+    assert_ne!(key(&dict), key(&other));
+
+    let gd_dict = Dictionary::new();
+    gd_dict.insert(1, "int");
+    gd_dict.insert(1.0, "float");
+    let converted = HashMap::<VariantKey, String>::from_variant(&gd_dict.owned_to_variant())
+        .expect("should be valid");
+    assert_eq!(2, converted.len());
+    assert_eq!(Some("float"), converted.get(&key(&1.0)).map(String::as_str));
+
+    let sorted = [
+        key(&"b"),
+        key(&2.5),
+        key(&f64::NAN),
+        key(&"a"),
+        key(&3),
+        key(&1.5),
+        key(&Variant::nil()),
+        key(&Vector2::new(1.0, 2.0)),
+        key(&Vector2::new(1.0, 1.0)),
+        key(&dict),
+        key(&other),
+        key(&dict),
+    ]
+    .into_iter()
+    .collect::<BTreeSet<_>>()
+    .into_iter()
+    .map(VariantKey::into_variant)
+    .collect::<Vec<_>>();
+
+    assert_eq!(11, sorted.len());
+    assert_eq!(Variant::nil(), sorted[0]);
+    assert_eq!(3.to_variant(), sorted[1]);
+    assert_eq!(1.5.to_variant(), sorted[2]);
+    assert_eq!(2.5.to_variant(), sorted[3]);
+    assert!(f64::from_variant(&sorted[4]).unwrap().is_nan());
+    assert_eq!("a".to_variant(), sorted[5]);
+    assert_eq!("b".to_variant(), sorted[6]);
+    assert_eq!(Vector2::new(1.0, 1.0).to_variant(), sorted[7]);
+    assert_eq!(Vector2::new(1.0, 2.0).to_variant(), sorted[8]);
+});
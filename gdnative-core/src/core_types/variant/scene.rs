@@ -0,0 +1,915 @@
+//! Reading and writing Godot 3 text scenes (`.tscn`) and text resources (`.tres`).
+//!
+//! Both formats consist of sections, each starting with a tag like `[node name="Player"]`
+//! that carries fields, followed by property assignments. A [`SceneDocument`] gives access to
+//! these sections generically, and through typed views for the tags used by scenes, such as
+//! [`Node`], [`ExtResource`] and [`Connection`].
+//!
+//! Like the [`config`][super::config] module, this is implemented in pure Rust, so scenes can be
+//! inspected and updated by tools without running the engine. Sections and properties that are
+//! not modified are written back exactly as they were read. Values are read and written in
+//! Godot's text format, as described in the [`text`][super::text] module.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use gdnative::core_types::scene::SceneDocument;
+//! use gdnative::core_types::{ObjectValue, VariantValue};
+//!
+//! let scene = SceneDocument::parse(&std::fs::read_to_string("Main.tscn")?)?;
+//!
+//! for node in scene.nodes() {
+//!     let script = match node.section().property("script") {
+//!         Some(VariantValue::Object(ObjectValue::ExtResource(id))) => scene.ext_resource(*id),
+//!         _ => continue,
+//!     };
+//!     if script.and_then(|script| script.path()).is_none() {
+//!         println!("{:?} has a missing script", node.path());
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::{mem, slice};
+
+use super::text::{self, ParseError, ParseErrorKind, Parser};
+use super::*;
+
+/// A Godot 3 text scene or resource, consisting of a sequence of [`Section`]s.
+///
+/// The document is written back to text through its `Display` implementation.
+#[derive(Clone, Debug, Default)]
+pub struct SceneDocument {
+    sections: Vec<Section>,
+    /// Whitespace and comments after the last section.
+    trailing: String,
+}
+
+/// A section of a [`SceneDocument`], consisting of a tag with fields, like
+/// `[node name="Player" parent="."]`, and the property assignments following it.
+#[derive(Clone, Debug)]
+pub struct Section {
+    name: String,
+    fields: Vec<(String, VariantValue)>,
+    properties: Vec<Property>,
+    /// Whitespace and comments before the tag.
+    leading: String,
+    /// The original text of the tag, which is written instead of the name and fields as long
+    /// as they aren't changed.
+    source: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+struct Property {
+    name: String,
+    value: VariantValue,
+    /// Whitespace and comments before the property.
+    leading: String,
+    /// The original text of the assignment, written as long as the value isn't changed.
+    source: Option<String>,
+}
+
+impl SceneDocument {
+    /// Creates an empty document.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a text scene or resource.
+    #[inline]
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(text);
+        let mut sections: Vec<Section> = Vec::new();
+
+        loop {
+            let gap_start = parser.position();
+            parser.skip_whitespace();
+            let start = parser.position();
+            let leading = text[gap_start..start].to_owned();
+
+            match parser.peek() {
+                None => {
+                    return Ok(SceneDocument {
+                        sections,
+                        trailing: leading,
+                    })
+                }
+                Some('[') => {
+                    let (name, fields) = tag(&mut parser)?;
+                    sections.push(Section {
+                        name,
+                        fields,
+                        properties: Vec::new(),
+                        leading,
+                        source: Some(text[start..parser.position()].to_owned()),
+                    });
+                }
+                Some(_) => {
+                    let section = sections
+                        .last_mut()
+                        .ok_or_else(|| parser.error(ParseErrorKind::Expected("'['")))?;
+                    let name = parser.property_name()?;
+                    let value = parser.value()?;
+                    section.properties.push(Property {
+                        name,
+                        value,
+                        leading,
+                        source: Some(text[start..parser.position()].to_owned()),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns the sections in document order.
+    #[inline]
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /// Returns the sections in document order, for modifying them in place.
+    #[inline]
+    pub fn sections_mut(&mut self) -> &mut [Section] {
+        &mut self.sections
+    }
+
+    /// Adds a section at the end of the document.
+    #[inline]
+    pub fn push_section(&mut self, section: Section) {
+        self.insert_section(self.sections.len(), section);
+    }
+
+    /// Inserts a section at `index`, separated from the surrounding sections by blank lines.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the number of sections.
+    #[inline]
+    pub fn insert_section(&mut self, index: usize, mut section: Section) {
+        if self.sections.is_empty() && self.trailing.is_empty() {
+            self.trailing.push('\n');
+        }
+        if index == 0 {
+            // The first section takes over the leading text of the document.
+            let first_leading = self
+                .sections
+                .first_mut()
+                .map(|first| mem::replace(&mut first.leading, section.leading.clone()));
+            section.leading = first_leading.unwrap_or_default();
+        }
+        self.sections.insert(index, section);
+    }
+
+    /// Removes and returns the section at `index`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    #[inline]
+    pub fn remove_section(&mut self, index: usize) -> Section {
+        let mut section = self.sections.remove(index);
+        if index == 0 {
+            if let Some(first) = self.sections.first_mut() {
+                first.leading = mem::take(&mut section.leading);
+            }
+        }
+        section
+    }
+
+    /// Returns the `[gd_scene]` or `[gd_resource]` header, if the document starts with one.
+    #[inline]
+    pub fn header(&self) -> Option<&Section> {
+        self.sections
+            .first()
+            .filter(|s| s.name == "gd_scene" || s.name == "gd_resource")
+    }
+
+    /// Returns `true` if the document is a scene, as opposed to a resource.
+    #[inline]
+    pub fn is_scene(&self) -> bool {
+        matches!(self.header(), Some(header) if header.name == "gd_scene")
+    }
+
+    /// Returns an iterator over the `[ext_resource]` sections.
+    #[inline]
+    pub fn ext_resources(&self) -> Views<'_, ExtResource<'_>> {
+        self.views(ExtResource::from_section)
+    }
+
+    /// Returns an iterator over the `[sub_resource]` sections.
+    #[inline]
+    pub fn sub_resources(&self) -> Views<'_, SubResource<'_>> {
+        self.views(SubResource::from_section)
+    }
+
+    /// Returns an iterator over the `[node]` sections.
+    #[inline]
+    pub fn nodes(&self) -> Views<'_, Node<'_>> {
+        self.views(Node::from_section)
+    }
+
+    /// Returns an iterator over the `[connection]` sections.
+    #[inline]
+    pub fn connections(&self) -> Views<'_, Connection<'_>> {
+        self.views(Connection::from_section)
+    }
+
+    /// Returns an iterator over the paths of the `[editable]` sections, which mark instanced
+    /// scenes whose children are editable.
+    #[inline]
+    pub fn editable_paths(&self) -> Views<'_, &str> {
+        self.views(|section| {
+            if section.name == "editable" {
+                section.field_str("path")
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the `[ext_resource]` with the given ID, as referenced by `ExtResource( id )`.
+    #[inline]
+    pub fn ext_resource(&self, id: i64) -> Option<ExtResource<'_>> {
+        self.ext_resources().find(|r| r.id() == Some(id))
+    }
+
+    /// Returns the `[sub_resource]` with the given ID, as referenced by `SubResource( id )`.
+    #[inline]
+    pub fn sub_resource(&self, id: i64) -> Option<SubResource<'_>> {
+        self.sub_resources().find(|r| r.id() == Some(id))
+    }
+
+    /// Returns the `[resource]` section of a text resource, which contains the properties of
+    /// the resource itself.
+    #[inline]
+    pub fn resource(&self) -> Option<&Section> {
+        self.sections.iter().find(|s| s.name == "resource")
+    }
+
+    fn views<'a, T>(&'a self, view: fn(&'a Section) -> Option<T>) -> Views<'a, T> {
+        Views {
+            iter: self.sections.iter(),
+            view,
+        }
+    }
+}
+
+impl FromStr for SceneDocument {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for SceneDocument {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for section in &self.sections {
+            f.write_str(&section.leading)?;
+            match &section.source {
+                Some(source) => f.write_str(source)?,
+                None => {
+                    write!(f, "[{}", section.name)?;
+                    for (name, value) in &section.fields {
+                        write!(f, " {name}={value}")?;
+                    }
+                    f.write_str("]")?;
+                }
+            }
+
+            for property in &section.properties {
+                f.write_str(&property.leading)?;
+                match &property.source {
+                    Some(source) => f.write_str(source)?,
+                    None => {
+                        text::write_property_name(f, &property.name)?;
+                        write!(f, " = {}", property.value)?;
+                    }
+                }
+            }
+        }
+        f.write_str(&self.trailing)
+    }
+}
+
+/// Parses a tag up to and including the closing bracket.
+fn tag(parser: &mut Parser) -> Result<(String, Vec<(String, VariantValue)>), ParseError> {
+    parser.expect('[', "'['")?;
+    let name = parser
+        .identifier()
+        .ok_or_else(|| parser.error(ParseErrorKind::Expected("section name")))?
+        .to_owned();
+
+    let mut fields = Vec::new();
+    while !parser.eat(']') {
+        let field = parser
+            .identifier()
+            .ok_or_else(|| match parser.peek() {
+                Some(_) => parser.error(ParseErrorKind::Expected("field name or ']'")),
+                None => parser.error(ParseErrorKind::UnexpectedEof),
+            })?
+            .to_owned();
+        parser.expect('=', "'='")?;
+        fields.push((field, parser.value()?));
+    }
+    Ok((name, fields))
+}
+
+impl Section {
+    /// Creates a section with the given tag name, and no fields or properties.
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Section {
+            name: name.into(),
+            fields: Vec::new(),
+            properties: Vec::new(),
+            leading: "\n\n".into(),
+            source: None,
+        }
+    }
+
+    /// Returns the name of the tag, e.g. `node` for `[node name="Player"]`.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value of a field of the tag.
+    #[inline]
+    pub fn field(&self, name: &str) -> Option<&VariantValue> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the names and values of the fields of the tag, in order.
+    #[inline]
+    pub fn fields(&self) -> Fields<'_> {
+        Fields {
+            iter: self.fields.iter(),
+        }
+    }
+
+    /// Sets the value of a field of the tag, returning the old value if it existed. New fields
+    /// are added at the end.
+    #[inline]
+    pub fn set_field(
+        &mut self,
+        name: &str,
+        value: impl Into<VariantValue>,
+    ) -> Option<VariantValue> {
+        self.source = None;
+        let value = value.into();
+        match self.fields.iter_mut().find(|(field, _)| field == name) {
+            Some((_, existing)) => Some(mem::replace(existing, value)),
+            None => {
+                self.fields.push((name.to_owned(), value));
+                None
+            }
+        }
+    }
+
+    /// Removes a field of the tag, returning its value if it existed.
+    #[inline]
+    pub fn remove_field(&mut self, name: &str) -> Option<VariantValue> {
+        let index = self.fields.iter().position(|(field, _)| field == name)?;
+        self.source = None;
+        Some(self.fields.remove(index).1)
+    }
+
+    /// Returns the value of a property.
+    #[inline]
+    pub fn property(&self, name: &str) -> Option<&VariantValue> {
+        self.properties
+            .iter()
+            .find(|property| property.name == name)
+            .map(|property| &property.value)
+    }
+
+    /// Returns an iterator over the names and values of the properties, in order.
+    #[inline]
+    pub fn properties(&self) -> Properties<'_> {
+        Properties {
+            iter: self.properties.iter(),
+        }
+    }
+
+    /// Sets the value of a property, returning the old value if it existed. New properties are
+    /// added at the end.
+    #[inline]
+    pub fn set_property(
+        &mut self,
+        name: &str,
+        value: impl Into<VariantValue>,
+    ) -> Option<VariantValue> {
+        let value = value.into();
+        match self.properties.iter_mut().find(|p| p.name == name) {
+            Some(property) => {
+                property.source = None;
+                Some(mem::replace(&mut property.value, value))
+            }
+            None => {
+                self.properties.push(Property {
+                    name: name.to_owned(),
+                    value,
+                    leading: "\n".into(),
+                    source: None,
+                });
+                None
+            }
+        }
+    }
+
+    /// Removes a property, returning its value if it existed.
+    #[inline]
+    pub fn remove_property(&mut self, name: &str) -> Option<VariantValue> {
+        let index = self.properties.iter().position(|p| p.name == name)?;
+        Some(self.properties.remove(index).value)
+    }
+
+    fn field_str(&self, name: &str) -> Option<&str> {
+        match self.field(name)? {
+            VariantValue::GodotString(s) | VariantValue::NodePath(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn field_i64(&self, name: &str) -> Option<i64> {
+        match self.field(name)? {
+            VariantValue::I64(i) => Some(*i),
+            _ => None,
+        }
+    }
+}
+
+/// Iterator over the fields of a [`Section`].
+#[derive(Clone, Debug)]
+pub struct Fields<'a> {
+    iter: slice::Iter<'a, (String, VariantValue)>,
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = (&'a str, &'a VariantValue);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+/// Iterator over the properties of a [`Section`].
+#[derive(Clone, Debug)]
+pub struct Properties<'a> {
+    iter: slice::Iter<'a, Property>,
+}
+
+impl<'a> Iterator for Properties<'a> {
+    type Item = (&'a str, &'a VariantValue);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|p| (p.name.as_str(), &p.value))
+    }
+}
+
+/// Iterator over the sections of a [`SceneDocument`] with a certain tag, as typed views.
+#[derive(Clone, Debug)]
+pub struct Views<'a, T> {
+    iter: slice::Iter<'a, Section>,
+    view: fn(&'a Section) -> Option<T>,
+}
+
+impl<'a, T> Iterator for Views<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let view = self.view;
+        self.iter.find_map(view)
+    }
+}
+
+/// An `[ext_resource]` section, which references a resource stored in another file.
+#[derive(Copy, Clone, Debug)]
+pub struct ExtResource<'a> {
+    section: &'a Section,
+}
+
+impl<'a> ExtResource<'a> {
+    /// Returns a view of `section`, if it is an `[ext_resource]` section.
+    #[inline]
+    pub fn from_section(section: &'a Section) -> Option<Self> {
+        (section.name == "ext_resource").then_some(ExtResource { section })
+    }
+
+    /// Returns the underlying section.
+    #[inline]
+    pub fn section(&self) -> &'a Section {
+        self.section
+    }
+
+    /// Returns the path of the referenced resource, e.g. `res://player.gdns`.
+    #[inline]
+    pub fn path(&self) -> Option<&'a str> {
+        self.section.field_str("path")
+    }
+
+    /// Returns the class name of the referenced resource, e.g. `Script`.
+    #[inline]
+    pub fn type_name(&self) -> Option<&'a str> {
+        self.section.field_str("type")
+    }
+
+    /// Returns the ID used to reference this resource in the document.
+    #[inline]
+    pub fn id(&self) -> Option<i64> {
+        self.section.field_i64("id")
+    }
+}
+
+/// A `[sub_resource]` section, which contains a resource stored in the document itself.
+#[derive(Copy, Clone, Debug)]
+pub struct SubResource<'a> {
+    section: &'a Section,
+}
+
+impl<'a> SubResource<'a> {
+    /// Returns a view of `section`, if it is a `[sub_resource]` section.
+    #[inline]
+    pub fn from_section(section: &'a Section) -> Option<Self> {
+        (section.name == "sub_resource").then_some(SubResource { section })
+    }
+
+    /// Returns the underlying section, which contains the properties of the resource.
+    #[inline]
+    pub fn section(&self) -> &'a Section {
+        self.section
+    }
+
+    /// Returns the class name of the resource, e.g. `RectangleShape2D`.
+    #[inline]
+    pub fn type_name(&self) -> Option<&'a str> {
+        self.section.field_str("type")
+    }
+
+    /// Returns the ID used to reference this resource in the document.
+    #[inline]
+    pub fn id(&self) -> Option<i64> {
+        self.section.field_i64("id")
+    }
+}
+
+/// A `[node]` section of a scene.
+#[derive(Copy, Clone, Debug)]
+pub struct Node<'a> {
+    section: &'a Section,
+}
+
+impl<'a> Node<'a> {
+    /// Returns a view of `section`, if it is a `[node]` section.
+    #[inline]
+    pub fn from_section(section: &'a Section) -> Option<Self> {
+        (section.name == "node").then_some(Node { section })
+    }
+
+    /// Returns the underlying section, which contains the properties of the node.
+    #[inline]
+    pub fn section(&self) -> &'a Section {
+        self.section
+    }
+
+    /// Returns the name of the node.
+    #[inline]
+    pub fn name(&self) -> Option<&'a str> {
+        self.section.field_str("name")
+    }
+
+    /// Returns the class name of the node. This is `None` for instanced scenes, and for nodes
+    /// that override properties of nodes in instanced scenes.
+    #[inline]
+    pub fn type_name(&self) -> Option<&'a str> {
+        self.section.field_str("type")
+    }
+
+    /// Returns the path of the parent node relative to the root node, which is `.` for
+    /// children of the root node. This is `None` for the root node.
+    #[inline]
+    pub fn parent(&self) -> Option<&'a str> {
+        self.section.field_str("parent")
+    }
+
+    /// Returns the path of this node relative to the root node, in the same form as the paths
+    /// used by `parent` and by connections. The root node itself is `.`.
+    #[inline]
+    pub fn path(&self) -> Option<String> {
+        let name = self.name()?;
+        match self.parent() {
+            None => Some(".".into()),
+            Some(".") => Some(name.into()),
+            Some(parent) => Some(format!("{parent}/{name}")),
+        }
+    }
+
+    /// Returns the ID of the `[ext_resource]` of the scene instanced by this node.
+    #[inline]
+    pub fn instance(&self) -> Option<i64> {
+        match self.section.field("instance")? {
+            VariantValue::Object(ObjectValue::ExtResource(id)) => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// Returns the groups the node is in.
+    #[inline]
+    pub fn groups(&self) -> Vec<&'a str> {
+        match self.section.field("groups") {
+            Some(VariantValue::VariantArray(groups)) => groups
+                .iter()
+                .filter_map(|group| match group {
+                    VariantValue::GodotString(s) => Some(s.as_str()),
+                    _ => None,
+                })
+                .collect(),
+            Some(VariantValue::StringArray(groups)) => groups.iter().map(String::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// A `[connection]` section of a scene, which connects a signal of a node to a method.
+#[derive(Copy, Clone, Debug)]
+pub struct Connection<'a> {
+    section: &'a Section,
+}
+
+impl<'a> Connection<'a> {
+    /// Returns a view of `section`, if it is a `[connection]` section.
+    #[inline]
+    pub fn from_section(section: &'a Section) -> Option<Self> {
+        (section.name == "connection").then_some(Connection { section })
+    }
+
+    /// Returns the underlying section.
+    #[inline]
+    pub fn section(&self) -> &'a Section {
+        self.section
+    }
+
+    /// Returns the name of the signal.
+    #[inline]
+    pub fn signal(&self) -> Option<&'a str> {
+        self.section.field_str("signal")
+    }
+
+    /// Returns the path of the node emitting the signal, relative to the root node.
+    #[inline]
+    pub fn from(&self) -> Option<&'a str> {
+        self.section.field_str("from")
+    }
+
+    /// Returns the path of the node receiving the signal, relative to the root node.
+    #[inline]
+    pub fn to(&self) -> Option<&'a str> {
+        self.section.field_str("to")
+    }
+
+    /// Returns the name of the method called when the signal is emitted.
+    #[inline]
+    pub fn method(&self) -> Option<&'a str> {
+        self.section.field_str("method")
+    }
+
+    /// Returns the `ConnectFlags` of the connection, which are 0 if not specified.
+    #[inline]
+    pub fn flags(&self) -> i64 {
+        self.section.field_i64("flags").unwrap_or(0)
+    }
+
+    /// Returns the extra arguments passed to the method.
+    #[inline]
+    pub fn binds(&self) -> &'a [VariantValue] {
+        match self.section.field("binds") {
+            Some(VariantValue::VariantArray(binds)) => binds,
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = r#"[gd_scene load_steps=4 format=2]
+
+[ext_resource path="res://Player.gdns" type="Script" id=1]
+[ext_resource path="res://Enemy.tscn" type="PackedScene" id=2]
+
+[sub_resource type="RectangleShape2D" id=1]
+extents = Vector2( 16, 16 )
+
+[node name="Main" type="Node2D"]
+script = ExtResource( 1 )
+
+[node name="Body" type="KinematicBody2D" parent="." groups=[ "players" ]]
+position = Vector2( 10, 20 )
+"custom data" = {
+"speed": 1.5
+}
+
+[node name="Shape" type="CollisionShape2D" parent="Body"]
+shape = SubResource( 1 )
+
+[node name="Enemy" parent="." instance=ExtResource( 2 )]
+
+[connection signal="body_entered" from="Body" to="." method="_on_body_entered" binds=[ 1 ]]
+
+[editable path="Enemy"]
+"#;
+
+    #[test]
+    fn round_trip() {
+        let scene = SceneDocument::parse(SCENE).unwrap();
+        assert_eq!(SCENE, scene.to_string());
+        assert!(scene.is_scene());
+        assert_eq!(10, scene.sections().len());
+    }
+
+    #[test]
+    fn typed_views() {
+        let scene: SceneDocument = SCENE.parse().unwrap();
+
+        let header = scene.header().unwrap();
+        assert_eq!(Some(&2.into()), header.field("format"));
+
+        let script = scene.ext_resource(1).unwrap();
+        assert_eq!(Some("res://Player.gdns"), script.path());
+        assert_eq!(Some("Script"), script.type_name());
+        assert_eq!(2, scene.ext_resources().count());
+
+        let shape = scene.sub_resource(1).unwrap();
+        assert_eq!(Some("RectangleShape2D"), shape.type_name());
+        assert_eq!(
+            Some(&Vector2::new(16.0, 16.0).into()),
+            shape.section().property("extents")
+        );
+
+        let nodes: Vec<_> = scene.nodes().collect();
+        assert_eq!(
+            vec![".", "Body", "Body/Shape", "Enemy"],
+            nodes
+                .iter()
+                .map(|node| node.path().unwrap())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&ObjectValue::ExtResource(1).into()),
+            nodes[0].section().property("script")
+        );
+        assert_eq!(vec!["players"], nodes[1].groups());
+        assert_eq!(
+            vec!["position", "custom data"],
+            nodes[1]
+                .section()
+                .properties()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(None, nodes[3].type_name());
+        assert_eq!(Some(2), nodes[3].instance());
+
+        let connection = scene.connections().next().unwrap();
+        assert_eq!(Some("body_entered"), connection.signal());
+        assert_eq!(Some("Body"), connection.from());
+        assert_eq!(Some("."), connection.to());
+        assert_eq!(Some("_on_body_entered"), connection.method());
+        assert_eq!(0, connection.flags());
+        assert_eq!(&[VariantValue::I64(1)], connection.binds());
+
+        assert_eq!(vec!["Enemy"], scene.editable_paths().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn resource() {
+        let text = r#"[gd_resource type="Resource" load_steps=2 format=2]
+
+[ext_resource path="res://Stats.gdns" type="Script" id=1]
+
+[resource]
+script = ExtResource( 1 )
+"max health" = 100
+"#;
+        let mut doc = SceneDocument::parse(text).unwrap();
+        assert!(!doc.is_scene());
+        assert_eq!(
+            Some(&100.into()),
+            doc.resource().unwrap().property("max health")
+        );
+
+        let index = doc.sections().len() - 1;
+        let resource = &mut doc.sections_mut()[index];
+        assert_eq!(Some(100.into()), resource.set_property("max health", 150));
+        assert_eq!(None, resource.set_property("speed", 2.5));
+        assert_eq!(
+            text.replace("100", "150") + "speed = 2.5\n",
+            doc.to_string()
+        );
+    }
+
+    #[test]
+    fn modify() {
+        let mut scene = SceneDocument::parse(SCENE).unwrap();
+
+        let body = &mut scene.sections_mut()[5];
+        body.set_field("parent", "Shape");
+        body.remove_property("custom data");
+
+        let mut node = Section::new("node");
+        node.set_field("name", "Camera");
+        node.set_field("type", "Camera2D");
+        node.set_field("parent", ".");
+        node.set_property("current", true);
+        scene.insert_section(8, node);
+
+        let removed = scene.remove_section(10);
+        assert_eq!("editable", removed.name());
+
+        let expected = r#"[gd_scene load_steps=4 format=2]
+
+[ext_resource path="res://Player.gdns" type="Script" id=1]
+[ext_resource path="res://Enemy.tscn" type="PackedScene" id=2]
+
+[sub_resource type="RectangleShape2D" id=1]
+extents = Vector2( 16, 16 )
+
+[node name="Main" type="Node2D"]
+script = ExtResource( 1 )
+
+[node name="Body" type="KinematicBody2D" parent="Shape" groups=[ "players" ]]
+position = Vector2( 10, 20 )
+
+[node name="Shape" type="CollisionShape2D" parent="Body"]
+shape = SubResource( 1 )
+
+[node name="Enemy" parent="." instance=ExtResource( 2 )]
+
+[node name="Camera" type="Camera2D" parent="."]
+current = true
+
+[connection signal="body_entered" from="Body" to="." method="_on_body_entered" binds=[ 1 ]]
+"#;
+        assert_eq!(expected, scene.to_string());
+    }
+
+    #[test]
+    fn new_document() {
+        let mut doc = SceneDocument::new();
+        let mut header = Section::new("gd_resource");
+        header.set_field("type", "Resource");
+        header.set_field("format", 2);
+        doc.push_section(header);
+
+        let mut resource = Section::new("resource");
+        resource.set_property("name", "test");
+        doc.insert_section(1, resource);
+
+        assert_eq!(
+            "[gd_resource type=\"Resource\" format=2]\n\n[resource]\nname = \"test\"\n",
+            doc.to_string()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        fn error(text: &str) -> (usize, usize, ParseErrorKind) {
+            let err = SceneDocument::parse(text).unwrap_err();
+            (err.line(), err.column(), err.kind().clone())
+        }
+
+        assert_eq!((1, 1, ParseErrorKind::Expected("'['")), error("name = 1\n"));
+        assert_eq!(
+            (1, 2, ParseErrorKind::Expected("section name")),
+            error("[\"node\"]")
+        );
+        assert_eq!(
+            (1, 12, ParseErrorKind::Expected("'='")),
+            error("[node name \"a\"]")
+        );
+        assert_eq!(
+            (1, 16, ParseErrorKind::Expected("field name or ']'")),
+            error("[node name=\"a\" 1]")
+        );
+        assert_eq!(
+            (2, 10, ParseErrorKind::UnexpectedEof),
+            error("[resource]\nvalue = [")
+        );
+        assert_eq!(
+            (3, 9, ParseErrorKind::Expected("'='")),
+            error("[resource]\nvalue = 1\nposition\n")
+        );
+    }
+}
@@ -0,0 +1,548 @@
+use super::*;
+use serde::de::DeserializeOwned;
+use serde::ser::{self, Serialize};
+
+/// Serializes a value into a `Variant` using its `Serialize` implementation.
+///
+/// Values are converted following the conventions of self-describing formats like JSON:
+///
+/// - Integers are converted to `i64`, and floats to `f64`.
+/// - Strings and chars are converted to `GodotString`s, and byte buffers to `PoolByteArray`s.
+/// - `None`, `()` and unit structs are converted to `Nil`.
+/// - Sequences and tuples are converted to `VariantArray`s.
+/// - Maps and structs are converted to `Dictionary`s.
+/// - Enums are externally tagged: unit variants are converted to their names as strings, and
+///   other variants to dictionaries in the form of `{ "Variant": value }`.
+///
+/// Godot's core types like `Vector2` and `Color` are structs like any other, and converted to
+/// `Dictionary`s of their fields. A `VariantDispatch` is converted back into the `Variant` it was
+/// created from instead, so values can be kept as their `Variant` types by storing them as
+/// `VariantDispatch`.
+///
+/// # Example
+///
+/// ```no_run
+/// use gdnative::core_types;
+/// use gdnative::prelude::*;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Player {
+///     name: String,
+///     position: VariantDispatch,
+/// }
+///
+/// let position = Vector2::new(1.0, 2.0).to_variant().dispatch();
+/// let player = Player { name: "Godette".into(), position };
+/// let dict = core_types::to_variant(&player).unwrap().to::<Dictionary>().unwrap();
+/// assert_eq!(Some(Vector2::new(1.0, 2.0)), dict.get("position").and_then(|v| v.to()));
+/// ```
+#[inline]
+pub fn to_variant<T>(value: &T) -> Result<Variant, SerializeError>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(VariantSerializer)
+}
+
+/// Error that can occur when serializing a value into a `Variant`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SerializeError {
+    /// A custom error message, usually from a `Serialize` implementation.
+    Custom(String),
+    /// An integer is out of the range of `i64`, which is the only integer type `Variant` supports.
+    IntegerOutOfRange,
+}
+
+impl fmt::Display for SerializeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SerializeError::Custom(s) => write!(f, "{s}"),
+            SerializeError::IntegerOutOfRange => write!(f, "integer out of range of i64"),
+        }
+    }
+}
+
+impl std::error::Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    #[inline]
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerializeError::Custom(msg.to_string())
+    }
+}
+
+/// A `Serializer` that produces `Variant`s. See [`to_variant`] for details.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct VariantSerializer;
+
+fn int<T>(v: T) -> Result<Variant, SerializeError>
+where
+    i64: TryFrom<T>,
+{
+    i64::try_from(v)
+        .map(|v| v.to_variant())
+        .map_err(|_| SerializeError::IntegerOutOfRange)
+}
+
+fn tagged(variant: &'static str, value: Variant) -> Variant {
+    let dict = Dictionary::new();
+    dict.insert(variant, value);
+    dict.owned_to_variant()
+}
+
+impl ser::Serializer for VariantSerializer {
+    type Ok = Variant;
+    type Error = SerializeError;
+
+    type SerializeSeq = SerializeSeq;
+    type SerializeTuple = SerializeSeq;
+    type SerializeTupleStruct = SerializeSeq;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeStruct;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<Variant, SerializeError> {
+        Ok(v.to_variant())
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<Variant, SerializeError> {
+        int(v)
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<Variant, SerializeError> {
+        int(v)
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<Variant, SerializeError> {
+        int(v)
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<Variant, SerializeError> {
+        int(v)
+    }
+
+    #[inline]
+    fn serialize_i128(self, v: i128) -> Result<Variant, SerializeError> {
+        int(v)
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<Variant, SerializeError> {
+        int(v)
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<Variant, SerializeError> {
+        int(v)
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<Variant, SerializeError> {
+        int(v)
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<Variant, SerializeError> {
+        int(v)
+    }
+
+    #[inline]
+    fn serialize_u128(self, v: u128) -> Result<Variant, SerializeError> {
+        int(v)
+    }
+
+    #[inline]
+    fn serialize_f32(self, v: f32) -> Result<Variant, SerializeError> {
+        Ok(f64::from(v).to_variant())
+    }
+
+    #[inline]
+    fn serialize_f64(self, v: f64) -> Result<Variant, SerializeError> {
+        Ok(v.to_variant())
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<Variant, SerializeError> {
+        Ok(v.encode_utf8(&mut [0; 4]).to_variant())
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Variant, SerializeError> {
+        Ok(v.to_variant())
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<Variant, SerializeError> {
+        Ok(PoolArray::from_slice(v).to_variant())
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Variant, SerializeError> {
+        Ok(Variant::nil())
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<Variant, SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Variant, SerializeError> {
+        Ok(Variant::nil())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Variant, SerializeError> {
+        Ok(Variant::nil())
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Variant, SerializeError> {
+        Ok(variant.to_variant())
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Variant, SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Variant, SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = value.serialize(self)?;
+        if name == super::serialize::VARIANT_DISPATCH_NAME {
+            dispatch_from_serialized(variant, value)
+        } else {
+            Ok(tagged(variant, value))
+        }
+    }
+
+    #[inline]
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeSeq, SerializeError> {
+        Ok(SerializeSeq {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<SerializeSeq, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeSeq, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant, SerializeError> {
+        Ok(SerializeTupleVariant {
+            variant,
+            seq: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, SerializeError> {
+        Ok(SerializeMap {
+            dict: Dictionary::new(),
+            key: None,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<SerializeStruct, SerializeError> {
+        Ok(SerializeStruct {
+            dict: Dictionary::new(),
+        })
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeStructVariant, SerializeError> {
+        Ok(SerializeStructVariant {
+            variant,
+            dict: Dictionary::new(),
+        })
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeSeq {
+    items: Vec<Variant>,
+}
+
+impl ser::SerializeSeq for SerializeSeq {
+    type Ok = Variant;
+    type Error = SerializeError;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.items.push(value.serialize(VariantSerializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerializeError> {
+        Ok(self.items.to_variant())
+    }
+}
+
+impl ser::SerializeTuple for SerializeSeq {
+    type Ok = Variant;
+    type Error = SerializeError;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerializeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeSeq {
+    type Ok = Variant;
+    type Error = SerializeError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerializeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeTupleVariant {
+    variant: &'static str,
+    seq: SerializeSeq,
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Variant;
+    type Error = SerializeError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(&mut self.seq, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerializeError> {
+        let value = ser::SerializeSeq::end(self.seq)?;
+        Ok(tagged(self.variant, value))
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeMap {
+    dict: Dictionary<Unique>,
+    key: Option<Variant>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Variant;
+    type Error = SerializeError;
+
+    #[inline]
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(VariantSerializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_key should be called before serialize_value");
+        self.dict.insert(key, value.serialize(VariantSerializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerializeError> {
+        Ok(self.dict.owned_to_variant())
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeStruct {
+    dict: Dictionary<Unique>,
+}
+
+impl ser::SerializeStruct for SerializeStruct {
+    type Ok = Variant;
+    type Error = SerializeError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.dict.insert(key, value.serialize(VariantSerializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerializeError> {
+        Ok(self.dict.owned_to_variant())
+    }
+}
+
+#[doc(hidden)]
+pub struct SerializeStructVariant {
+    variant: &'static str,
+    dict: Dictionary<Unique>,
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Variant;
+    type Error = SerializeError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerializeError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.dict.insert(key, value.serialize(VariantSerializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Variant, SerializeError> {
+        Ok(tagged(self.variant, self.dict.owned_to_variant()))
+    }
+}
+
+/// Converts the serialized contents of a `VariantDispatch` back into the `Variant` type
+/// named by `variant`, reversing the conversions done by its `Serialize` implementation.
+fn dispatch_from_serialized(variant: &str, value: Variant) -> Result<Variant, SerializeError> {
+    // Core types and pool array elements were serialized as plain values by this serializer, so
+    // they are read back through their `Deserialize` implementations.
+    fn convert<T: DeserializeOwned>(value: &Variant) -> Result<T, SerializeError> {
+        super::from_variant(value).map_err(ser::Error::custom)
+    }
+
+    fn core_type<T: DeserializeOwned + ToVariant>(
+        value: &Variant,
+    ) -> Result<Variant, SerializeError> {
+        convert::<T>(value).map(|v| v.to_variant())
+    }
+
+    fn pool<T>(value: &Variant) -> Result<Variant, SerializeError>
+    where
+        T: PoolElement + DeserializeOwned,
+    {
+        convert::<Vec<T>>(value).map(|vec| PoolArray::from_vec(vec).owned_to_variant())
+    }
+
+    match variant {
+        "Vector2" => core_type::<Vector2>(&value),
+        "Rect2" => core_type::<Rect2>(&value),
+        "Vector3" => core_type::<Vector3>(&value),
+        "Transform2D" => core_type::<Transform2D>(&value),
+        "Plane" => core_type::<Plane>(&value),
+        "Quat" => core_type::<Quat>(&value),
+        "Aabb" => core_type::<Aabb>(&value),
+        "Basis" => core_type::<Basis>(&value),
+        "Transform" => core_type::<Transform>(&value),
+        "Color" => core_type::<Color>(&value),
+        "NodePath" => convert::<String>(&value).map(|path| NodePath::from_str(&path).to_variant()),
+        "Dictionary" => {
+            // Dictionaries are serialized as sequences of `{ key, value }` entries, which are
+            // `VariantDispatch`es themselves and already converted.
+            let entries = value
+                .try_to::<Vec<Dictionary>>()
+                .map_err(ser::Error::custom)?;
+            let dict = Dictionary::new();
+            for entry in entries {
+                dict.insert(entry.get_or_nil("key"), entry.get_or_nil("value"));
+            }
+            Ok(dict.owned_to_variant())
+        }
+        "ByteArray" => pool::<u8>(&value),
+        "Int32Array" => pool::<i32>(&value),
+        "Float32Array" => pool::<f32>(&value),
+        "StringArray" => pool::<GodotString>(&value),
+        "Vector2Array" => pool::<Vector2>(&value),
+        "Vector3Array" => pool::<Vector3>(&value),
+        "ColorArray" => pool::<Color>(&value),
+        _ => Ok(value),
+    }
+}
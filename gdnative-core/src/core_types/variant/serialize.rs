@@ -6,6 +6,11 @@ use serde::{
 };
 use std::fmt::Formatter;
 
+/// Name that `VariantDispatch` is (de)serialized with, so that the `Variant` serializer and
+/// deserializer can tell it apart from user types. It is not a valid Rust identifier, so user types
+/// can only have this name when renamed on purpose.
+pub(super) const VARIANT_DISPATCH_NAME: &str = "$gdnative::VariantDispatch";
+
 /// Custom implementation to allow using the same visitor for VariantType as well as the discriminant
 /// of VariantDispatch.
 struct VariantTypeVisitor;
@@ -157,7 +162,7 @@ impl Serialize for VariantDispatch {
     {
         use VariantDispatch::*;
 
-        const NAME: &str = "VariantDispatch";
+        const NAME: &str = VARIANT_DISPATCH_NAME;
 
         macro_rules! newtype_variant {
             ($t:expr, $v:expr) => {
@@ -269,7 +274,7 @@ impl<'de> Deserialize<'de> for VariantDispatch {
         D: Deserializer<'de>,
     {
         deserializer.deserialize_enum(
-            "VariantDispatch",
+            VARIANT_DISPATCH_NAME,
             VariantType::NAMES,
             VariantDispatchVisitor,
         )
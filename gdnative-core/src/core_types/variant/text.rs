@@ -0,0 +1,1184 @@
+//! Parser and printer for Godot's text representation of values, as used by `var2str`,
+//! `str2var`, `ConfigFile` and the `.tscn` and `.tres` formats.
+//!
+//! The functions in this module are implemented in pure Rust, and follow the grammar of the
+//! engine's `VariantParser` and `VariantWriter`. The [`write_value`] and [`parse_value`]
+//! functions operate on [`VariantValue`]s and do not require a running engine, so they can be
+//! used from build scripts and offline tools. The text representation of a `VariantValue` is
+//! also available through its `Display` implementation.
+//!
+//! Output follows the formatting of Godot 3, with a few exceptions:
+//!
+//! - Floating point numbers are written with the shortest representation that parses back to
+//!   the same value, instead of being rounded to 6 significant digits.
+//! - Dictionary entries are written in insertion order, instead of being sorted.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use gdnative::core_types::text;
+//! use gdnative::core_types::{Vector2, VariantValue};
+//!
+//! let value = text::parse_value(r#"{ "position": Vector2( 1, 2.5 ) }"#).unwrap();
+//! assert_eq!(
+//!     VariantValue::Dictionary(vec![
+//!         ("position".into(), Vector2::new(1.0, 2.5).into()),
+//!     ]),
+//!     value,
+//! );
+//!
+//! assert_eq!("{\n\"position\": Vector2( 1, 2.5 )\n}", text::write_value(&value));
+//! ```
+
+use std::fmt::{self, Write};
+
+use super::value::MAX_DEPTH;
+use super::*;
+
+/// Error that can occur when parsing a value from Godot's text format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    kind: ParseErrorKind,
+}
+
+/// The kind of a [`ParseError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The input ended before the value was complete.
+    UnexpectedEof,
+    /// A character that cannot start or continue a value was found.
+    UnexpectedChar(char),
+    /// Something else was found where the given token was expected.
+    Expected(&'static str),
+    /// A number literal is malformed or out of range.
+    InvalidNumber(String),
+    /// A string literal contains an invalid escape sequence.
+    InvalidEscape,
+    /// A color literal is not a valid HTML color code.
+    InvalidColor(String),
+    /// An identifier is neither a constant nor a known constructor.
+    UnknownIdentifier(String),
+    /// A constructor was called with the wrong number of arguments.
+    ArgumentCount {
+        constructor: String,
+        expected: usize,
+        found: usize,
+    },
+    /// Collections are nested deeper than Godot allows.
+    TooDeep,
+    /// The input contains more characters after a complete value.
+    TrailingCharacters,
+}
+
+impl ParseError {
+    /// Returns the 1-based line number where the error occurred.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the 1-based column number, in characters, where the error occurred.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the kind of this error.
+    #[inline]
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ParseErrorKind as E;
+
+        match self {
+            E::UnexpectedEof => write!(f, "unexpected end of input"),
+            E::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+            E::Expected(expected) => write!(f, "expected {expected}"),
+            E::InvalidNumber(s) => write!(f, "invalid number {s:?}"),
+            E::InvalidEscape => write!(f, "invalid escape sequence"),
+            E::InvalidColor(s) => write!(f, "invalid color code {s:?}"),
+            E::UnknownIdentifier(s) => write!(f, "unknown identifier {s:?}"),
+            E::ArgumentCount {
+                constructor,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{constructor} expects {expected} arguments, but {found} were given"
+            ),
+            E::TooDeep => write!(f, "maximum nesting depth of {MAX_DEPTH} exceeded"),
+            E::TrailingCharacters => write!(f, "trailing characters after value"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Writes a `Variant` in Godot's text format.
+///
+/// This is equivalent to `var2str(variant)`, except that objects are written as `null`.
+///
+/// # Errors
+///
+/// Returns an error if the `Variant` contains collections nested deeper than Godot allows,
+/// e.g. an array that contains itself.
+#[inline]
+pub fn write_variant(variant: &Variant) -> Result<String, FromVariantError> {
+    VariantValue::from_variant(variant).map(|value| write_value(&value))
+}
+
+/// Parses a `Variant` from Godot's text format.
+///
+/// This is equivalent to `str2var(text)`, except that objects are always parsed as `Nil`. Use
+/// [`parse_value`] to inspect objects and resource references.
+#[inline]
+pub fn parse_variant(text: &str) -> Result<Variant, ParseError> {
+    parse_value(text).map(|value| value.to_variant())
+}
+
+/// Writes a `VariantValue` in Godot's text format.
+///
+/// Objects identified only by their instance IDs have no text representation, and are written
+/// as `null`.
+#[inline]
+pub fn write_value(value: &VariantValue) -> String {
+    value.to_string()
+}
+
+/// Parses a `VariantValue` from Godot's text format.
+///
+/// The input must contain exactly one value, optionally surrounded by whitespace and `;`
+/// comments.
+#[inline]
+pub fn parse_value(text: &str) -> Result<VariantValue, ParseError> {
+    let mut parser = Parser::new(text);
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.peek().is_some() {
+        return Err(parser.error(ParseErrorKind::TrailingCharacters));
+    }
+    Ok(value)
+}
+
+impl fmt::Display for VariantValue {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_to(f, self)
+    }
+}
+
+fn write_to<W: Write>(w: &mut W, value: &VariantValue) -> fmt::Result {
+    use VariantValue as V;
+
+    match value {
+        V::Nil => w.write_str("null"),
+        V::Bool(v) => write!(w, "{v}"),
+        V::I64(v) => write!(w, "{v}"),
+        V::F64(v) => write_f64(w, *v),
+        V::GodotString(v) => write_string(w, v),
+        V::Vector2(v) => write_ctor(w, "Vector2", &[v.x, v.y]),
+        V::Rect2(v) => write_ctor(
+            w,
+            "Rect2",
+            &[v.position.x, v.position.y, v.size.x, v.size.y],
+        ),
+        V::Vector3(v) => write_ctor(w, "Vector3", &[v.x, v.y, v.z]),
+        V::Transform2D(v) => write_ctor(
+            w,
+            "Transform2D",
+            &[v.a.x, v.a.y, v.b.x, v.b.y, v.origin.x, v.origin.y],
+        ),
+        V::Plane(v) => write_ctor(w, "Plane", &[v.normal.x, v.normal.y, v.normal.z, v.d]),
+        V::Quat(v) => write_ctor(w, "Quat", &[v.x, v.y, v.z, v.w]),
+        V::Aabb(v) => write_ctor(
+            w,
+            "AABB",
+            &[
+                v.position.x,
+                v.position.y,
+                v.position.z,
+                v.size.x,
+                v.size.y,
+                v.size.z,
+            ],
+        ),
+        V::Basis(v) => write_ctor(w, "Basis", &basis_components(v)),
+        V::Transform(v) => {
+            let mut components = [0.0; 12];
+            components[..9].copy_from_slice(&basis_components(&v.basis));
+            components[9..].copy_from_slice(&[v.origin.x, v.origin.y, v.origin.z]);
+            write_ctor(w, "Transform", &components)
+        }
+        V::Color(v) => write_ctor(w, "Color", &[v.r, v.g, v.b, v.a]),
+        V::NodePath(v) => {
+            w.write_str("NodePath(")?;
+            write_string(w, v)?;
+            w.write_str(")")
+        }
+        V::Rid => w.write_str("RID()"),
+        V::Object(ObjectValue::Full { class, properties }) => {
+            write!(w, "Object({class},")?;
+            for (name, value) in properties {
+                write_string(w, name)?;
+                w.write_str(":")?;
+                write_to(w, value)?;
+                w.write_str(",")?;
+            }
+            w.write_str(")")
+        }
+        V::Object(ObjectValue::Resource(path)) => {
+            w.write_str("Resource( ")?;
+            write_string(w, path)?;
+            w.write_str(" )")
+        }
+        V::Object(ObjectValue::ExtResource(id)) => write!(w, "ExtResource( {id} )"),
+        V::Object(ObjectValue::SubResource(id)) => write!(w, "SubResource( {id} )"),
+        V::Object(_) => w.write_str("null"),
+        V::Dictionary(entries) => {
+            w.write_str("{\n")?;
+            for (i, (key, value)) in entries.iter().enumerate() {
+                if i > 0 {
+                    w.write_str(",\n")?;
+                }
+                write_to(w, key)?;
+                w.write_str(": ")?;
+                write_to(w, value)?;
+            }
+            if !entries.is_empty() {
+                w.write_str("\n")?;
+            }
+            w.write_str("}")
+        }
+        V::VariantArray(items) => {
+            w.write_str("[ ")?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    w.write_str(", ")?;
+                }
+                write_to(w, item)?;
+            }
+            w.write_str(" ]")
+        }
+        V::ByteArray(v) => write_list(w, "PoolByteArray", v, |w, b| write!(w, "{b}")),
+        V::Int32Array(v) => write_list(w, "PoolIntArray", v, |w, i| write!(w, "{i}")),
+        V::Float32Array(v) => write_list(w, "PoolRealArray", v, |w, f| write_f32(w, *f)),
+        V::StringArray(v) => write_list(w, "PoolStringArray", v, |w, s| write_string(w, s)),
+        V::Vector2Array(v) => {
+            write_list(w, "PoolVector2Array", v, |w, v| write_f32s(w, &[v.x, v.y]))
+        }
+        V::Vector3Array(v) => write_list(w, "PoolVector3Array", v, |w, v| {
+            write_f32s(w, &[v.x, v.y, v.z])
+        }),
+        V::ColorArray(v) => write_list(w, "PoolColorArray", v, |w, c| {
+            write_f32s(w, &[c.r, c.g, c.b, c.a])
+        }),
+    }
+}
+
+fn basis_components(basis: &Basis) -> [f32; 9] {
+    let [x, y, z] = basis.elements;
+    [x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z]
+}
+
+fn write_f64<W: Write>(w: &mut W, v: f64) -> fmt::Result {
+    if v.is_nan() {
+        w.write_str("nan")
+    } else if v.is_infinite() {
+        w.write_str(if v > 0.0 { "inf" } else { "inf_neg" })
+    } else {
+        // `Debug` always includes a decimal point or an exponent, which is what tells floats
+        // apart from integers.
+        write!(w, "{v:?}")
+    }
+}
+
+fn write_f32<W: Write>(w: &mut W, v: f32) -> fmt::Result {
+    if v.is_nan() {
+        w.write_str("nan")
+    } else if v.is_infinite() {
+        w.write_str(if v > 0.0 { "inf" } else { "inf_neg" })
+    } else if v != 0.0 && !(1e-4..1e16).contains(&v.abs()) {
+        // Like Godot's `%g`, very large and very small values use an exponent. Without one they
+        // would be written as long integers, which don't always read back as numbers.
+        write!(w, "{v:e}")
+    } else {
+        write!(w, "{v}")
+    }
+}
+
+fn write_f32s<W: Write>(w: &mut W, components: &[f32]) -> fmt::Result {
+    for (i, v) in components.iter().enumerate() {
+        if i > 0 {
+            w.write_str(", ")?;
+        }
+        write_f32(w, *v)?;
+    }
+    Ok(())
+}
+
+fn write_ctor<W: Write>(w: &mut W, name: &str, components: &[f32]) -> fmt::Result {
+    write!(w, "{name}( ")?;
+    write_f32s(w, components)?;
+    w.write_str(" )")
+}
+
+fn write_list<W: Write, T>(
+    w: &mut W,
+    name: &str,
+    items: &[T],
+    mut write_item: impl FnMut(&mut W, &T) -> fmt::Result,
+) -> fmt::Result {
+    write!(w, "{name}( ")?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            w.write_str(", ")?;
+        }
+        write_item(w, item)?;
+    }
+    w.write_str(" )")
+}
+
+/// Writes a quoted string. Like in Godot, only quotes and backslashes are escaped, so that
+/// multi-line strings stay readable.
+pub(crate) fn write_string<W: Write>(w: &mut W, s: &str) -> fmt::Result {
+    w.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
+
+/// Writes the name of a property like Godot's `String::property_name_encode`, quoting it if it
+/// contains characters that would otherwise end it early.
+pub(crate) fn write_property_name<W: Write>(w: &mut W, name: &str) -> fmt::Result {
+    let needs_quotes = name.is_empty()
+        || name.starts_with(['[', ';'])
+        || name
+            .chars()
+            .any(|c| c == '=' || c == '"' || c <= ' ' || c > '~');
+
+    if needs_quotes {
+        write_string(w, name)
+    } else {
+        w.write_str(name)
+    }
+}
+
+/// Recursive descent parser for Godot's text format, operating on a position in a larger
+/// document, so that errors are reported relative to the whole document.
+pub(crate) struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(src: &'a str) -> Self {
+        Parser { src, pos: 0 }
+    }
+
+    /// Returns the current byte offset into the document.
+    pub(crate) fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub(crate) fn set_position(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    pub(crate) fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub(crate) fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    pub(crate) fn error(&self, kind: ParseErrorKind) -> ParseError {
+        self.error_at(self.pos, kind)
+    }
+
+    pub(crate) fn error_at(&self, pos: usize, kind: ParseErrorKind) -> ParseError {
+        let before = &self.src[..pos];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        ParseError { line, column, kind }
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.peek() {
+            Some(c) => self.error(ParseErrorKind::UnexpectedChar(c)),
+            None => self.error(ParseErrorKind::UnexpectedEof),
+        }
+    }
+
+    /// Skips whitespace and `;` comments.
+    pub(crate) fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == ';' {
+                let len = self.rest().find('\n').unwrap_or(self.rest().len());
+                self.pos += len;
+            } else if c.is_whitespace() {
+                self.pos += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Skips whitespace and consumes `expected` if it is the next character.
+    pub(crate) fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn expect(&mut self, expected: char, name: &'static str) -> Result<(), ParseError> {
+        if self.eat(expected) {
+            Ok(())
+        } else if self.peek().is_none() {
+            Err(self.error(ParseErrorKind::UnexpectedEof))
+        } else {
+            Err(self.error(ParseErrorKind::Expected(name)))
+        }
+    }
+
+    pub(crate) fn identifier(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let first = rest.chars().next()?;
+        if !(first.is_alphabetic() || first == '_') {
+            return None;
+        }
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        Some(&rest[..len])
+    }
+
+    pub(crate) fn string(&mut self) -> Result<String, ParseError> {
+        self.expect('"', "string")?;
+        let mut s = String::new();
+        loop {
+            let c = self
+                .bump()
+                .ok_or_else(|| self.error(ParseErrorKind::UnexpectedEof))?;
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let start = self.pos - 1;
+                    let escaped = match self.bump() {
+                        Some('b') => '\u{8}',
+                        Some('t') => '\t',
+                        Some('n') => '\n',
+                        Some('f') => '\u{c}',
+                        Some('r') => '\r',
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('\'') => '\'',
+                        Some('/') => '/',
+                        Some('u') => {
+                            let high = self.hex4(start)?;
+                            let code = if (0xD800..0xDC00).contains(&high) {
+                                // Surrogate pair
+                                if !self.rest().starts_with("\\u") {
+                                    return Err(self.error_at(start, ParseErrorKind::InvalidEscape));
+                                }
+                                self.pos += 2;
+                                let low = self.hex4(start)?;
+                                if !(0xDC00..0xE000).contains(&low) {
+                                    return Err(self.error_at(start, ParseErrorKind::InvalidEscape));
+                                }
+                                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                            } else {
+                                high
+                            };
+                            char::from_u32(code).ok_or_else(|| {
+                                self.error_at(start, ParseErrorKind::InvalidEscape)
+                            })?
+                        }
+                        _ => return Err(self.error_at(start, ParseErrorKind::InvalidEscape)),
+                    };
+                    s.push(escaped);
+                }
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self, start: usize) -> Result<u32, ParseError> {
+        let digits = self
+            .rest()
+            .get(..4)
+            .filter(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error_at(start, ParseErrorKind::InvalidEscape))?;
+        self.pos += 4;
+        Ok(u32::from_str_radix(digits, 16).expect("digits should be valid"))
+    }
+
+    fn number(&mut self) -> Result<VariantValue, ParseError> {
+        let start = self.pos;
+        let rest = self.rest();
+        let mut len = 0;
+        let mut is_float = false;
+        for (i, c) in rest.char_indices() {
+            match c {
+                '0'..='9' => {}
+                '-' | '+' if i == 0 || rest[..i].ends_with(['e', 'E']) => {}
+                '.' | 'e' | 'E' => is_float = true,
+                _ => break,
+            }
+            len = i + 1;
+        }
+
+        let literal = &rest[..len];
+        self.pos += len;
+        let invalid = || self.error_at(start, ParseErrorKind::InvalidNumber(literal.to_owned()));
+
+        if is_float {
+            literal
+                .parse()
+                .map(VariantValue::F64)
+                .map_err(|_| invalid())
+        } else {
+            literal
+                .parse()
+                .map(VariantValue::I64)
+                .map_err(|_| invalid())
+        }
+    }
+
+    /// Parses the name of a property assignment, which is either quoted or extends up to the
+    /// `=`, and consumes the `=`, which must be on the same line.
+    pub(crate) fn property_name(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        let name = if self.peek() == Some('"') {
+            self.string()?
+        } else {
+            let rest = self.rest();
+            let len = rest.find(['=', '\n']).unwrap_or(rest.len());
+            let name = rest[..len].trim_end();
+            self.pos += name.len();
+            name.to_owned()
+        };
+
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t']).len();
+        if self.pos == start {
+            return Err(self.error(ParseErrorKind::Expected("property name")));
+        }
+        match self.peek() {
+            Some('=') => {
+                self.pos += 1;
+                Ok(name)
+            }
+            Some(_) => Err(self.error(ParseErrorKind::Expected("'='"))),
+            None => Err(self.error(ParseErrorKind::UnexpectedEof)),
+        }
+    }
+
+    /// Parses a single value.
+    pub(crate) fn value(&mut self) -> Result<VariantValue, ParseError> {
+        self.value_with_depth(0)
+    }
+
+    fn value_with_depth(&mut self, depth: usize) -> Result<VariantValue, ParseError> {
+        self.skip_whitespace();
+        if depth > MAX_DEPTH {
+            return Err(self.error(ParseErrorKind::TooDeep));
+        }
+
+        match self.peek() {
+            Some('{') => self.dictionary(depth),
+            Some('[') => self.array(depth),
+            Some('"') => self.string().map(VariantValue::GodotString),
+            Some('#') => {
+                let start = self.pos;
+                self.pos += 1;
+                let len = self
+                    .rest()
+                    .find(|c: char| !c.is_ascii_hexdigit())
+                    .unwrap_or(self.rest().len());
+                let code = &self.rest()[..len];
+                self.pos += len;
+                Color::from_html(code)
+                    .map(VariantValue::Color)
+                    .ok_or_else(|| {
+                        self.error_at(start, ParseErrorKind::InvalidColor(code.to_owned()))
+                    })
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                if self.rest().starts_with("-inf") {
+                    self.pos += 4;
+                    return Ok(VariantValue::F64(f64::NEG_INFINITY));
+                }
+                self.number()
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => self.identifier_value(depth),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn dictionary(&mut self, depth: usize) -> Result<VariantValue, ParseError> {
+        self.expect('{', "'{'")?;
+        let mut entries = Vec::new();
+        while !self.eat('}') {
+            if !entries.is_empty() {
+                self.expect(',', "',' or '}'")?;
+                if self.eat('}') {
+                    break;
+                }
+            }
+            let key = self.value_with_depth(depth + 1)?;
+            self.expect(':', "':'")?;
+            let value = self.value_with_depth(depth + 1)?;
+            entries.push((key, value));
+        }
+        Ok(VariantValue::Dictionary(entries))
+    }
+
+    fn array(&mut self, depth: usize) -> Result<VariantValue, ParseError> {
+        self.expect('[', "'['")?;
+        let mut items = Vec::new();
+        while !self.eat(']') {
+            if !items.is_empty() {
+                self.expect(',', "',' or ']'")?;
+                if self.eat(']') {
+                    break;
+                }
+            }
+            items.push(self.value_with_depth(depth + 1)?);
+        }
+        Ok(VariantValue::VariantArray(items))
+    }
+
+    /// Parses comma-separated constructor arguments up to and including the closing
+    /// parenthesis.
+    fn args(&mut self, depth: usize) -> Result<Vec<VariantValue>, ParseError> {
+        self.expect('(', "'('")?;
+        let mut args = Vec::new();
+        while !self.eat(')') {
+            if !args.is_empty() {
+                self.expect(',', "',' or ')'")?;
+            }
+            args.push(self.value_with_depth(depth + 1)?);
+        }
+        Ok(args)
+    }
+
+    fn identifier_value(&mut self, depth: usize) -> Result<VariantValue, ParseError> {
+        let start = self.pos;
+        let ident = self.identifier().ok_or_else(|| self.unexpected())?;
+
+        let value = match ident {
+            "true" => VariantValue::Bool(true),
+            "false" => VariantValue::Bool(false),
+            "null" | "nil" => VariantValue::Nil,
+            "inf" => VariantValue::F64(f64::INFINITY),
+            "inf_neg" => VariantValue::F64(f64::NEG_INFINITY),
+            "nan" => VariantValue::F64(f64::NAN),
+            "Object" => self.object(depth)?,
+            _ => {
+                let ctor = Constructor { start, name: ident };
+                let args = self.args(depth)?;
+                self.construct(ctor, args)?
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn object(&mut self, depth: usize) -> Result<VariantValue, ParseError> {
+        self.expect('(', "'('")?;
+        let class = self
+            .identifier()
+            .ok_or_else(|| self.error(ParseErrorKind::Expected("class name")))?
+            .to_owned();
+
+        let mut properties = Vec::new();
+        loop {
+            if self.eat(')') {
+                break;
+            }
+            self.expect(',', "',' or ')'")?;
+            if self.eat(')') {
+                break;
+            }
+            let name = self.string()?;
+            self.expect(':', "':'")?;
+            let value = self.value_with_depth(depth + 1)?;
+            properties.push((name, value));
+        }
+
+        Ok(VariantValue::Object(ObjectValue::Full {
+            class,
+            properties,
+        }))
+    }
+
+    fn construct(
+        &self,
+        ctor: Constructor<'a>,
+        args: Vec<VariantValue>,
+    ) -> Result<VariantValue, ParseError> {
+        use VariantValue as V;
+
+        let value = match ctor.name {
+            "Vector2" => {
+                let [x, y] = self.floats(&ctor, &args)?;
+                V::Vector2(Vector2::new(x, y))
+            }
+            "Rect2" => {
+                let [x, y, w, h] = self.floats(&ctor, &args)?;
+                V::Rect2(Rect2 {
+                    position: Vector2::new(x, y),
+                    size: Vector2::new(w, h),
+                })
+            }
+            "Vector3" => {
+                let [x, y, z] = self.floats(&ctor, &args)?;
+                V::Vector3(Vector3::new(x, y, z))
+            }
+            "Transform2D" | "Matrix32" => {
+                let [ax, ay, bx, by, ox, oy] = self.floats(&ctor, &args)?;
+                V::Transform2D(Transform2D {
+                    a: Vector2::new(ax, ay),
+                    b: Vector2::new(bx, by),
+                    origin: Vector2::new(ox, oy),
+                })
+            }
+            "Plane" => {
+                let [x, y, z, d] = self.floats(&ctor, &args)?;
+                V::Plane(Plane {
+                    normal: Vector3::new(x, y, z),
+                    d,
+                })
+            }
+            "Quat" => {
+                let [x, y, z, w] = self.floats(&ctor, &args)?;
+                V::Quat(Quat::new(x, y, z, w))
+            }
+            "AABB" | "Rect3" => {
+                let [px, py, pz, sx, sy, sz] = self.floats(&ctor, &args)?;
+                V::Aabb(Aabb {
+                    position: Vector3::new(px, py, pz),
+                    size: Vector3::new(sx, sy, sz),
+                })
+            }
+            "Basis" | "Matrix3" => V::Basis(basis_from(self.floats(&ctor, &args)?)),
+            "Transform" => {
+                let c: [f32; 12] = self.floats(&ctor, &args)?;
+                let mut basis = [0.0; 9];
+                basis.copy_from_slice(&c[..9]);
+                V::Transform(Transform {
+                    basis: basis_from(basis),
+                    origin: Vector3::new(c[9], c[10], c[11]),
+                })
+            }
+            "Color" if args.len() == 3 => {
+                let [r, g, b] = self.floats(&ctor, &args)?;
+                V::Color(Color::from_rgb(r, g, b))
+            }
+            "Color" => {
+                let [r, g, b, a] = self.floats(&ctor, &args)?;
+                V::Color(Color::from_rgba(r, g, b, a))
+            }
+            "NodePath" => {
+                let [path] = self.strings(&ctor, args)?;
+                V::NodePath(path)
+            }
+            "RID" => V::Rid,
+            "Resource" => {
+                let [path] = self.strings(&ctor, args)?;
+                V::Object(ObjectValue::Resource(path))
+            }
+            "ExtResource" => {
+                let [id] = self.ints(&ctor, &args)?;
+                V::Object(ObjectValue::ExtResource(id))
+            }
+            "SubResource" => {
+                let [id] = self.ints(&ctor, &args)?;
+                V::Object(ObjectValue::SubResource(id))
+            }
+            "PoolByteArray" | "ByteArray" => V::ByteArray(
+                args.iter()
+                    .map(|arg| match arg {
+                        V::I64(i) => u8::try_from(*i).ok(),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| ctor.error(self, "bytes"))?,
+            ),
+            "PoolIntArray" | "IntArray" => V::Int32Array(
+                args.iter()
+                    .map(|arg| match arg {
+                        V::I64(i) => i32::try_from(*i).ok(),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| ctor.error(self, "32-bit integers"))?,
+            ),
+            "PoolRealArray" | "RealArray" | "FloatArray" => {
+                V::Float32Array(self.float_list(&ctor, &args, 1, |c| c[0])?)
+            }
+            "PoolStringArray" | "StringArray" => V::StringArray(
+                args.into_iter()
+                    .map(|arg| match arg {
+                        V::GodotString(s) => Some(s),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| ctor.error(self, "strings"))?,
+            ),
+            "PoolVector2Array" | "Vector2Array" => {
+                V::Vector2Array(self.float_list(&ctor, &args, 2, |c| Vector2::new(c[0], c[1]))?)
+            }
+            "PoolVector3Array" | "Vector3Array" => {
+                V::Vector3Array(
+                    self.float_list(&ctor, &args, 3, |c| Vector3::new(c[0], c[1], c[2]))?,
+                )
+            }
+            "PoolColorArray" | "ColorArray" => {
+                V::ColorArray(self.float_list(&ctor, &args, 4, |c| {
+                    Color::from_rgba(c[0], c[1], c[2], c[3])
+                })?)
+            }
+            name => {
+                return Err(self.error_at(
+                    ctor.start,
+                    ParseErrorKind::UnknownIdentifier(name.to_owned()),
+                ))
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn floats<const N: usize>(
+        &self,
+        ctor: &Constructor,
+        args: &[VariantValue],
+    ) -> Result<[f32; N], ParseError> {
+        if args.len() != N {
+            return Err(ctor.argument_count(self, N, args.len()));
+        }
+        let mut floats = [0.0; N];
+        for (float, arg) in floats.iter_mut().zip(args) {
+            *float = as_f32(arg).ok_or_else(|| ctor.error(self, "numbers"))?;
+        }
+        Ok(floats)
+    }
+
+    fn float_list<T>(
+        &self,
+        ctor: &Constructor,
+        args: &[VariantValue],
+        stride: usize,
+        make: impl Fn(&[f32]) -> T,
+    ) -> Result<Vec<T>, ParseError> {
+        let floats = args
+            .iter()
+            .map(as_f32)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| ctor.error(self, "numbers"))?;
+        if floats.len() % stride != 0 {
+            let expected = floats.len() - floats.len() % stride;
+            return Err(ctor.argument_count(self, expected, floats.len()));
+        }
+        Ok(floats.chunks(stride).map(make).collect())
+    }
+
+    fn ints<const N: usize>(
+        &self,
+        ctor: &Constructor,
+        args: &[VariantValue],
+    ) -> Result<[i64; N], ParseError> {
+        if args.len() != N {
+            return Err(ctor.argument_count(self, N, args.len()));
+        }
+        let mut ints = [0; N];
+        for (int, arg) in ints.iter_mut().zip(args) {
+            match arg {
+                VariantValue::I64(i) => *int = *i,
+                _ => return Err(ctor.error(self, "integers")),
+            }
+        }
+        Ok(ints)
+    }
+
+    fn strings<const N: usize>(
+        &self,
+        ctor: &Constructor,
+        args: Vec<VariantValue>,
+    ) -> Result<[String; N], ParseError> {
+        if args.len() != N {
+            return Err(ctor.argument_count(self, N, args.len()));
+        }
+        let strings = args
+            .into_iter()
+            .map(|arg| match arg {
+                VariantValue::GodotString(s) => Ok(s),
+                _ => Err(ctor.error(self, "strings")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(strings.try_into().expect("length should be checked"))
+    }
+}
+
+struct Constructor<'a> {
+    start: usize,
+    name: &'a str,
+}
+
+impl<'a> Constructor<'a> {
+    fn argument_count(&self, parser: &Parser, expected: usize, found: usize) -> ParseError {
+        parser.error_at(
+            self.start,
+            ParseErrorKind::ArgumentCount {
+                constructor: self.name.to_owned(),
+                expected,
+                found,
+            },
+        )
+    }
+
+    fn error(&self, parser: &Parser, expected: &'static str) -> ParseError {
+        parser.error_at(self.start, ParseErrorKind::Expected(expected))
+    }
+}
+
+fn as_f32(value: &VariantValue) -> Option<f32> {
+    match value {
+        VariantValue::I64(i) => Some(*i as f32),
+        VariantValue::F64(f) => Some(*f as f32),
+        _ => None,
+    }
+}
+
+fn basis_from(c: [f32; 9]) -> Basis {
+    Basis {
+        elements: [
+            Vector3::new(c[0], c[1], c[2]),
+            Vector3::new(c[3], c[4], c[5]),
+            Vector3::new(c[6], c[7], c[8]),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(text: &str, value: VariantValue) {
+        assert_eq!(Ok(&value), parse_value(text).as_ref(), "parsing {text:?}");
+        assert_eq!(text, write_value(&value));
+    }
+
+    #[test]
+    fn scalars() {
+        round_trip("null", VariantValue::Nil);
+        round_trip("true", true.into());
+        round_trip("-42", (-42).into());
+        round_trip("1.0", 1.0.into());
+        round_trip("0.1", 0.1.into());
+        round_trip("1e300", 1e300.into());
+        round_trip("inf", f64::INFINITY.into());
+        round_trip("inf_neg", f64::NEG_INFINITY.into());
+        round_trip(r#""a \"quoted\"\\ string""#, r#"a "quoted"\ string"#.into());
+        round_trip("\"multi\nline\"", "multi\nline".into());
+
+        assert_eq!(Ok(VariantValue::Nil), parse_value("nil"));
+        assert_eq!(Ok(f64::NEG_INFINITY.into()), parse_value("-inf"));
+        assert_eq!(Ok(2.5e-3.into()), parse_value("2.5E-3"));
+        assert_eq!(
+            Ok("tab\t\u{e9}\u{1f600}".into()),
+            parse_value(r#""tab\té😀""#)
+        );
+        assert!(matches!(parse_value("nan"), Ok(VariantValue::F64(f)) if f.is_nan()));
+    }
+
+    #[test]
+    fn constructors() {
+        round_trip("Vector2( 1, 2.5 )", Vector2::new(1.0, 2.5).into());
+        round_trip("Vector3( 0.1, -2, 3 )", Vector3::new(0.1, -2.0, 3.0).into());
+        round_trip(
+            "Transform2D( 1, 0, 0, 1, 10, 20 )",
+            Transform2D {
+                origin: Vector2::new(10.0, 20.0),
+                ..Transform2D::IDENTITY
+            }
+            .into(),
+        );
+        round_trip(
+            "Transform( 1, 0, 0, 0, 1, 0, 0, 0, 1, 4, 5, 6 )",
+            Transform {
+                basis: Basis::IDENTITY,
+                origin: Vector3::new(4.0, 5.0, 6.0),
+            }
+            .into(),
+        );
+        round_trip(
+            "Color( 1, 0.5, 0, 1 )",
+            Color::from_rgba(1.0, 0.5, 0.0, 1.0).into(),
+        );
+        round_trip(
+            "NodePath(\"Player/Sprite:modulate\")",
+            VariantValue::NodePath("Player/Sprite:modulate".into()),
+        );
+        round_trip(
+            "PoolIntArray( 1, -2, 3 )",
+            VariantValue::Int32Array(vec![1, -2, 3]),
+        );
+        round_trip("PoolByteArray(  )", VariantValue::ByteArray(vec![]));
+        round_trip(
+            "PoolVector2Array( 1, 2, 3, 4 )",
+            VariantValue::Vector2Array(vec![Vector2::new(1.0, 2.0), Vector2::new(3.0, 4.0)]),
+        );
+        round_trip(
+            "PoolStringArray( \"a\", \"b\" )",
+            VariantValue::StringArray(vec!["a".into(), "b".into()]),
+        );
+
+        round_trip(
+            "Vector2( 1e30, -1.5e-30 )",
+            Vector2::new(1e30, -1.5e-30).into(),
+        );
+        round_trip(
+            "PoolRealArray( 3.4028235e38, 1e-45, 0.0001, 1000000000000000 )",
+            VariantValue::Float32Array(vec![f32::MAX, 1e-45, 1e-4, 1e15]),
+        );
+
+        assert_eq!(
+            Ok(Vector2::new(1.0, 2.0).into()),
+            parse_value("Vector2(1,2)")
+        );
+        assert_eq!(
+            Ok(VariantValue::Float32Array(vec![1.5])),
+            parse_value("RealArray(1.5)")
+        );
+    }
+
+    #[test]
+    fn resources() {
+        round_trip("ExtResource( 1 )", ObjectValue::ExtResource(1).into());
+        round_trip("SubResource( 2 )", ObjectValue::SubResource(2).into());
+        round_trip(
+            "Resource( \"res://icon.png\" )",
+            ObjectValue::Resource("res://icon.png".into()).into(),
+        );
+        round_trip(
+            "Object(InputEventKey,\"scancode\":65,\"pressed\":false,)",
+            ObjectValue::Full {
+                class: "InputEventKey".into(),
+                properties: vec![
+                    ("scancode".into(), 65.into()),
+                    ("pressed".into(), false.into()),
+                ],
+            }
+            .into(),
+        );
+    }
+
+    #[test]
+    fn collections() {
+        round_trip("[  ]", VariantValue::VariantArray(vec![]));
+        round_trip("{\n}", VariantValue::Dictionary(vec![]));
+        round_trip(
+            "{\n\"a\": [ 1, 2 ],\n3: {\n\"b\": null\n}\n}",
+            VariantValue::Dictionary(vec![
+                (
+                    "a".into(),
+                    VariantValue::VariantArray(vec![1.into(), 2.into()]),
+                ),
+                (
+                    3.into(),
+                    VariantValue::Dictionary(vec![("b".into(), VariantValue::Nil)]),
+                ),
+            ]),
+        );
+
+        assert_eq!(
+            Ok(VariantValue::VariantArray(vec![1.into(), 2.into()])),
+            parse_value("[1, 2, ] ; trailing comment"),
+        );
+        assert_eq!(
+            Ok(VariantValue::Dictionary(vec![("a".into(), 1.into())])),
+            parse_value("{ \"a\": 1, }"),
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let err = parse_value("[\n  1,\n  Vector2(1, 2, 3)\n]").unwrap_err();
+        assert_eq!((3, 3), (err.line(), err.column()));
+        assert_eq!(
+            &ParseErrorKind::ArgumentCount {
+                constructor: "Vector2".into(),
+                expected: 2,
+                found: 3,
+            },
+            err.kind(),
+        );
+
+        assert_eq!(
+            &ParseErrorKind::UnexpectedEof,
+            parse_value("[ 1, 2").unwrap_err().kind()
+        );
+        assert_eq!(
+            &ParseErrorKind::UnknownIdentifier("Vector2i".into()),
+            parse_value("Vector2i(1, 2)").unwrap_err().kind()
+        );
+        assert_eq!(
+            &ParseErrorKind::InvalidEscape,
+            parse_value(r#""\q""#).unwrap_err().kind()
+        );
+        assert_eq!(
+            &ParseErrorKind::TrailingCharacters,
+            parse_value("1 2").unwrap_err().kind()
+        );
+        assert_eq!(
+            &ParseErrorKind::Expected("32-bit integers"),
+            parse_value("PoolIntArray(1, 1.5)").unwrap_err().kind()
+        );
+
+        let nested = "[".repeat(MAX_DEPTH + 2);
+        assert_eq!(
+            &ParseErrorKind::TooDeep,
+            parse_value(&nested).unwrap_err().kind()
+        );
+    }
+}
@@ -0,0 +1,384 @@
+use super::*;
+
+/// Maximum nesting depth of collections, consistent with Godot's `MAX_RECURSION_DEPTH`.
+pub(crate) const MAX_DEPTH: usize = 1024;
+
+/// Owned, engine-independent representation of a `Variant` value.
+///
+/// Unlike [`VariantDispatch`], this type does not hold any engine-managed values, so it can be
+/// created, inspected and dropped on any thread, and without a running engine at all. This makes
+/// it suitable for offline tools and unit tests, or for reading and writing serialized data on
+/// worker threads. It can be converted to and from `Variant` with [`ToVariant`] and
+/// [`FromVariant`].
+///
+/// Engine-managed types are represented as plain Rust values:
+///
+/// - `GodotString` and `NodePath` are represented as `String`s.
+/// - `Dictionary` is represented as a list of key-value pairs, in insertion order.
+/// - `VariantArray` and `PoolArray`s are represented as `Vec`s.
+/// - `Rid` is represented without its value, since it is only meaningful within the engine.
+/// - Objects are represented as an [`ObjectValue`].
+#[derive(Clone, Debug, PartialEq, Default)]
+#[non_exhaustive]
+pub enum VariantValue {
+    #[default]
+    Nil,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    GodotString(String),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    NodePath(String),
+    Rid,
+    Object(ObjectValue),
+    Dictionary(Vec<(VariantValue, VariantValue)>),
+    VariantArray(Vec<VariantValue>),
+    ByteArray(Vec<u8>),
+    Int32Array(Vec<i32>),
+    Float32Array(Vec<f32>),
+    StringArray(Vec<String>),
+    Vector2Array(Vec<Vector2>),
+    Vector3Array(Vec<Vector3>),
+    ColorArray(Vec<Color>),
+}
+
+/// Engine-independent representation of an object held in a [`VariantValue`].
+///
+/// Objects cannot be recreated outside of the engine, so this only records the information
+/// that serialized formats store about them.
+#[derive(Clone, Debug, PartialEq, Default)]
+#[non_exhaustive]
+pub enum ObjectValue {
+    /// A null object.
+    #[default]
+    Null,
+    /// An object identified by its instance ID, as stored by binary serialization.
+    Id(u64),
+    /// A live object, identified by its class name and instance ID.
+    Instance { class: String, id: u64 },
+    /// An object stored by its class name and the values of its stored properties.
+    Full {
+        class: String,
+        properties: Vec<(String, VariantValue)>,
+    },
+    /// A resource loaded from a path, written as `Resource("res://path")` in text formats.
+    Resource(String),
+    /// An external resource of a scene or resource file, written as `ExtResource(id)`.
+    ExtResource(i64),
+    /// An internal resource of a scene or resource file, written as `SubResource(id)`.
+    SubResource(i64),
+}
+
+impl VariantValue {
+    /// Returns the `VariantType` of the `Variant` this value represents.
+    #[inline]
+    pub fn get_type(&self) -> VariantType {
+        match self {
+            VariantValue::Nil => VariantType::Nil,
+            VariantValue::Bool(_) => VariantType::Bool,
+            VariantValue::I64(_) => VariantType::I64,
+            VariantValue::F64(_) => VariantType::F64,
+            VariantValue::GodotString(_) => VariantType::GodotString,
+            VariantValue::Vector2(_) => VariantType::Vector2,
+            VariantValue::Rect2(_) => VariantType::Rect2,
+            VariantValue::Vector3(_) => VariantType::Vector3,
+            VariantValue::Transform2D(_) => VariantType::Transform2D,
+            VariantValue::Plane(_) => VariantType::Plane,
+            VariantValue::Quat(_) => VariantType::Quat,
+            VariantValue::Aabb(_) => VariantType::Aabb,
+            VariantValue::Basis(_) => VariantType::Basis,
+            VariantValue::Transform(_) => VariantType::Transform,
+            VariantValue::Color(_) => VariantType::Color,
+            VariantValue::NodePath(_) => VariantType::NodePath,
+            VariantValue::Rid => VariantType::Rid,
+            VariantValue::Object(_) => VariantType::Object,
+            VariantValue::Dictionary(_) => VariantType::Dictionary,
+            VariantValue::VariantArray(_) => VariantType::VariantArray,
+            VariantValue::ByteArray(_) => VariantType::ByteArray,
+            VariantValue::Int32Array(_) => VariantType::Int32Array,
+            VariantValue::Float32Array(_) => VariantType::Float32Array,
+            VariantValue::StringArray(_) => VariantType::StringArray,
+            VariantValue::Vector2Array(_) => VariantType::Vector2Array,
+            VariantValue::Vector3Array(_) => VariantType::Vector3Array,
+            VariantValue::ColorArray(_) => VariantType::ColorArray,
+        }
+    }
+
+    /// Returns `true` if this is `Nil`, or a null object.
+    #[inline]
+    pub fn is_nil(&self) -> bool {
+        matches!(
+            self,
+            VariantValue::Nil | VariantValue::Object(ObjectValue::Null)
+        )
+    }
+
+    /// Returns the value associated with `key`, if this is a `Dictionary` that contains it.
+    #[inline]
+    pub fn get(&self, key: &VariantValue) -> Option<&VariantValue> {
+        match self {
+            VariantValue::Dictionary(entries) => {
+                entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+            }
+            _ => None,
+        }
+    }
+
+    fn from_variant_with_depth(variant: &Variant, depth: usize) -> Result<Self, FromVariantError> {
+        if depth > MAX_DEPTH {
+            return Err(FromVariantError::custom(
+                "maximum nesting depth exceeded, the value might contain a cycle",
+            ));
+        }
+
+        let value = match variant.dispatch() {
+            VariantDispatch::Nil => VariantValue::Nil,
+            VariantDispatch::Bool(v) => VariantValue::Bool(v),
+            VariantDispatch::I64(v) => VariantValue::I64(v),
+            VariantDispatch::F64(v) => VariantValue::F64(v),
+            VariantDispatch::GodotString(v) => VariantValue::GodotString(v.to_string()),
+            VariantDispatch::Vector2(v) => VariantValue::Vector2(v),
+            VariantDispatch::Rect2(v) => VariantValue::Rect2(v),
+            VariantDispatch::Vector3(v) => VariantValue::Vector3(v),
+            VariantDispatch::Transform2D(v) => VariantValue::Transform2D(v),
+            VariantDispatch::Plane(v) => VariantValue::Plane(v),
+            VariantDispatch::Quat(v) => VariantValue::Quat(v),
+            VariantDispatch::Aabb(v) => VariantValue::Aabb(v),
+            VariantDispatch::Basis(v) => VariantValue::Basis(v),
+            VariantDispatch::Transform(v) => VariantValue::Transform(v),
+            VariantDispatch::Color(v) => VariantValue::Color(v),
+            VariantDispatch::NodePath(v) => VariantValue::NodePath(v.to_string()),
+            VariantDispatch::Rid(_) => VariantValue::Rid,
+            VariantDispatch::Object(v) => VariantValue::Object(ObjectValue::from_object(&v)),
+            VariantDispatch::Dictionary(v) => VariantValue::Dictionary(
+                v.iter()
+                    .map(|(key, value)| {
+                        Ok((
+                            Self::from_variant_with_depth(&key, depth + 1)?,
+                            Self::from_variant_with_depth(&value, depth + 1)?,
+                        ))
+                    })
+                    .collect::<Result<_, FromVariantError>>()?,
+            ),
+            VariantDispatch::VariantArray(v) => VariantValue::VariantArray(
+                v.iter()
+                    .enumerate()
+                    .map(|(index, item)| {
+                        Self::from_variant_with_depth(&item, depth + 1).map_err(|err| {
+                            FromVariantError::InvalidItem {
+                                index,
+                                error: Box::new(err),
+                            }
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            VariantDispatch::ByteArray(v) => VariantValue::ByteArray(v.to_vec()),
+            VariantDispatch::Int32Array(v) => VariantValue::Int32Array(v.to_vec()),
+            VariantDispatch::Float32Array(v) => VariantValue::Float32Array(v.to_vec()),
+            VariantDispatch::StringArray(v) => {
+                VariantValue::StringArray(v.read().iter().map(GodotString::to_string).collect())
+            }
+            VariantDispatch::Vector2Array(v) => VariantValue::Vector2Array(v.to_vec()),
+            VariantDispatch::Vector3Array(v) => VariantValue::Vector3Array(v.to_vec()),
+            VariantDispatch::ColorArray(v) => VariantValue::ColorArray(v.to_vec()),
+        };
+
+        Ok(value)
+    }
+}
+
+impl ObjectValue {
+    fn from_object(variant: &Variant) -> Self {
+        unsafe {
+            let ptr = (get_api().godot_variant_as_object)(variant.sys());
+            match ptr::NonNull::new(ptr) {
+                Some(ptr) => {
+                    let obj = object::RawObject::<ManuallyManagedClassPlaceholder>::from_sys_ref_unchecked(ptr);
+                    ObjectValue::Instance {
+                        class: obj.class_name(),
+                        id: obj.instance_id() as u64,
+                    }
+                }
+                None => ObjectValue::Null,
+            }
+        }
+    }
+}
+
+/// Objects are converted to their class names and instance IDs, or `ObjectValue::Null`. Collections that are
+/// nested deeper than Godot allows, e.g. arrays containing themselves, result in an error.
+impl FromVariant for VariantValue {
+    #[inline]
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Self::from_variant_with_depth(variant, 0)
+    }
+}
+
+/// Objects cannot be recreated from an `ObjectValue`, and are converted to `Nil`. `Rid`s are
+/// converted to empty, invalid `Rid`s.
+impl ToVariant for VariantValue {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        match self {
+            VariantValue::Nil | VariantValue::Object(_) => Variant::nil(),
+            VariantValue::Bool(v) => v.to_variant(),
+            VariantValue::I64(v) => v.to_variant(),
+            VariantValue::F64(v) => v.to_variant(),
+            VariantValue::GodotString(v) => GodotString::from_str(v).to_variant(),
+            VariantValue::Vector2(v) => v.to_variant(),
+            VariantValue::Rect2(v) => v.to_variant(),
+            VariantValue::Vector3(v) => v.to_variant(),
+            VariantValue::Transform2D(v) => v.to_variant(),
+            VariantValue::Plane(v) => v.to_variant(),
+            VariantValue::Quat(v) => v.to_variant(),
+            VariantValue::Aabb(v) => v.to_variant(),
+            VariantValue::Basis(v) => v.to_variant(),
+            VariantValue::Transform(v) => v.to_variant(),
+            VariantValue::Color(v) => v.to_variant(),
+            VariantValue::NodePath(v) => NodePath::from_str(v).to_variant(),
+            VariantValue::Rid => Rid::new().to_variant(),
+            VariantValue::Dictionary(entries) => {
+                let dict = Dictionary::new();
+                for (key, value) in entries {
+                    dict.insert(key.to_variant(), value.to_variant());
+                }
+                dict.owned_to_variant()
+            }
+            VariantValue::VariantArray(items) => {
+                let array = VariantArray::new();
+                for item in items {
+                    array.push(item.to_variant());
+                }
+                array.owned_to_variant()
+            }
+            VariantValue::ByteArray(v) => PoolArray::from_slice(v).to_variant(),
+            VariantValue::Int32Array(v) => PoolArray::from_slice(v).to_variant(),
+            VariantValue::Float32Array(v) => PoolArray::from_slice(v).to_variant(),
+            VariantValue::StringArray(v) => v
+                .iter()
+                .map(|s| GodotString::from_str(s))
+                .collect::<PoolArray<GodotString>>()
+                .to_variant(),
+            VariantValue::Vector2Array(v) => PoolArray::from_slice(v).to_variant(),
+            VariantValue::Vector3Array(v) => PoolArray::from_slice(v).to_variant(),
+            VariantValue::ColorArray(v) => PoolArray::from_slice(v).to_variant(),
+        }
+    }
+}
+
+impl From<&Variant> for VariantValue {
+    /// Converts a `Variant` into a `VariantValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `Variant` is nested deeper than Godot allows. See [`FromVariant`] for a
+    /// fallible conversion.
+    #[inline]
+    fn from(variant: &Variant) -> Self {
+        VariantValue::from_variant(variant).expect("variant should not be nested too deeply")
+    }
+}
+
+macro_rules! impl_variant_value_from {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        $(
+            impl From<$ty> for VariantValue {
+                #[inline]
+                fn from(v: $ty) -> Self {
+                    VariantValue::$variant(v)
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_value_from!(
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    GodotString(String),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    Object(ObjectValue),
+    VariantArray(Vec<VariantValue>),
+    ByteArray(Vec<u8>),
+    Int32Array(Vec<i32>),
+    Float32Array(Vec<f32>),
+    StringArray(Vec<String>),
+    Vector2Array(Vec<Vector2>),
+    Vector3Array(Vec<Vector3>),
+    ColorArray(Vec<Color>),
+);
+
+impl From<&str> for VariantValue {
+    #[inline]
+    fn from(v: &str) -> Self {
+        VariantValue::GodotString(v.to_owned())
+    }
+}
+
+impl From<i32> for VariantValue {
+    #[inline]
+    fn from(v: i32) -> Self {
+        VariantValue::I64(v.into())
+    }
+}
+
+impl From<f32> for VariantValue {
+    #[inline]
+    fn from(v: f32) -> Self {
+        VariantValue::F64(v.into())
+    }
+}
+
+godot_test!(
+    test_variant_value {
+        let dict = Dictionary::new();
+        dict.insert("name", "Godot");
+        dict.insert("position", Vector2::new(1.0, 2.0));
+        dict.insert("tags", PoolArray::from_vec(vec![GodotString::from("a")]));
+        let variant = dict.owned_to_variant();
+
+        let value = VariantValue::from_variant(&variant).expect("should convert");
+        assert_eq!(
+            VariantValue::Dictionary(vec![
+                ("name".into(), "Godot".into()),
+                ("position".into(), Vector2::new(1.0, 2.0).into()),
+                ("tags".into(), VariantValue::StringArray(vec!["a".into()])),
+            ]),
+            value,
+        );
+        // Dictionaries are compared by reference, so compare the values instead
+        assert_eq!(Ok(value.clone()), VariantValue::from_variant(&value.to_variant()));
+
+        let bytes = binary::encode_variant(&variant).expect("should encode");
+        assert_eq!(binary::encode_value(&value), bytes);
+        let (decoded, len) = binary::decode_variant(&bytes).expect("should decode");
+        assert_eq!(bytes.len(), len);
+        assert_eq!(Ok(value), VariantValue::from_variant(&decoded));
+
+        let array = VariantArray::new_shared();
+        let alias = unsafe { array.new_ref().assume_unique() };
+        alias.push(&array);
+        assert!(VariantValue::from_variant(&array.to_variant()).is_err());
+        alias.clear();
+    }
+);
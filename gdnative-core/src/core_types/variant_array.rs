@@ -1,13 +1,16 @@
+use std::cmp::Ordering;
 use std::iter::{Extend, FromIterator};
 use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
 
 use crate::private::get_api;
 use crate::sys;
 
+use crate::core_types::GodotString;
 use crate::core_types::OwnedToVariant;
 use crate::core_types::ToVariant;
 use crate::core_types::Variant;
-use crate::object::NewRef;
+use crate::object::{AsArg, AsVariant, NewRef};
 
 use crate::object::ownership::*;
 
@@ -30,6 +33,7 @@ use std::fmt;
 /// safe.
 ///
 /// [thread-safety]: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html
+#[repr(transparent)]
 pub struct VariantArray<Own: Ownership = Shared> {
     sys: sys::godot_array,
 
@@ -147,6 +151,153 @@ impl<Own: Ownership> VariantArray<Own> {
         unsafe { (get_api().godot_array_sort)(self.sys_mut()) }
     }
 
+    /// Sorts the array using a custom method `func` on `obj`, as in GDScript.
+    ///
+    /// The method receives two elements as arguments, and must return `true` if the first
+    /// one should be placed before the second. For sorting with a Rust closure, see
+    /// [`sort_by`][Self::sort_by].
+    #[inline]
+    pub fn sort_custom<O: AsVariant>(&self, obj: O, func: impl Into<GodotString>) {
+        let func = func.into();
+        unsafe { (get_api().godot_array_sort_custom)(self.sys_mut(), obj.as_arg_ptr(), func.sys()) }
+    }
+
+    /// Sorts the array with a comparator function.
+    ///
+    /// Elements are copied into a temporary `Vec` for sorting, and written back afterwards.
+    /// The sort is stable.
+    #[inline]
+    pub fn sort_by<F>(&self, mut compare: F)
+    where
+        F: FnMut(&Variant, &Variant) -> Ordering,
+    {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_by(|a, b| compare(a, b));
+        self.write_back(items);
+    }
+
+    /// Sorts the array with a key extraction function.
+    ///
+    /// Elements are copied into a temporary `Vec` for sorting, and written back afterwards.
+    /// The sort is stable.
+    #[inline]
+    pub fn sort_by_key<K, F>(&self, mut f: F)
+    where
+        F: FnMut(&Variant) -> K,
+        K: Ord,
+    {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_by_key(|item| f(item));
+        self.write_back(items);
+    }
+
+    /// Shuffles the array such that the items will have a random order.
+    ///
+    /// This uses Godot's global random number generator.
+    #[inline]
+    pub fn shuffle(&self) {
+        unsafe { (get_api().godot_array_shuffle)(self.sys_mut()) }
+    }
+
+    /// Finds the index of an existing value (or the insertion index that maintains sorting
+    /// order, if the value is not yet present in the array) using binary search.
+    /// Optionally, a `before` specifier can be passed. If `false`, the returned index comes
+    /// after all existing entries of the value in the array.
+    ///
+    /// Calling `bsearch` on an unsorted array results in unexpected behavior.
+    #[inline]
+    pub fn bsearch<T: ToVariant>(&self, value: T, before: bool) -> i32 {
+        unsafe { (get_api().godot_array_bsearch)(self.sys_mut(), value.to_variant().sys(), before) }
+    }
+
+    /// Finds the index of an existing value (or the insertion index that maintains sorting
+    /// order, if the value is not yet present in the array) using binary search and a custom
+    /// comparison method `func` on `obj`. See [`sort_custom`][Self::sort_custom] for the
+    /// requirements on the method.
+    ///
+    /// Calling `bsearch_custom` on an unsorted array results in unexpected behavior.
+    #[inline]
+    pub fn bsearch_custom<T: ToVariant, O: AsVariant>(
+        &self,
+        value: T,
+        obj: O,
+        func: impl Into<GodotString>,
+        before: bool,
+    ) -> i32 {
+        let func = func.into();
+        unsafe {
+            (get_api().godot_array_bsearch_custom)(
+                self.sys_mut(),
+                value.to_variant().sys(),
+                obj.as_arg_ptr(),
+                func.sys(),
+                before,
+            )
+        }
+    }
+
+    /// Returns the maximum value contained in the array if all elements are of comparable
+    /// types. If the elements can't be compared or the array is empty, `Nil` is returned.
+    #[inline]
+    pub fn max(&self) -> Variant {
+        unsafe { Variant((get_api().godot_array_max)(self.sys())) }
+    }
+
+    /// Returns the minimum value contained in the array if all elements are of comparable
+    /// types. If the elements can't be compared or the array is empty, `Nil` is returned.
+    #[inline]
+    pub fn min(&self) -> Variant {
+        unsafe { Variant((get_api().godot_array_min)(self.sys())) }
+    }
+
+    /// Duplicates the subset described in the function and returns it in a new array,
+    /// as in GDScript. Unlike Rust ranges, **both `begin` and `end` are inclusive**.
+    /// `step` can be negative to traverse the array backwards, but must not be zero.
+    /// If `deep` is true, elements are copied recursively.
+    ///
+    /// For slicing with Rust range syntax, see [`slice_range`][Self::slice_range].
+    #[inline]
+    pub fn slice(&self, begin: i32, end: i32, step: i32, deep: bool) -> VariantArray<Unique> {
+        unsafe {
+            let sys = (get_api().godot_array_slice)(self.sys(), begin, end, step, deep);
+            VariantArray::<Unique>::from_sys(sys)
+        }
+    }
+
+    /// Returns a shallow copy of the elements in `range` as a new array.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds, or its start is greater than its end.
+    #[inline]
+    pub fn slice_range<R: RangeBounds<i32>>(&self, range: R) -> VariantArray<Unique> {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+
+        assert!(
+            0 <= start && start <= end && end <= len,
+            "Range {}..{} out of bounds (len {})",
+            start,
+            end,
+            len
+        );
+
+        if start == end {
+            VariantArray::new()
+        } else {
+            self.slice(start, end - 1, 1, false)
+        }
+    }
+
     /// Create a copy of the array.
     ///
     /// This creates a new array and is **not** a cheap reference count
@@ -208,6 +359,12 @@ impl<Own: Ownership> VariantArray<Own> {
         VariantArray::from_sys(sys)
     }
 
+    fn write_back(&self, items: Vec<Variant>) {
+        for (idx, item) in (0..).zip(items) {
+            self.set(idx, item);
+        }
+    }
+
     fn check_bounds(&self, idx: i32) {
         assert!(
             idx >= 0 && idx < self.len(),
@@ -279,6 +436,38 @@ impl<Own: LocalThreadOwnership> VariantArray<Own> {
     pub fn insert<T: OwnedToVariant>(&self, at: i32, val: T) {
         unsafe { (get_api().godot_array_insert)(self.sys_mut(), at, val.owned_to_variant().sys()) }
     }
+
+    /// Appends all elements of `iter` at the end of the array.
+    #[inline]
+    pub fn extend<T, I>(&self, iter: I)
+    where
+        T: OwnedToVariant,
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+
+    /// Retains only the elements specified by the predicate, preserving their order.
+    #[inline]
+    pub fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&Variant) -> bool,
+    {
+        let len = self.len();
+        let mut retained = 0;
+        for idx in 0..len {
+            let item = self.get(idx);
+            if f(&item) {
+                if retained != idx {
+                    self.set(retained, item);
+                }
+                retained += 1;
+            }
+        }
+        self.resize(retained);
+    }
 }
 
 /// Operations allowed on non-unique arrays.
@@ -497,7 +686,7 @@ impl IntoIterator for VariantArray<Unique> {
 impl<T: ToVariant> FromIterator<T> for VariantArray<Unique> {
     #[inline]
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut arr = Self::new();
+        let arr = Self::new();
         arr.extend(iter);
         arr
     }
@@ -506,9 +695,7 @@ impl<T: ToVariant> FromIterator<T> for VariantArray<Unique> {
 impl<T: ToVariant, Own: LocalThreadOwnership> Extend<T> for VariantArray<Own> {
     #[inline]
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        for elem in iter {
-            self.push(&elem.to_variant());
-        }
+        VariantArray::extend(self, iter)
     }
 }
 
@@ -644,3 +831,45 @@ godot_test!(
         assert!(!array_clone.is_empty());
     }
 );
+
+godot_test!(
+    test_array_ext {
+        let array = (1..=5).collect::<VariantArray<Unique>>(); // [1, 2, 3, 4, 5]
+
+        let to_i64s = |array: &VariantArray<Unique>| {
+            array.iter().map(|v| v.try_to::<i64>().unwrap()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(array.max(), Variant::new(5));
+        assert_eq!(array.min(), Variant::new(1));
+        assert!(VariantArray::new().max().is_nil());
+
+        assert_eq!(to_i64s(&array.slice(1, 3, 1, false)), vec![2, 3, 4]);
+        assert_eq!(to_i64s(&array.slice_range(1..3)), vec![2, 3]);
+        assert_eq!(to_i64s(&array.slice_range(..=1)), vec![1, 2]);
+        assert_eq!(to_i64s(&array.slice_range(3..)), vec![4, 5]);
+        assert!(array.slice_range(5..).is_empty());
+        assert!(std::panic::catch_unwind(|| array.slice_range(4..6)).is_err());
+
+        assert_eq!(array.bsearch(3, true), 2);
+        assert_eq!(array.bsearch(3, false), 3);
+        assert_eq!(array.bsearch(0, true), 0);
+
+        array.sort_by(|a, b| b.try_to::<i64>().unwrap().cmp(&a.try_to::<i64>().unwrap()));
+        assert_eq!(to_i64s(&array), vec![5, 4, 3, 2, 1]);
+
+        array.sort_by_key(|v| v.try_to::<i64>().unwrap() % 3);
+        assert_eq!(to_i64s(&array), vec![3, 4, 1, 5, 2]);
+
+        array.retain(|v| v.try_to::<i64>().unwrap() % 2 == 1);
+        assert_eq!(to_i64s(&array), vec![3, 1, 5]);
+
+        array.extend(vec![7, 9]);
+        assert_eq!(to_i64s(&array), vec![3, 1, 5, 7, 9]);
+
+        array.shuffle();
+        assert_eq!(array.len(), 5);
+        array.sort();
+        assert_eq!(to_i64s(&array), vec![1, 3, 5, 7, 9]);
+    }
+);
@@ -6,6 +6,8 @@
 
 use std::marker::PhantomData;
 
+use indexmap::{IndexMap, IndexSet};
+
 use accessor::{Getter, RawGetter, RawSetter, Setter};
 use invalid_accessor::{InvalidGetter, InvalidSetter};
 
@@ -49,6 +51,10 @@ pub mod hint;
 ///   expensive computationally than what the user would expect.
 ///
 /// As such, we do not allow these types to be exported as properties directly as a precaution.
+/// The exceptions are `IndexMap` and `IndexSet` from the `indexmap` crate: their conversions
+/// preserve order exactly, which makes them useful for configuration edited in the inspector.
+/// The caveats above still apply to them.
+///
 /// If you wish to export collections to GDScript, consider the following options:
 ///
 /// - Exporting a [`Variant`] collection such as [`VariantArray`] or [`Dictionary`] explicitly,
@@ -645,4 +651,50 @@ mod impl_export {
             hint.unwrap_or_default().export_info()
         }
     }
+
+    impl<T> Export for TypedArray<T, Shared>
+    where
+        T: Export,
+    {
+        type Hint = T::Hint;
+
+        #[inline]
+        fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+            hint::ArrayHint::with_maybe_element_hint::<T>(hint).export_info()
+        }
+    }
+
+    impl<K, V, S> Export for IndexMap<K, V, S>
+    where
+        K: ToVariant + ToVariantEq,
+        V: ToVariant,
+    {
+        type Hint = NoHint;
+
+        #[inline]
+        fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
+            ExportInfo::new(VariantType::Dictionary)
+        }
+    }
+
+    impl<T, S> Export for IndexSet<T, S>
+    where
+        T: Export,
+    {
+        type Hint = T::Hint;
+
+        #[inline]
+        fn export_info(hint: Option<Self::Hint>) -> ExportInfo {
+            hint::ArrayHint::with_maybe_element_hint::<T>(hint).export_info()
+        }
+    }
+
+    impl<K, V> Export for TypedDictionary<K, V, Shared> {
+        type Hint = NoHint;
+
+        #[inline]
+        fn export_info(_hint: Option<Self::Hint>) -> ExportInfo {
+            ExportInfo::new(VariantType::Dictionary)
+        }
+    }
 }
@@ -1,5 +1,5 @@
 use crate::core_types::{GodotString, Variant, VariantType};
-use crate::export::{ClassBuilder, ExportInfo, NativeClass, PropertyUsage};
+use crate::export::{ClassBuilder, Export, ExportInfo, NativeClass, PropertyUsage};
 
 /// Class to construct a signal. Make sure to call [`Self::done()`] in the end.
 ///
@@ -37,6 +37,20 @@ impl<'a, C: NativeClass> SignalBuilder<'a, C> {
         })
     }
 
+    /// Add a parameter for the signal with a name, using the type information of a Rust type.
+    ///
+    /// This is what `#[signal]` declarations in `#[methods]` blocks use. Like with
+    /// [`Self::with_param`], the type is only used for UI purposes.
+    #[inline]
+    pub fn with_param_typed<T: Export>(self, parameter_name: &str) -> Self {
+        self.with_param_custom(SignalParam {
+            name: parameter_name.into(),
+            default: Variant::nil(),
+            export_info: T::export_info(None),
+            usage: PropertyUsage::DEFAULT,
+        })
+    }
+
     /// Add a parameter for the signal with a name and default value.
     ///
     /// The type is inferred from the default value.
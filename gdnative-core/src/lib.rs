@@ -43,6 +43,9 @@ extern crate approx;
 #[doc(inline)]
 pub use gdnative_derive::godot_wrap_method;
 
+#[doc(inline)]
+pub use gdnative_derive::node_path;
+
 /// Derive macros and macro attributes.
 #[doc(inline)]
 pub use gdnative_derive as derive;
@@ -113,6 +113,34 @@ macro_rules! godot_error {
     });
 }
 
+/// Returns a `&'static StringName` for a string literal, which is created only once per call
+/// site, the first time the expression is evaluated.
+///
+/// This avoids converting the name from UTF-8 each time a method or signal name is passed to
+/// the engine. `&StringName` can be passed to all APIs that accept names as
+/// `impl Into<GodotString>`, like `Object::call`, `Object::emit_signal`, `Object::connect`
+/// or `Variant::call`. Since GDNative only takes these names as strings, every call still
+/// converts the `StringName` to a `GodotString` through the engine.
+///
+/// # Example
+///
+/// ```no_run
+/// use gdnative::prelude::*;
+/// use gdnative::api::Area2D;
+///
+/// fn notify(area: &Area2D, body: Ref<Node>) {
+///     area.emit_signal(string_name!("body_entered"), &[body.to_variant()]);
+/// }
+/// ```
+#[macro_export]
+macro_rules! string_name {
+    ($name:literal) => {{
+        static NAME: $crate::private::CachedStringName =
+            $crate::private::CachedStringName::new($name);
+        NAME.get()
+    }};
+}
+
 macro_rules! impl_basic_trait_as_sys {
     (
         Drop for $Type:ty as $GdType:ident : $gd_method:ident
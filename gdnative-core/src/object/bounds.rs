@@ -37,6 +37,11 @@ pub trait MemorySpec: Sized {
     where
         Self: Memory;
 
+    #[doc(hidden)]
+    unsafe fn impl_is_alive<T: GodotObject<Memory = Self>>(this: &Ref<T, Shared>) -> bool
+    where
+        Self: Memory;
+
     #[doc(hidden)]
     unsafe fn maybe_add_ref<T: GodotObject<Memory = Self>>(raw: &RawObject<T>)
     where
@@ -74,6 +79,9 @@ impl MemorySpec for ManuallyManaged {
     unsafe fn impl_assume_safe<'a, T: GodotObject<Memory = Self>>(
         this: &Ref<T, Shared>,
     ) -> TRef<'a, T, Shared> {
+        #[cfg(all(feature = "debug-checks", debug_assertions))]
+        debug_checks::check_access(this);
+
         debug_assert!(
             this.is_instance_sane(),
             "assume_safe called on an invalid pointer"
@@ -92,6 +100,17 @@ impl MemorySpec for ManuallyManaged {
         this.cast_access()
     }
 
+    #[inline]
+    unsafe fn impl_is_alive<T: GodotObject<Memory = Self>>(this: &Ref<T, Shared>) -> bool {
+        let api = get_api();
+        let ptr = this.as_ptr();
+
+        // Instance IDs are never reused, so this also catches new objects that happen to be
+        // allocated at the address of a freed one.
+        (api.godot_is_instance_valid)(ptr)
+            && (api.godot_instance_from_id)(this.ptr.instance_id() as sys::godot_int) == ptr
+    }
+
     #[inline]
     unsafe fn maybe_add_ref<T: GodotObject<Memory = Self>>(_raw: &RawObject<T>) {}
     #[inline]
@@ -133,6 +152,12 @@ impl MemorySpec for RefCounted {
         this.cast_access()
     }
 
+    #[inline(always)]
+    unsafe fn impl_is_alive<T: GodotObject<Memory = Self>>(_this: &Ref<T, Shared>) -> bool {
+        // `Ref`s to reference-counted objects keep them alive.
+        true
+    }
+
     #[inline]
     unsafe fn maybe_add_ref<T: GodotObject<Memory = Self>>(raw: &RawObject<T>) {
         raw.add_ref();
@@ -159,12 +184,27 @@ pub trait PtrWrapper {
 }
 
 /// Simply releases the held object without deallocating it.
+///
+/// The instance ID of the object is stored alongside the pointer, so that it's possible to
+/// check whether the object is still alive later. This makes `Ref`s to manually-managed objects
+/// twice the size of a pointer, and creating one from a pointer calls `get_instance_id` once,
+/// whether or not the `debug-checks` feature is enabled.
 #[derive(Copy, Clone)]
-pub struct Forget(NonNull<sys::godot_object>);
+pub struct Forget(NonNull<sys::godot_object>, i64);
+impl Forget {
+    /// Returns the instance ID of the object at the time the reference was created.
+    #[inline]
+    pub(crate) fn instance_id(&self) -> i64 {
+        self.1
+    }
+}
 impl PtrWrapper for Forget {
     #[inline]
     fn new(ptr: NonNull<sys::godot_object>) -> Self {
-        Forget(ptr)
+        let instance_id = unsafe {
+            RawObject::<ManuallyManagedClassPlaceholder>::from_sys_ref_unchecked(ptr).instance_id()
+        };
+        Forget(ptr, instance_id)
     }
 
     #[inline]
@@ -196,6 +236,60 @@ impl Drop for UnRef {
     }
 }
 
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+// Runtime checks for assume_safe
+
+#[cfg(all(feature = "debug-checks", debug_assertions))]
+mod debug_checks {
+    use crate::object::memory::ManuallyManaged;
+    use crate::object::ownership::Shared;
+    use crate::object::{GodotObject, Ref};
+    use crate::private::{get_api, is_main_thread, NodeMethodTable};
+
+    use super::MemorySpec;
+
+    /// Panics if the object behind `this` was freed, or if it's a node in the scene tree and
+    /// the current thread is not the main thread.
+    pub(super) unsafe fn check_access<T: GodotObject<Memory = ManuallyManaged>>(
+        this: &Ref<T, Shared>,
+    ) {
+        if !ManuallyManaged::impl_is_alive(this) {
+            panic!(
+                "assume_safe called on a freed object: {} with instance ID {}",
+                T::class_name(),
+                this.ptr.instance_id(),
+            );
+        }
+
+        if !is_main_thread() {
+            let raw = this.as_raw_unchecked();
+            if raw.is_class_by_name("Node") && is_inside_tree(this.as_ptr()) {
+                panic!(
+                    "assume_safe called on a {} with instance ID {} from a thread other than \
+                    the main thread, while it's inside the scene tree. See the thread-safety \
+                    guidelines: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html",
+                    raw.class_name(),
+                    this.ptr.instance_id(),
+                );
+            }
+        }
+    }
+
+    unsafe fn is_inside_tree(node: *mut crate::sys::godot_object) -> bool {
+        let api = get_api();
+        let method = NodeMethodTable::get(api).is_inside_tree;
+        let mut argument_buffer = [std::ptr::null() as *const libc::c_void; 0];
+        let mut inside_tree = false;
+        (api.godot_method_bind_ptrcall)(
+            method,
+            node,
+            argument_buffer.as_mut_ptr() as *mut _,
+            &mut inside_tree as *mut bool as *mut _,
+        );
+        inside_tree
+    }
+}
+
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 // assume_safe and lifetime bounds
 
@@ -335,6 +335,24 @@ impl<T: NativeClass> Instance<T, Shared> {
             script: self.script.clone(),
         }
     }
+
+    /// Returns a `TInstance` if the base object is still alive, and `None` otherwise. See
+    /// `Ref::try_access`.
+    ///
+    /// # Safety
+    ///
+    /// It's safe to call `try_access` only if the constraints of `Ref::try_access` are
+    /// satisfied for the base object.
+    #[inline]
+    pub unsafe fn try_access<'a, 'r>(&'r self) -> Option<TInstance<'a, T, Shared>>
+    where
+        AssumeSafeLifetime<'a, 'r>: LifetimeConstraint<<T::Base as GodotObject>::Memory>,
+    {
+        Some(TInstance {
+            owner: self.owner.try_access()?,
+            script: self.script.clone(),
+        })
+    }
 }
 
 impl<T: NativeClass> Instance<T, Shared>
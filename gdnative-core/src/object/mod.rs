@@ -291,6 +291,15 @@ pub trait QueueFree: GodotObject {
 /// It's unsound to pass `ThreadLocal` references to the engine because there is no guarantee
 /// that the reference will stay on the same thread.
 ///
+/// # Size and cost
+///
+/// References to manually-managed objects store the instance ID of the object next to the
+/// pointer, so `try_access` and the `debug-checks` feature can tell whether the object was
+/// freed. This makes them 16 bytes in size on 64-bit targets instead of 8, and creating one
+/// from a raw pointer, e.g. when an object is returned from an API method, costs an additional
+/// call to `get_instance_id`. Casting and converting existing references don't repeat the call.
+/// References to reference-counted objects are unaffected.
+///
 /// # Conditional trait implementations
 ///
 /// Many trait implementations for `Ref` are conditional, dependent on the type parameters.
@@ -481,9 +490,13 @@ where
     where
         U: GodotObject<Memory = T::Memory>,
     {
-        let ret = Ref::move_from_sys(self.ptr.as_non_null());
-        std::mem::forget(self);
-        ret
+        // Copies `ptr` instead of going through `move_from_sys`, which would call
+        // `get_instance_id` again for manually-managed objects.
+        let this = std::mem::ManuallyDrop::new(self);
+        Ref {
+            ptr: std::ptr::read(&this.ptr),
+            _marker: PhantomData,
+        }
     }
 
     /// Performs a downcast to a `NativeClass` instance, keeping the reference count.
@@ -546,6 +559,10 @@ impl<T: GodotObject> Ref<T, Shared> {
     ///
     /// Failure to satisfy either of the conditions will lead to undefined behavior.
     ///
+    /// With the `debug-checks` feature, debug builds panic instead if a manually-managed
+    /// object was already freed, or if a node inside the scene tree is accessed from a thread
+    /// other than the main thread. For a non-panicking liveness check, see `try_access`.
+    ///
     /// [thread-safety]: https://docs.godotengine.org/en/stable/tutorials/threads/thread_safe_apis.html
     #[inline(always)]
     pub unsafe fn assume_safe<'a, 'r>(&'r self) -> TRef<'a, T, Shared>
@@ -555,6 +572,31 @@ impl<T: GodotObject> Ref<T, Shared> {
         T::Memory::impl_assume_safe(self)
     }
 
+    /// Returns a reference that can be used to call API methods, if the underlying object is
+    /// still alive. Returns `None` otherwise.
+    ///
+    /// For manually-managed types, liveness is checked with the instance ID recorded when
+    /// `self` was created, and `godot_is_instance_valid`. Unlike `is_instance_sane`, this also
+    /// detects freed objects when a new object happens to occupy the same address. References
+    /// to reference-counted objects are always alive.
+    ///
+    /// # Safety
+    ///
+    /// The check only covers the object being alive at the time of the call. The remaining
+    /// constraints of `assume_safe` still apply: the object must not be freed during `'a`,
+    /// and the current thread must have exclusive access to it.
+    #[inline]
+    pub unsafe fn try_access<'a, 'r>(&'r self) -> Option<TRef<'a, T, Shared>>
+    where
+        AssumeSafeLifetime<'a, 'r>: LifetimeConstraint<T::Memory>,
+    {
+        if T::Memory::impl_is_alive(self) {
+            Some(self.assume_safe())
+        } else {
+            None
+        }
+    }
+
     /// Assume that `self` is the unique reference to the underlying object.
     ///
     /// This is guaranteed to be a no-op at runtime if `debug_assertions` is disabled. Runtime
@@ -820,9 +862,13 @@ impl<T: GodotObject, Own: Ownership> Ref<T, Own> {
     ///
     /// The cast must be valid.
     unsafe fn cast_access<TargetOws: Ownership>(self) -> Ref<T, TargetOws> {
-        let ret = Ref::move_from_sys(self.ptr.as_non_null());
-        std::mem::forget(self);
-        ret
+        // Copies `ptr` instead of going through `move_from_sys`, which would call
+        // `get_instance_id` again for manually-managed objects.
+        let this = std::mem::ManuallyDrop::new(self);
+        Ref {
+            ptr: std::ptr::read(&this.ptr),
+            _marker: PhantomData,
+        }
     }
 
     /// Assume that the reference is safe in an `unsafe` context even if it can be used safely.
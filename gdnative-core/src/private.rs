@@ -32,6 +32,10 @@ pub unsafe fn bind_api(options: *mut sys::godot_gdnative_init_options) -> bool {
     GODOT_API = Some(api);
     GDNATIVE_LIBRARY_SYS = Some((*options).gd_native_library);
 
+    // `gdnative_init` is called from the main thread.
+    #[cfg(all(feature = "debug-checks", debug_assertions))]
+    let _ = MAIN_THREAD.set(std::thread::current().id());
+
     ObjectMethodTable::get(get_api());
     ReferenceMethodTable::get(get_api());
     NativeScriptMethodTable::get(get_api());
@@ -79,6 +83,21 @@ unsafe fn check_api_compatibility(
     Ok(())
 }
 
+#[cfg(all(feature = "debug-checks", debug_assertions))]
+static MAIN_THREAD: once_cell::sync::OnceCell<std::thread::ThreadId> =
+    once_cell::sync::OnceCell::new();
+
+/// Returns `true` if the current thread is the one the library was initialized from, or if
+/// that is unknown.
+#[cfg(all(feature = "debug-checks", debug_assertions))]
+#[inline]
+pub(crate) fn is_main_thread() -> bool {
+    match MAIN_THREAD.get() {
+        Some(&id) => id == std::thread::current().id(),
+        None => true,
+    }
+}
+
 /// Returns a reference to the current API struct.
 ///
 /// This function is intended to be part of the internal API. It should only be called after
@@ -233,6 +252,32 @@ pub mod inventory {
     }
 }
 
+/// Lazily interned `StringName`, used by the `string_name!` macro.
+pub struct CachedStringName {
+    name: &'static str,
+    cell: once_cell::sync::OnceCell<crate::core_types::StringName>,
+}
+
+// SAFETY: `StringName`s are immutable, and reference-counted atomically by the engine, so
+// shared references can be used from any thread.
+unsafe impl Sync for CachedStringName {}
+
+impl CachedStringName {
+    #[inline]
+    pub const fn new(name: &'static str) -> Self {
+        CachedStringName {
+            name,
+            cell: once_cell::sync::OnceCell::new(),
+        }
+    }
+
+    #[inline]
+    pub fn get(&'static self) -> &'static crate::core_types::StringName {
+        self.cell
+            .get_or_init(|| crate::core_types::StringName::from_str(self.name))
+    }
+}
+
 pub mod godot_object {
     pub trait Sealed {}
 }
@@ -311,9 +356,15 @@ macro_rules! make_method_table {
 
 make_method_table!(struct ObjectMethodTable for Object {
     get_class,
+    get_instance_id,
     is_class,
 });
 
+#[cfg(all(feature = "debug-checks", debug_assertions))]
+make_method_table!(struct NodeMethodTable for Node {
+    is_inside_tree,
+});
+
 make_method_table!(struct ReferenceMethodTable for Reference {
     reference,
     unreference,
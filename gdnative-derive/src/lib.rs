@@ -13,7 +13,9 @@ use syn::{parse::Parser, AttributeArgs, DeriveInput, ItemFn, ItemImpl, ItemType}
 mod init;
 mod methods;
 mod native_script;
+mod node_path;
 mod profiled;
+mod scene_nodes;
 mod syntax;
 mod utils;
 mod varargs;
@@ -53,6 +55,48 @@ mod variant;
 /// - `#[methods(pub)]`<br>
 /// Mix-in types are private by default. The `pub` argument makes them public instead.
 ///
+/// ## Signals: `#[signal]`
+///
+/// Signals can be declared as functions without a body, marked with `#[signal]`. The
+/// parameters of the function become the parameters of the signal, and are registered with
+/// the type information of their [`Export`] implementations. Only doc comments are allowed
+/// as other attributes.
+///
+/// For a signal `hit`, the following methods are generated, with the visibility of the
+/// declaration:
+///
+/// - `emit_hit(&self, base, ...)` emits the signal with the declared parameters from the
+///   base object.
+/// - `connect_hit(&self, base, target, method, flags)` connects the signal to a method of
+///   another object, like `Object::connect`. Unlike `emit_hit`, it is not typed: the method is
+///   given by name, and only its existence is checked, returning `GodotError::MethodNotFound`
+///   if the target has no method with that name. Neither it nor the engine checks the parameter
+///   types of the method, which receives the parameters of the signal in the order of their
+///   declaration.
+///
+/// [`Export`]: https://docs.rs/gdnative/latest/gdnative/export/trait.Export.html
+///
+/// ```
+/// use gdnative::prelude::*;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// #[no_constructor]
+/// struct Player;
+///
+/// #[methods]
+/// impl Player {
+///     /// Emitted when the player takes damage.
+///     #[signal]
+///     fn hit(damage: i64, source: Ref<Node>);
+///
+///     #[method]
+///     fn take_damage(&self, #[base] base: &Node, damage: i64, source: Ref<Node>) {
+///         self.emit_hit(base, damage, source);
+///     }
+/// }
+/// ```
+///
 /// ## Example
 ///
 /// ### Universal
@@ -613,6 +657,75 @@ pub fn derive_from_varargs(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Generates typed accessors for the nodes of a scene, reading the `.tscn` file at compile time.
+///
+/// The derive adds an `unsafe` method to the type for each node in the scene except the root,
+/// taking the root node of an instance of the scene as `base`, and returning the node cast to
+/// its class. Methods are named after the nodes in snake case, or after their paths if the
+/// name is not unique in the scene.
+///
+/// Because the scene is read at compile time, renaming or removing a node in the editor turns
+/// uses of its accessor into compile errors, instead of failures at runtime. The same applies
+/// when the class of a node changes, or does not exist in the API. For instanced scenes, the
+/// class of the root node of the instanced scene is used, and the classes of editable children
+/// and of nodes in inherited scenes are looked up in the scenes they come from. It's a compile
+/// error if the class of a node can't be determined.
+///
+/// The accessors are `unsafe` for the same reasons as `assume_safe`: the returned `TRef`
+/// asserts that the node is alive and only used from the current thread during `'a`. `base`
+/// being valid doesn't guarantee that for its children, which can be freed or moved to other
+/// threads independently, so there is no safe variant.
+///
+/// ## Type attributes
+///
+/// ### `#[scene("res://path/to/Scene.tscn")]`
+///
+/// **Required.** The scene to read. Paths starting with `res://` are resolved relative to the
+/// Godot project, and other paths relative to the crate directory.
+///
+/// ### `#[scene("res://path/to/Scene.tscn", project = "path/to/project")]`
+///
+/// The directory of the Godot project, relative to the crate directory. If this is not given,
+/// the `GODOT_PROJECT_DIR` environment variable is used if set, and otherwise the closest
+/// directory containing a `project.godot` file, starting from the crate directory.
+///
+/// ## Example
+///
+/// ```ignore
+/// use gdnative::prelude::*;
+/// use gdnative::api::Camera2D;
+///
+/// #[derive(SceneNodes)]
+/// #[scene("res://Player.tscn")]
+/// struct PlayerNodes;
+///
+/// #[derive(NativeClass)]
+/// #[inherit(KinematicBody2D)]
+/// struct Player;
+///
+/// #[methods]
+/// impl Player {
+///     fn new(_base: &KinematicBody2D) -> Self {
+///         Player
+///     }
+///
+///     #[method]
+///     fn _ready(&self, #[base] base: TRef<KinematicBody2D>) {
+///         // The `Camera2D` node at `Body/Camera` in `Player.tscn`
+///         let camera: TRef<Camera2D> = unsafe { PlayerNodes.camera(base) };
+///         camera.make_current();
+///     }
+/// }
+/// ```
+#[proc_macro_derive(SceneNodes, attributes(scene))]
+pub fn derive_scene_nodes(input: TokenStream) -> TokenStream {
+    let derive_input = syn::parse_macro_input!(input as DeriveInput);
+    match scene_nodes::derive_scene_nodes(&derive_input) {
+        Ok(stream) => stream.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Declares the library-level GDNative callbacks. See [`gdnative::init::GDNativeCallbacks`].
 ///
 /// ## Arguments
@@ -653,6 +766,39 @@ pub fn godot_wrap_method(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Creates a `NodePath` from a string literal, checking its syntax at compile time.
+///
+/// Node names must not be empty or contain `.` or `"`, except for the special names `.` and
+/// `..`. Subnames, following the first `:`, must not be empty. The resulting path is
+/// otherwise the same as one created with `NodePath::from_str`.
+///
+/// ## Example
+///
+/// ```
+/// use gdnative::prelude::*;
+///
+/// fn player_position() -> NodePath {
+///     node_path!("../World/Player:position:x")
+/// }
+/// ```
+///
+/// Invalid paths are rejected:
+///
+/// ```compile_fail
+/// use gdnative::prelude::*;
+///
+/// fn sprite() -> NodePath {
+///     node_path!("World//Sprite.png")
+/// }
+/// ```
+#[proc_macro]
+pub fn node_path(input: TokenStream) -> TokenStream {
+    match node_path::expand_node_path(input.into()) {
+        Ok(stream) => stream.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Returns a standard header for derived implementations.
 ///
 /// Adds the `automatically_derived` attribute and prevents common lints from triggering
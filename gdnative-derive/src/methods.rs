@@ -11,12 +11,15 @@ use crate::syntax::rpc_mode::RpcMode;
 use crate::utils::find_non_concrete;
 
 use self::mixin_args::{MixinArgsBuilder, MixinKind};
+use self::signal::ExportSignal;
 
 mod mixin_args;
+mod signal;
 
 pub(crate) struct ClassMethodExport {
     pub(crate) class_ty: Box<Type>,
     pub(crate) methods: Vec<ExportMethod>,
+    pub(crate) signals: Vec<ExportSignal>,
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -406,6 +409,7 @@ pub(crate) fn derive_methods(
                 }
             )
         })
+        .chain(export.signals.iter().map(|signal| signal.register(&builder)))
         .collect::<Vec<_>>();
 
     match args.mixin {
@@ -470,6 +474,14 @@ pub(crate) fn derive_methods(
     }
 }
 
+fn is_signal(method: &syn::ImplItemMethod) -> bool {
+    method.attrs.iter().any(is_signal_attr)
+}
+
+fn is_signal_attr(attr: &syn::Attribute) -> bool {
+    matches!(attr.style, syn::AttrStyle::Outer) && attr.path.is_ident("signal")
+}
+
 /// Extract the data to export from the impl block.
 #[allow(clippy::single_match)]
 fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
@@ -485,6 +497,7 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
     let mut export = ClassMethodExport {
         class_ty: ast.self_ty,
         methods: vec![],
+        signals: vec![],
     };
 
     let mut methods_to_export: Vec<ExportMethod> = Vec::new();
@@ -493,6 +506,24 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
     // add all items back to the impl block again.
     for func in ast.items {
         let items = match func {
+            ImplItem::Method(mut method) if is_signal(&method) => {
+                let mut errors = vec![];
+
+                // signal declarations are replaced by their helper methods.
+                let idx = method.attrs.iter().position(is_signal_attr).unwrap();
+                let attr = method.attrs.remove(idx);
+                let helpers = ExportSignal::parse(&attr, method, &mut errors).map(|signal| {
+                    let helpers = signal.helpers();
+                    export.signals.push(signal);
+                    ImplItem::Verbatim(helpers)
+                });
+
+                errors
+                    .into_iter()
+                    .map(|err| ImplItem::Verbatim(err.to_compile_error()))
+                    .chain(helpers)
+                    .collect()
+            }
             ImplItem::Method(mut method) => {
                 let mut export_args = None;
                 let mut errors = vec![];
@@ -0,0 +1,236 @@
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Attribute, FnArg, ImplItemMethod, Item, Pat, ReturnType, Stmt, Type, Visibility};
+
+/// A signal declared with `#[signal] fn name(params...);` in a `#[methods]` block.
+pub(crate) struct ExportSignal {
+    ident: Ident,
+    vis: Visibility,
+    docs: Vec<Attribute>,
+    params: Vec<(Ident, Type)>,
+}
+
+impl ExportSignal {
+    /// Parses a signal declaration. `method` is expected to have the `#[signal]` attribute
+    /// already removed.
+    pub(crate) fn parse(
+        attr: &Attribute,
+        method: ImplItemMethod,
+        errors: &mut Vec<syn::Error>,
+    ) -> Option<Self> {
+        let error_count = errors.len();
+        let sig = method.sig;
+
+        if !attr.tokens.is_empty() {
+            errors.push(syn::Error::new(
+                attr.tokens.span(),
+                "#[signal] does not take any arguments",
+            ));
+        }
+
+        let mut docs = Vec::new();
+        for attr in method.attrs {
+            if attr.path.is_ident("doc") {
+                docs.push(attr);
+            } else {
+                errors.push(syn::Error::new(
+                    attr.span(),
+                    "only doc comments are allowed on signals",
+                ));
+            }
+        }
+
+        if let Some(token) = &sig.constness {
+            errors.push(syn::Error::new(token.span, "signals cannot be `const`"));
+        }
+        if let Some(token) = &sig.asyncness {
+            errors.push(syn::Error::new(token.span, "signals cannot be `async`"));
+        }
+        if let Some(token) = &sig.unsafety {
+            errors.push(syn::Error::new(token.span, "signals cannot be `unsafe`"));
+        }
+        if let Some(abi) = &sig.abi {
+            errors.push(syn::Error::new(abi.span(), "signals cannot have an ABI"));
+        }
+        if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+            errors.push(syn::Error::new(
+                sig.generics.span(),
+                "signals cannot be generic",
+            ));
+        }
+        if let Some(variadic) = &sig.variadic {
+            errors.push(syn::Error::new(
+                variadic.span(),
+                "signals cannot be variadic",
+            ));
+        }
+        if let ReturnType::Type(_, ty) = &sig.output {
+            errors.push(syn::Error::new(ty.span(), "signals cannot return a value"));
+        }
+
+        // syn represents an omitted body as a block that contains the semicolon verbatim.
+        let has_body = !matches!(
+            method.block.stmts.as_slice(),
+            [Stmt::Item(Item::Verbatim(_))]
+        );
+        if has_body {
+            errors.push(syn::Error::new(
+                method.block.span(),
+                "signals are declared without a body (replace it with `;`)",
+            ));
+        }
+
+        let mut params = Vec::new();
+        for arg in sig.inputs {
+            let arg = match arg {
+                FnArg::Receiver(receiver) => {
+                    errors.push(syn::Error::new(
+                        receiver.span(),
+                        "signals cannot take `self`",
+                    ));
+                    continue;
+                }
+                FnArg::Typed(arg) => arg,
+            };
+
+            if let Some(attr) = arg.attrs.first() {
+                errors.push(syn::Error::new(
+                    attr.span(),
+                    "signal parameters cannot have attributes",
+                ));
+            }
+
+            match *arg.pat {
+                Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                    params.push((pat.ident, *arg.ty));
+                }
+                pat => errors.push(syn::Error::new(
+                    pat.span(),
+                    "signal parameters must be plain identifiers",
+                )),
+            }
+        }
+
+        (errors.len() == error_count).then(|| ExportSignal {
+            ident: sig.ident,
+            vis: method.vis,
+            docs,
+            params,
+        })
+    }
+
+    /// Name of the signal in Godot.
+    fn name(&self) -> String {
+        self.ident.unraw().to_string()
+    }
+
+    /// Returns a statement that registers the signal with `builder`.
+    pub(crate) fn register(&self, builder: &Ident) -> TokenStream2 {
+        let name = self.name();
+        let params = self.params.iter().map(|(ident, ty)| {
+            let param_name = ident.unraw().to_string();
+            quote_spanned!(ty.span()=> .with_param_typed::<#ty>(#param_name))
+        });
+
+        quote_spanned!(self.ident.span()=>
+            {
+                #builder.signal(#name)
+                    #(#params)*
+                    .done();
+            }
+        )
+    }
+
+    /// Returns the `emit_*` and `connect_*` methods for the signal, to be added to the
+    /// `impl` block.
+    pub(crate) fn helpers(&self) -> TokenStream2 {
+        let gdnative_core = crate::crate_gdnative_core();
+        let gdnative_bindings = crate::crate_gdnative_bindings();
+
+        let ExportSignal {
+            ident,
+            vis,
+            docs,
+            params,
+        } = self;
+
+        let name = self.name();
+        let span = ident.span();
+        let emit = format_ident!("emit_{}", name, span = span);
+        let connect = format_ident!("connect_{}", name, span = span);
+
+        // Mixed-site hygiene keeps these from clashing with parameters of the same names.
+        let base = Ident::new("base", Span::mixed_site());
+        let target = Ident::new("target", Span::mixed_site());
+        let method = Ident::new("method", Span::mixed_site());
+        let flags = Ident::new("flags", Span::mixed_site());
+
+        let param_idents = params.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
+        let param_types = params.iter().map(|(_, ty)| ty);
+
+        let emit_doc = format!("Emits the `{name}` signal from `base`.");
+        let connect_doc = format!(
+            "Connects the `{name}` signal of `base` to `method` on `target`. See \
+            `Object::connect` for the meaning of `flags`.\n\n\
+            Unlike the `emit_{name}` method, this is not typed. Returns \
+            `GodotError::MethodNotFound` if `target` has no method named `method`, but its \
+            parameter types are not checked, neither here nor by the engine. The method is \
+            called with the arguments of the signal, in the order of their declaration."
+        );
+        let docs = if docs.is_empty() {
+            quote!()
+        } else {
+            quote!(#[doc = ""] #(#docs)*)
+        };
+
+        quote_spanned!(span=>
+            #[doc = #emit_doc]
+            #docs
+            #[allow(dead_code, clippy::too_many_arguments)]
+            #vis fn #emit(
+                &self,
+                #base: &<Self as #gdnative_core::export::NativeClass>::Base,
+                #(#param_idents: #param_types),*
+            ) {
+                #gdnative_core::object::GodotObject::upcast::<#gdnative_bindings::Object>(#base)
+                    .emit_signal(
+                        #name,
+                        &[#(#gdnative_core::core_types::OwnedToVariant::owned_to_variant(#param_idents)),*],
+                    );
+            }
+
+            #[doc = #connect_doc]
+            #[allow(dead_code)]
+            #vis fn #connect<__GdTarget>(
+                &self,
+                #base: &<Self as #gdnative_core::export::NativeClass>::Base,
+                #target: #gdnative_core::object::TRef<'_, __GdTarget, #gdnative_core::object::ownership::Shared>,
+                #method: impl Into<#gdnative_core::core_types::GodotString>,
+                #flags: i64,
+            ) -> #gdnative_core::core_types::GodotResult
+            where
+                __GdTarget: #gdnative_core::object::GodotObject
+                    + #gdnative_core::object::SubClass<#gdnative_bindings::Object>,
+            {
+                let #target = #target.upcast::<#gdnative_bindings::Object>();
+                let #method: #gdnative_core::core_types::GodotString = #method.into();
+
+                // The engine only reports a missing method when the signal is emitted.
+                if !#target.has_method(#method.clone()) {
+                    return Err(#gdnative_core::core_types::GodotError::MethodNotFound);
+                }
+
+                #gdnative_core::object::GodotObject::upcast::<#gdnative_bindings::Object>(#base)
+                    .connect(
+                        #name,
+                        #target,
+                        #method,
+                        #gdnative_core::core_types::VariantArray::new_shared(),
+                        #flags,
+                    )
+            }
+        )
+    }
+}
@@ -0,0 +1,91 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+pub(crate) fn expand_node_path(input: TokenStream2) -> Result<TokenStream2, syn::Error> {
+    let lit = syn::parse2::<LitStr>(input)?;
+    let path = lit.value();
+
+    if let Err(message) = validate(&path) {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("invalid node path {path:?}: {message}"),
+        ));
+    }
+
+    let gdnative_core = crate::crate_gdnative_core();
+    Ok(quote! {
+        #gdnative_core::core_types::NodePath::from_str(#lit)
+    })
+}
+
+/// Checks `path` using the same rules as `NodePathBuilder`.
+fn validate(path: &str) -> Result<(), String> {
+    let relative = path.strip_prefix('/').unwrap_or(path);
+    let (names, subnames) = match relative.split_once(':') {
+        Some((names, subnames)) => (names, Some(subnames)),
+        None => (relative, None),
+    };
+
+    // Paths consisting only of subnames (property paths) or of nothing at all are valid.
+    if !names.is_empty() || (path.starts_with('/') && subnames.is_none()) {
+        for name in names.split('/') {
+            let is_valid = match name {
+                "" => false,
+                "." | ".." => true,
+                _ => !name.contains(['.', '"']),
+            };
+
+            if !is_valid {
+                return Err(format!("invalid node name {name:?}"));
+            }
+        }
+    }
+
+    if let Some(subnames) = subnames {
+        // Godot allows a trailing colon, which is used to refer to resources.
+        let mut subnames = subnames.split(':').peekable();
+        while let Some(subname) = subnames.next() {
+            if subname.is_empty() && subnames.peek().is_some() {
+                return Err("empty subname".into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+
+    #[test]
+    fn valid_paths() {
+        for path in [
+            "",
+            "Sprite",
+            "../Sibling",
+            "./Child/%Unique",
+            "/root/Main",
+            "Path2D/PathFollow2D/Sprite:texture:size",
+            ":position:x",
+            "Sprite:texture:",
+        ] {
+            assert!(validate(path).is_ok(), "{path:?} should be valid");
+        }
+    }
+
+    #[test]
+    fn invalid_paths() {
+        for path in [
+            "/",
+            "Parent//Child",
+            "Child/",
+            "Sprite.png",
+            "Sprite::texture",
+            "Sprite:::",
+            "\"Quoted\"",
+        ] {
+            assert!(validate(path).is_err(), "{path:?} should be invalid");
+        }
+    }
+}
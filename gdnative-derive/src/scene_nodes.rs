@@ -0,0 +1,824 @@
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use syn::parse::{Parse, ParseStream};
+use syn::{DeriveInput, LitStr};
+
+/// Maximum depth of instanced scenes followed to find the class of an instance's root node.
+const MAX_INSTANCE_DEPTH: usize = 16;
+
+pub(crate) fn derive_scene_nodes(input: &DeriveInput) -> Result<TokenStream2, syn::Error> {
+    let mut attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("scene"));
+    let attr = attrs.next().ok_or_else(|| {
+        syn::Error::new(
+            input.ident.span(),
+            "missing `#[scene(\"res://Scene.tscn\")]` attribute",
+        )
+    })?;
+    if let Some(extra) = attrs.next() {
+        return Err(syn::Error::new_spanned(
+            extra,
+            "only one `#[scene]` attribute is allowed",
+        ));
+    }
+
+    let args = attr.parse_args::<SceneArgs>()?;
+    let span = args.path.span();
+    let error = |message: String| syn::Error::new_spanned(attr, message);
+
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .ok_or_else(|| error("`CARGO_MANIFEST_DIR` is not set".into()))?;
+    let resolver = Resolver {
+        project: args
+            .project
+            .map(|project| manifest_dir.join(project.value())),
+        manifest_dir,
+        files: Vec::new(),
+    };
+
+    let scene_path = args.path.value();
+    let (nodes, files) = resolver.scene_nodes(&scene_path).map_err(error)?;
+
+    let gdnative_core = crate::crate_gdnative_core();
+    let gdnative_bindings = crate::crate_gdnative_bindings();
+    let derived = crate::automatically_derived();
+
+    let mut accessors = Vec::new();
+    for (method, node) in method_names(&nodes).map_err(error)? {
+        let method = method_ident(&method, span);
+        let class = class_ident(&node.class, span).map_err(error)?;
+        let path = &node.path;
+
+        let doc = format!(
+            "Returns the `{}` node at `{path}` in `{scene_path}`.",
+            node.class
+        );
+        let panic_message = format!(
+            "node `{path}` of `{scene_path}` does not exist or is not a `{}`",
+            node.class
+        );
+
+        accessors.push(quote_spanned! { span =>
+            #[doc = #doc]
+            ///
+            /// # Panics
+            ///
+            /// If the node does not exist or has a different type, e.g. because `base` is not an
+            /// instance of the scene.
+            ///
+            /// # Safety
+            ///
+            /// This method accesses the scene tree. As a result, any calls to this function must
+            /// follow the official thread-safety guidelines. `assume_safe` invariants must be
+            /// observed for the resulting node during `'a`, if any.
+            #[inline]
+            #[track_caller]
+            pub unsafe fn #method<'a, B>(
+                &self,
+                base: #gdnative_core::object::TRef<'a, B>,
+            ) -> #gdnative_core::object::TRef<'a, #gdnative_bindings::#class>
+            where
+                B: #gdnative_core::object::SubClass<#gdnative_bindings::Node>,
+            {
+                let node: ::std::option::Option<
+                    #gdnative_core::object::TRef<'a, #gdnative_bindings::#class>,
+                > = #gdnative_bindings::utils::NodeResolveExt::get_node_as(&*base, #path);
+                node.unwrap_or_else(|| ::std::panic!(#panic_message))
+            }
+        });
+    }
+
+    // Makes Cargo rebuild the crate when one of the scene files changes.
+    let mut dependencies = Vec::new();
+    for file in files {
+        let file = file
+            .to_str()
+            .ok_or_else(|| error(format!("path {} is not valid UTF-8", file.display())))?
+            .to_owned();
+        dependencies.push(quote! {
+            const _: &[u8] = ::std::include_bytes!(#file);
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #(#dependencies)*
+
+        #derived
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#accessors)*
+        }
+    })
+}
+
+/// Arguments of the `#[scene]` attribute: `#[scene("res://Scene.tscn", project = "../godot")]`.
+struct SceneArgs {
+    path: LitStr,
+    project: Option<LitStr>,
+}
+
+impl Parse for SceneArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut project = None;
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let key = input.parse::<Ident>()?;
+            if key != "project" {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "unknown argument, expected `project`",
+                ));
+            }
+            if project.is_some() {
+                return Err(syn::Error::new(key.span(), "duplicate argument `project`"));
+            }
+            input.parse::<Token![=]>()?;
+            project = Some(input.parse()?);
+        }
+
+        Ok(SceneArgs { path, project })
+    }
+}
+
+/// A node of a scene, other than its root.
+#[derive(Debug, PartialEq)]
+struct SceneNode {
+    /// Path relative to the root node.
+    path: String,
+    name: String,
+    class: String,
+}
+
+struct Resolver {
+    manifest_dir: PathBuf,
+    project: Option<PathBuf>,
+    /// The files read so far.
+    files: Vec<PathBuf>,
+}
+
+impl Resolver {
+    /// Returns the nodes of a scene and the files read to determine them.
+    fn scene_nodes(mut self, path: &str) -> Result<(Vec<SceneNode>, Vec<PathBuf>), String> {
+        let file = self.resolve(path, &self.manifest_dir.clone())?;
+        let tags = self.read(&file)?;
+
+        let mut nodes = Vec::new();
+        for tag in tags.iter().filter(|tag| tag.name == "node") {
+            let path = match node_path(tag) {
+                Some(path) if path != "." => path,
+                // The root node, or a node without a name
+                _ => continue,
+            };
+
+            let class = self.node_class(&file, &tags, &path, 0)?;
+            nodes.push(SceneNode {
+                name: tag.str("name").unwrap_or_default().to_owned(),
+                path,
+                class,
+            });
+        }
+
+        Ok((nodes, self.files))
+    }
+
+    /// Returns the class of the node at `path` in `scene`, whose tags are `tags`.
+    ///
+    /// The class is specified in the tag of the node, unless the node is the root of an
+    /// instanced scene, or a node inside one. In these cases it's looked up in the instanced
+    /// scene. This includes nodes of instances with editable children, and of inherited
+    /// scenes.
+    fn node_class(
+        &mut self,
+        scene: &Path,
+        tags: &[Tag],
+        path: &str,
+        depth: usize,
+    ) -> Result<String, String> {
+        let tag = tags
+            .iter()
+            .find(|tag| tag.name == "node" && node_path(tag).as_deref() == Some(path));
+
+        if let Some(class) = tag.and_then(|tag| tag.str("type")) {
+            return Ok(class.to_owned());
+        }
+
+        // The closest node that instances a scene containing this one, which is either the
+        // node itself or one of its ancestors, and the path of this node in the instanced scene.
+        let instance = tag
+            .filter(|tag| tag.field("instance").is_some())
+            .map(|tag| (tag, ".".to_owned()))
+            .or_else(|| {
+                ancestors(path).find_map(|(ancestor, rest)| {
+                    let tag = tags.iter().find(|tag| {
+                        tag.name == "node"
+                            && tag.field("instance").is_some()
+                            && node_path(tag).as_deref() == Some(ancestor)
+                    })?;
+                    Some((tag, rest))
+                })
+            });
+
+        let (instance, path_in_instance) = instance.ok_or_else(|| {
+            format!(
+                "cannot determine the type of node `{path}` in {}",
+                scene.display()
+            )
+        })?;
+
+        if depth >= MAX_INSTANCE_DEPTH {
+            return Err(format!(
+                "cannot determine the type of node `{path}` in {}: instanced scenes are nested \
+                 more than {MAX_INSTANCE_DEPTH} levels deep, or instance themselves",
+                scene.display()
+            ));
+        }
+
+        let instanced = match instance.field("instance") {
+            Some(Value::ExtResource(id)) => ext_resource_paths(tags).get(id).map(|p| p.to_string()),
+            _ => None,
+        };
+        let instanced = instanced.ok_or_else(|| {
+            format!(
+                "cannot determine the type of node `{path}` in {}: the scene it instances is \
+                 not an external resource of the file",
+                scene.display()
+            )
+        })?;
+
+        let dir = scene.parent().unwrap_or(scene).to_owned();
+        let file = self.resolve(&instanced, &dir)?;
+        let instanced_tags = self.read(&file)?;
+        self.node_class(&file, &instanced_tags, &path_in_instance, depth + 1)
+    }
+
+    fn read(&mut self, file: &Path) -> Result<Vec<Tag>, String> {
+        let text = std::fs::read_to_string(file)
+            .map_err(|err| format!("cannot read {}: {err}", file.display()))?;
+        if !self.files.iter().any(|read| read == file) {
+            self.files.push(file.to_owned());
+        }
+        read_tags(&text).map_err(|err| format!("cannot parse {}: {err}", file.display()))
+    }
+
+    /// Resolves a `res://` path relative to the Godot project, and other paths relative to
+    /// `dir`.
+    fn resolve(&mut self, path: &str, dir: &Path) -> Result<PathBuf, String> {
+        match path.strip_prefix("res://") {
+            Some(path) => Ok(self.project_dir()?.join(path)),
+            None => Ok(dir.join(path)),
+        }
+    }
+
+    /// Returns the directory of the Godot project, which is the `project` argument if given,
+    /// then the `GODOT_PROJECT_DIR` environment variable, and otherwise the closest directory
+    /// containing a `project.godot` file, starting from the crate directory.
+    fn project_dir(&mut self) -> Result<PathBuf, String> {
+        if let Some(project) = &self.project {
+            return Ok(project.clone());
+        }
+
+        let project = match env::var_os("GODOT_PROJECT_DIR") {
+            Some(dir) => self.manifest_dir.join(dir),
+            None => self
+                .manifest_dir
+                .ancestors()
+                .find(|dir| dir.join("project.godot").is_file())
+                .ok_or_else(|| {
+                    "cannot find the Godot project for `res://` paths: \
+                     use `#[scene(\"res://...\", project = \"path/to/project\")]`, \
+                     or set the `GODOT_PROJECT_DIR` environment variable"
+                        .to_owned()
+                })?
+                .to_owned(),
+        };
+
+        self.project = Some(project.clone());
+        Ok(project)
+    }
+}
+
+/// Returns the path of a node relative to the root node, which is `.` for the root itself.
+fn node_path(tag: &Tag) -> Option<String> {
+    let name = tag.str("name")?;
+    match tag.str("parent") {
+        None => Some(".".into()),
+        Some(".") => Some(name.into()),
+        Some(parent) => Some(format!("{parent}/{name}")),
+    }
+}
+
+/// Returns the ancestors of the node at `path`, from the closest to the root, along with the
+/// path of the node relative to each of them.
+fn ancestors(path: &str) -> impl Iterator<Item = (&str, String)> {
+    let parents = path
+        .match_indices('/')
+        .rev()
+        .map(move |(i, _)| (&path[..i], &path[i + 1..]));
+    parents
+        .chain(std::iter::once((".", path)))
+        .map(|(ancestor, rest)| (ancestor, rest.to_owned()))
+}
+
+fn ext_resource_paths(tags: &[Tag]) -> HashMap<i64, &str> {
+    tags.iter()
+        .filter(|tag| tag.name == "ext_resource")
+        .filter_map(|tag| match (tag.field("id"), tag.str("path")) {
+            (Some(Value::Int(id)), Some(path)) => Some((*id, path)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Chooses a method name for each node. Nodes are named after their own name if it is unique
+/// in the scene, and after their path otherwise.
+fn method_names(nodes: &[SceneNode]) -> Result<Vec<(String, &SceneNode)>, String> {
+    let mut counts = HashMap::new();
+    for node in nodes {
+        *counts.entry(snake_case(&node.name)).or_insert(0) += 1;
+    }
+
+    let mut names = HashMap::new();
+    let mut methods = Vec::new();
+    for node in nodes {
+        let mut method = snake_case(&node.name);
+        if counts[&method] > 1 {
+            method = node
+                .path
+                .split('/')
+                .map(snake_case)
+                .collect::<Vec<_>>()
+                .join("_");
+        }
+
+        if let Some(other) = names.insert(method.clone(), &node.path) {
+            return Err(format!(
+                "nodes `{other}` and `{}` would both have the accessor `{method}`, \
+                 consider renaming one of them",
+                node.path
+            ));
+        }
+        methods.push((method, node));
+    }
+
+    Ok(methods)
+}
+
+/// Converts a node name like `HealthBar` or `HTTPRequest 2` to snake case.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && i > 0 && !out.is_empty() && !out.ends_with('_') {
+            let prev = chars[i - 1];
+            let next_is_lower = matches!(chars.get(i + 1), Some(next) if next.is_lowercase());
+            if prev.is_lowercase() || (prev.is_uppercase() && next_is_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+
+    let out = out.trim_end_matches('_');
+    if out.is_empty() {
+        "node".into()
+    } else if out.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{out}")
+    } else {
+        out.into()
+    }
+}
+
+fn method_ident(name: &str, span: Span) -> Ident {
+    match name {
+        "self" | "super" | "crate" => Ident::new(&format!("{name}_"), span),
+        _ if syn::parse_str::<Ident>(name).is_ok() => Ident::new(name, span),
+        _ => Ident::new_raw(name, span),
+    }
+}
+
+fn class_ident(class: &str, span: Span) -> Result<Ident, String> {
+    syn::parse_str::<Ident>(class)
+        .map(|ident| Ident::new(&ident.to_string(), span))
+        .map_err(|_| format!("invalid class name {class:?}"))
+}
+
+/// A tag of a scene file, like `[node name="Player" type="KinematicBody2D" parent="."]`.
+#[derive(Debug, PartialEq)]
+struct Tag {
+    name: String,
+    fields: Vec<(String, Value)>,
+}
+
+/// The values of tag fields that are relevant for finding nodes and their types.
+#[derive(Debug, PartialEq)]
+enum Value {
+    Str(String),
+    Int(i64),
+    ExtResource(i64),
+    Other,
+}
+
+impl Tag {
+    fn field(&self, name: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    }
+
+    fn str(&self, name: &str) -> Option<&str> {
+        match self.field(name)? {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the tags of a scene file, skipping over properties.
+///
+/// The parser of `gdnative::core_types::text` can't be used here, since `gdnative-core` depends
+/// on this crate. Neither can the one of `gdnative-build`, which only reads `project.godot`
+/// files and is private to that crate. Only tag headers are needed to find nodes, so values
+/// other than strings, integers and `ExtResource`s are skipped rather than parsed.
+fn read_tags(text: &str) -> Result<Vec<Tag>, String> {
+    let mut reader = Reader { src: text, pos: 0 };
+    let mut tags = Vec::new();
+    let mut line_start = true;
+
+    while let Some(c) = reader.bump() {
+        match c {
+            '\n' => line_start = true,
+            '[' if line_start => {
+                tags.push(reader.tag()?);
+                line_start = false;
+            }
+            ';' if line_start => reader.skip_line(),
+            '"' => {
+                reader.string()?;
+                line_start = false;
+            }
+            c if c.is_whitespace() => {}
+            _ => line_start = false,
+        }
+    }
+
+    Ok(tags)
+}
+
+struct Reader<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, message: &str) -> String {
+        let line = self.src[..self.pos].matches('\n').count() + 1;
+        format!("{message} at line {line}")
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn skip_line(&mut self) {
+        self.pos += self.rest().find('\n').unwrap_or(self.rest().len());
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.bump() == Some(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{expected}`")))
+        }
+    }
+
+    fn ident(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        Some(&rest[..len]).filter(|ident| !ident.is_empty())
+    }
+
+    /// Reads a tag, after the opening bracket.
+    fn tag(&mut self) -> Result<Tag, String> {
+        let name = self
+            .ident()
+            .ok_or_else(|| self.error("expected tag name"))?
+            .to_owned();
+
+        let mut fields = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(']') {
+                self.pos += 1;
+                return Ok(Tag { name, fields });
+            }
+
+            let field = self
+                .ident()
+                .ok_or_else(|| self.error("expected field name or `]`"))?
+                .to_owned();
+            self.expect('=')?;
+            fields.push((field, self.value()?));
+        }
+    }
+
+    /// Reads a string, after the opening quote.
+    fn string(&mut self) -> Result<String, String> {
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(s),
+                Some('\\') => match self.bump() {
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    Some(c) => s.push(c),
+                    None => break,
+                },
+                Some(c) => s.push(c),
+                None => break,
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') => {
+                self.pos += 1;
+                self.string().map(Value::Str)
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let rest = self.rest();
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || "+-.".contains(c)))
+                    .unwrap_or(rest.len());
+                self.pos += len;
+                Ok(rest[..len].parse().map_or(Value::Other, Value::Int))
+            }
+            Some('[') | Some('{') => {
+                self.skip_nested()?;
+                Ok(Value::Other)
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let ident = self.ident().unwrap_or_default();
+                if self.peek() != Some('(') {
+                    return Ok(Value::Other);
+                }
+
+                if ident == "ExtResource" {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    let id = match self.value()? {
+                        Value::Int(id) => id,
+                        _ => return Err(self.error("expected resource ID")),
+                    };
+                    self.expect(')')?;
+                    Ok(Value::ExtResource(id))
+                } else {
+                    self.skip_nested()?;
+                    Ok(Value::Other)
+                }
+            }
+            _ => Err(self.error("expected value")),
+        }
+    }
+
+    /// Skips a value enclosed in brackets, braces or parentheses, including nested ones.
+    fn skip_nested(&mut self) -> Result<(), String> {
+        let mut depth = 0;
+        while let Some(c) = self.bump() {
+            match c {
+                '[' | '{' | '(' => depth += 1,
+                ']' | '}' | ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                '"' => {
+                    self.string()?;
+                }
+                _ => {}
+            }
+        }
+        Err(self.error("unexpected end of file"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = r#"[gd_scene load_steps=3 format=2]
+
+[ext_resource path="res://Player.gdns" type="Script" id=1]
+[ext_resource path="res://Enemy.tscn" type="PackedScene" id=2]
+
+[node name="Main" type="Node2D"]
+script = ExtResource( 1 )
+
+[node name="Camera" type="Camera2D" parent="." groups=[ "cameras", "[main]" ]]
+text = "a line
+[node name=\"Fake\"]"
+
+[node name="Enemy" parent="." instance=ExtResource( 2 )]
+position = Vector2( 1, 2 )
+
+[connection signal="pressed" from="Camera" to="." method="_on_pressed" binds=[ 1 ]]
+"#;
+
+    #[test]
+    fn reads_tags() {
+        let tags = read_tags(SCENE).unwrap();
+        assert_eq!(
+            vec![
+                "gd_scene",
+                "ext_resource",
+                "ext_resource",
+                "node",
+                "node",
+                "node",
+                "connection"
+            ],
+            tags.iter().map(|tag| tag.name.as_str()).collect::<Vec<_>>()
+        );
+
+        assert_eq!(Some("Camera2D"), tags[4].str("type"));
+        assert_eq!(Some(&Value::Other), tags[4].field("groups"));
+        assert_eq!(Some(&Value::ExtResource(2)), tags[5].field("instance"));
+        assert_eq!(Some(&Value::Int(3)), tags[0].field("load_steps"));
+
+        let ext_resources = ext_resource_paths(&tags);
+        assert_eq!(Some(&"res://Enemy.tscn"), ext_resources.get(&2));
+    }
+
+    #[test]
+    fn rejects_invalid_tags() {
+        assert!(read_tags("[node name=]").is_err());
+        assert!(read_tags("[node name=\"a\"").is_err());
+        assert!(read_tags("[node 1]").is_err());
+    }
+
+    #[test]
+    fn resolves_instanced_classes() {
+        let dir = env::temp_dir().join(format!("gdnative-derive-scenes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, text: &str| std::fs::write(dir.join(name), text).unwrap();
+
+        write(
+            "Enemy.tscn",
+            r#"[ext_resource path="Body.tscn" type="PackedScene" id=1]
+[node name="Enemy" type="KinematicBody2D"]
+[node name="Body" parent="." instance=ExtResource( 1 )]
+"#,
+        );
+        write(
+            "Body.tscn",
+            r#"[node name="Body" type="Node2D"]
+[node name="Sprite" type="Sprite" parent="."]
+"#,
+        );
+        write(
+            "Main.tscn",
+            r#"[ext_resource path="Enemy.tscn" type="PackedScene" id=1]
+[node name="Main" type="Node2D"]
+[node name="Enemy" parent="." instance=ExtResource( 1 )]
+[node name="Sprite" parent="Enemy/Body"]
+modulate = Color( 1, 0, 0, 1 )
+"#,
+        );
+        write(
+            "Loop.tscn",
+            r#"[ext_resource path="Loop.tscn" type="PackedScene" id=1]
+[node name="Loop" instance=ExtResource( 1 )]
+[node name="Inner" parent="."]
+"#,
+        );
+        write(
+            "Missing.tscn",
+            r#"[node name="Main" type="Node"]
+[node name="Child" parent="."]
+"#,
+        );
+
+        let resolver = || Resolver {
+            manifest_dir: dir.clone(),
+            project: None,
+            files: Vec::new(),
+        };
+
+        let (nodes, files) = resolver().scene_nodes("Main.tscn").unwrap();
+        assert_eq!(
+            vec![
+                ("Enemy", "KinematicBody2D"),
+                ("Enemy/Body/Sprite", "Sprite")
+            ],
+            nodes
+                .iter()
+                .map(|node| (node.path.as_str(), node.class.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(3, files.len());
+
+        let err = resolver().scene_nodes("Loop.tscn").unwrap_err();
+        assert!(err.contains("nested more than"), "{err}");
+        let err = resolver().scene_nodes("Missing.tscn").unwrap_err();
+        assert!(
+            err.contains("cannot determine the type of node `Child`"),
+            "{err}"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn snake_case_names() {
+        assert_eq!("health_bar", snake_case("HealthBar"));
+        assert_eq!("camera2d", snake_case("Camera2D"));
+        assert_eq!("http_request_2", snake_case("HTTPRequest 2"));
+        assert_eq!("label", snake_case("label"));
+        assert_eq!("_3d_view", snake_case("3D View"));
+        assert_eq!("node", snake_case("@@"));
+    }
+
+    #[test]
+    fn method_names_for_duplicates() {
+        let node = |path: &str, class: &str| SceneNode {
+            path: path.into(),
+            name: path.rsplit('/').next().unwrap().into(),
+            class: class.into(),
+        };
+
+        let nodes = [
+            node("Camera", "Camera2D"),
+            node("Sprite", "Sprite"),
+            node("UI/Sprite", "Sprite"),
+        ];
+        assert_eq!(
+            vec!["camera", "sprite", "ui_sprite"],
+            method_names(&nodes)
+                .unwrap()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        );
+
+        let nodes = [
+            node("A/B", "Node"),
+            node("A_B", "Node"),
+            node("C/B", "Node"),
+        ];
+        assert!(method_names(&nodes).is_err());
+    }
+
+    #[test]
+    fn method_idents() {
+        let span = Span::call_site();
+        assert_eq!("camera", method_ident("camera", span).to_string());
+        assert_eq!("r#type", method_ident("type", span).to_string());
+        assert_eq!("self_", method_ident("self", span).to_string());
+    }
+}
@@ -85,6 +85,8 @@ pub trait AttrBuilder: FromIterator<syn::Meta> {
 
 pub mod field;
 pub mod item;
+pub mod rename;
 
 pub use field::{FieldAttr, FieldAttrBuilder};
 pub use item::{ItemAttr, ItemAttrBuilder};
+pub use rename::RenameRule;
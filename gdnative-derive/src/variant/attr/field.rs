@@ -13,6 +13,9 @@ pub struct FieldAttr {
     pub skip_from_variant: bool,
     pub to_variant_with: Option<syn::Path>,
     pub from_variant_with: Option<syn::Path>,
+    pub rename: Option<String>,
+    pub default: Option<syn::Path>,
+    pub flatten: bool,
 }
 
 impl FieldAttr {
@@ -30,6 +33,9 @@ pub struct FieldAttrBuilder {
     skip_from_variant: bool,
     to_variant_with: Option<syn::Path>,
     from_variant_with: Option<syn::Path>,
+    rename: Option<syn::LitStr>,
+    default: Option<syn::Path>,
+    flatten: bool,
     errors: Vec<syn::Error>,
 }
 
@@ -77,16 +83,30 @@ impl FieldAttrBuilder {
             match name.as_str() {
                 skip_to_variant,
                 skip_from_variant,
+                flatten,
             }
         }
 
-        #[allow(clippy::single_match)]
         match name.as_str() {
             "skip" => {
                 self.skip_to_variant = true;
                 self.skip_from_variant = true;
                 return Ok(());
             }
+            "default" => {
+                if self
+                    .default
+                    .replace(parse_quote_spanned!(flag.span()=> ::std::default::Default::default))
+                    .is_some()
+                {
+                    return Err(syn::Error::new(
+                        flag.span(),
+                        "the argument default is already set",
+                    ));
+                }
+
+                return Ok(());
+            }
             _ => {}
         }
 
@@ -101,12 +121,11 @@ impl FieldAttrBuilder {
         self.errors.extend(err);
     }
 
-    #[allow(clippy::single_match)]
     fn try_set_pair(&mut self, pair: &syn::MetaNameValue) -> Result<(), syn::Error> {
         let syn::MetaNameValue { path, lit, .. } = pair;
 
         const VALID_KEYS: &str =
-            "to_variant_with, from_variant_with, with, skip_to_variant, skip_from_variant, skip";
+            "to_variant_with, from_variant_with, with, skip_to_variant, skip_from_variant, skip, rename, default";
 
         let name = path
             .get_ident()
@@ -133,6 +152,7 @@ impl FieldAttrBuilder {
             match name.as_str() = lit {
                 to_variant_with: syn::Path,
                 from_variant_with: syn::Path,
+                default: syn::Path,
             }
         }
 
@@ -172,6 +192,21 @@ impl FieldAttrBuilder {
 
                 return Ok(());
             }
+            "rename" => {
+                let lit_str = match lit {
+                    syn::Lit::Str(lit_str) => lit_str.clone(),
+                    _ => return Err(syn::Error::new(lit.span(), "expected string literal")),
+                };
+
+                if self.rename.replace(lit_str).is_some() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "the argument rename is already set",
+                    ));
+                }
+
+                return Ok(());
+            }
             _ => {}
         }
 
@@ -199,11 +234,35 @@ impl AttrBuilder for FieldAttrBuilder {
     type Attr = FieldAttr;
     fn done(mut self) -> Result<FieldAttr, syn::Error> {
         if self.errors.is_empty() {
+            if self.flatten {
+                if let Some(rename) = &self.rename {
+                    return Err(syn::Error::new(
+                        rename.span(),
+                        "`rename` cannot be used together with `flatten`",
+                    ));
+                }
+                if let Some(default) = &self.default {
+                    return Err(syn::Error::new(
+                        default.span(),
+                        "`default` cannot be used together with `flatten`",
+                    ));
+                }
+                if let Some(to_variant_with) = &self.to_variant_with {
+                    return Err(syn::Error::new(
+                        to_variant_with.span(),
+                        "`with` and `to_variant_with` cannot be used together with `flatten`",
+                    ));
+                }
+            }
+
             Ok(FieldAttr {
                 skip_to_variant: self.skip_to_variant,
                 skip_from_variant: self.skip_from_variant,
                 to_variant_with: self.to_variant_with,
                 from_variant_with: self.from_variant_with,
+                rename: self.rename.map(|lit| lit.value()),
+                default: self.default,
+                flatten: self.flatten,
             })
         } else {
             let first_error = self.errors.remove(0);
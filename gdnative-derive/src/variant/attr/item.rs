@@ -5,16 +5,21 @@ use syn::spanned::Spanned;
 
 use crate::variant::{attr::generate_error_with_docs, repr::EnumReprKind};
 
-use super::AttrBuilder;
+use super::{AttrBuilder, RenameRule};
 
 #[derive(Clone, Debug)]
 pub struct ItemAttr {
     pub enum_repr_kind: Option<(EnumReprKind, Span)>,
+    pub rename_all: Option<RenameRule>,
 }
 
 #[derive(Debug, Default)]
 pub struct ItemAttrBuilder {
     enum_repr_kind: Option<syn::Ident>,
+    tag: Option<syn::LitStr>,
+    content: Option<syn::LitStr>,
+    untagged: Option<Span>,
+    rename_all: Option<syn::LitStr>,
 
     errors: Vec<syn::Error>,
 }
@@ -44,6 +49,17 @@ impl ItemAttrBuilder {
     }
 
     fn try_set_flag(&mut self, flag: &syn::Path) -> Result<(), syn::Error> {
+        if flag.is_ident("untagged") {
+            if self.untagged.replace(flag.span()).is_some() {
+                return Err(syn::Error::new(
+                    flag.span(),
+                    "the argument untagged is already set",
+                ));
+            }
+
+            return Ok(());
+        }
+
         Err(generate_error_with_docs(
             flag.span(),
             "Unknown flag, or missing macro arguments",
@@ -59,7 +75,7 @@ impl ItemAttrBuilder {
     fn try_set_pair(&mut self, pair: &syn::MetaNameValue) -> Result<(), syn::Error> {
         let syn::MetaNameValue { path, lit, .. } = pair;
 
-        const VALID_KEYS: &str = "enum";
+        const VALID_KEYS: &str = "enum, tag, content, rename_all";
 
         let name = path
             .get_ident()
@@ -88,6 +104,31 @@ impl ItemAttrBuilder {
             }
         }
 
+        match name.as_str() {
+            "tag" | "content" | "rename_all" => {
+                let lit_str = match lit {
+                    syn::Lit::Str(lit_str) => lit_str.clone(),
+                    _ => return Err(syn::Error::new(lit.span(), "expected string literal")),
+                };
+
+                let slot = match name.as_str() {
+                    "tag" => &mut self.tag,
+                    "content" => &mut self.content,
+                    _ => &mut self.rename_all,
+                };
+
+                if slot.replace(lit_str).is_some() {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!("the argument {name} is already set"),
+                    ));
+                }
+
+                return Ok(());
+            }
+            _ => {}
+        }
+
         Err(syn::Error::new(
             path.span(),
             format!("unknown argument, expected one of:\n\t{VALID_KEYS}"),
@@ -124,7 +165,50 @@ impl AttrBuilder for ItemAttrBuilder {
                 })
                 .transpose()?;
 
-            Ok(ItemAttr { enum_repr_kind })
+            let tagging = match (self.tag, self.content, self.untagged) {
+                (None, None, None) => None,
+                (Some(tag), None, None) => {
+                    let span = tag.span();
+                    Some((EnumReprKind::Internal { tag }, span))
+                }
+                (Some(tag), Some(content), None) => {
+                    let span = tag.span();
+                    Some((EnumReprKind::Adjacent { tag, content }, span))
+                }
+                (None, None, Some(span)) => Some((EnumReprKind::Untagged, span)),
+                (None, Some(content), None) => {
+                    return Err(syn::Error::new(
+                        content.span(),
+                        "`content` can only be used together with `tag`",
+                    ));
+                }
+                (_, _, Some(span)) => {
+                    return Err(syn::Error::new(
+                        span,
+                        "`untagged` cannot be used together with `tag` or `content`",
+                    ));
+                }
+            };
+
+            let enum_repr_kind = match (enum_repr_kind, tagging) {
+                (Some(_), Some((_, span))) => {
+                    return Err(syn::Error::new(
+                        span,
+                        "`tag`, `content` and `untagged` cannot be used together with `enum`",
+                    ));
+                }
+                (kind, tagging) => kind.or(tagging),
+            };
+
+            let rename_all = self
+                .rename_all
+                .map(|lit| RenameRule::parse(&lit))
+                .transpose()?;
+
+            Ok(ItemAttr {
+                enum_repr_kind,
+                rename_all,
+            })
         } else {
             let first_error = self.errors.remove(0);
             let errors = self
@@ -0,0 +1,158 @@
+/// Case conversion rules for `#[variant(rename_all = "...")]`, following the conventions
+/// used by `serde`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum RenameRule {
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebab,
+}
+
+const RULES: &[(&str, RenameRule)] = &[
+    ("lowercase", RenameRule::Lower),
+    ("UPPERCASE", RenameRule::Upper),
+    ("PascalCase", RenameRule::Pascal),
+    ("camelCase", RenameRule::Camel),
+    ("snake_case", RenameRule::Snake),
+    ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+    ("kebab-case", RenameRule::Kebab),
+    ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+];
+
+impl RenameRule {
+    pub fn parse(lit: &syn::LitStr) -> Result<Self, syn::Error> {
+        let value = lit.value();
+        RULES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| {
+                let expected = RULES
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                syn::Error::new(
+                    lit.span(),
+                    format!("unknown rename rule, expected one of:\n\t{expected}"),
+                )
+            })
+    }
+
+    /// Applies the rule to a field name, which is expected to be in `snake_case`.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply_to_field(field);
+                lowercase_first(&pascal)
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+
+    /// Applies the rule to an enum variant name, which is expected to be in `PascalCase`.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::Pascal => variant.to_owned(),
+            RenameRule::Lower => variant.to_ascii_lowercase(),
+            RenameRule::Upper => variant.to_ascii_uppercase(),
+            RenameRule::Camel => lowercase_first(variant),
+            RenameRule::Snake => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnake => RenameRule::Snake
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            RenameRule::Kebab => RenameRule::Snake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            RenameRule::ScreamingKebab => RenameRule::ScreamingSnake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+}
+
+fn lowercase_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename_fields() {
+        let field = "very_tasty_field";
+        for (rule, expected) in [
+            (RenameRule::Lower, "very_tasty_field"),
+            (RenameRule::Upper, "VERY_TASTY_FIELD"),
+            (RenameRule::Pascal, "VeryTastyField"),
+            (RenameRule::Camel, "veryTastyField"),
+            (RenameRule::Snake, "very_tasty_field"),
+            (RenameRule::ScreamingSnake, "VERY_TASTY_FIELD"),
+            (RenameRule::Kebab, "very-tasty-field"),
+            (RenameRule::ScreamingKebab, "VERY-TASTY-FIELD"),
+        ] {
+            assert_eq!(expected, rule.apply_to_field(field), "{rule:?}");
+        }
+    }
+
+    #[test]
+    fn rename_variants() {
+        let variant = "VeryTastyVariant";
+        for (rule, expected) in [
+            (RenameRule::Lower, "verytastyvariant"),
+            (RenameRule::Upper, "VERYTASTYVARIANT"),
+            (RenameRule::Pascal, "VeryTastyVariant"),
+            (RenameRule::Camel, "veryTastyVariant"),
+            (RenameRule::Snake, "very_tasty_variant"),
+            (RenameRule::ScreamingSnake, "VERY_TASTY_VARIANT"),
+            (RenameRule::Kebab, "very-tasty-variant"),
+            (RenameRule::ScreamingKebab, "VERY-TASTY-VARIANT"),
+        ] {
+            assert_eq!(expected, rule.apply_to_variant(variant), "{rule:?}");
+        }
+    }
+}
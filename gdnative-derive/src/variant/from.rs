@@ -35,8 +35,16 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
             variants,
             kind,
             primitive_repr,
+            names,
         }) => match kind {
-            EnumReprKind::External => expand_external(&ident, &input_ident, variants)?,
+            EnumReprKind::External => expand_external(&ident, &input_ident, variants, &names)?,
+            EnumReprKind::Internal { tag } => {
+                expand_internal(&ident, &input_ident, &tag, variants, &names)?
+            }
+            EnumReprKind::Adjacent { tag, content } => {
+                expand_adjacent(&ident, &input_ident, &tag, &content, variants, &names)?
+            }
+            EnumReprKind::Untagged => expand_untagged(&ident, &input_ident, variants, &names)?,
             EnumReprKind::Str => {
                 if let Some((var_ident, _)) = variants
                     .iter()
@@ -48,12 +56,7 @@ pub(crate) fn expand_from_variant(derive_data: DeriveData) -> Result<TokenStream
                     ));
                 }
 
-                let var_ident_strings: Vec<String> = variants
-                    .iter()
-                    .map(|(var_ident, _)| format!("{var_ident}"))
-                    .collect();
-
-                let var_ident_string_literals = var_ident_strings
+                let var_ident_string_literals = names
                     .iter()
                     .map(|string| Literal::string(string))
                     .collect::<Vec<_>>();
@@ -164,15 +167,11 @@ fn expand_external(
     ident: &syn::Ident,
     input_ident: &syn::Ident,
     variants: Vec<(Ident, super::repr::VariantRepr)>,
+    names: &[String],
 ) -> Result<TokenStream2, syn::Error> {
     let var_input_ident = Ident::new("__enum_variant", Span::call_site());
 
-    let var_ident_strings: Vec<String> = variants
-        .iter()
-        .map(|(var_ident, _)| format!("{var_ident}"))
-        .collect();
-
-    let var_ident_string_literals = var_ident_strings
+    let var_ident_string_literals = names
         .iter()
         .map(|string| Literal::string(string))
         .collect::<Vec<_>>();
@@ -238,3 +237,211 @@ fn expand_external(
         }
     })
 }
+
+/// Generates code that extracts the tag of an internally or adjacently tagged enum from
+/// `__dict`, binding it to `__tag`.
+fn expand_tag(tag: &syn::LitStr, expected: &TokenStream2) -> TokenStream2 {
+    quote! {
+        let __tag = String::from_variant(&__dict.get_or_nil(#tag))
+            .map_err(|__err| FVE::InvalidEnumRepr {
+                expected: #expected,
+                error: std::boxed::Box::new(FVE::InvalidField {
+                    field_name: #tag,
+                    error: std::boxed::Box::new(__err),
+                }),
+            })?;
+    }
+}
+
+fn expand_internal(
+    ident: &syn::Ident,
+    input_ident: &syn::Ident,
+    tag: &syn::LitStr,
+    variants: Vec<(Ident, VariantRepr)>,
+    names: &[String],
+) -> Result<TokenStream2, syn::Error> {
+    let var_input_ident = Ident::new("__enum_variant", Span::call_site());
+    let expected = quote! { VariantEnumRepr::InternallyTagged };
+
+    let var_ident_string_literals = names
+        .iter()
+        .map(|name| Literal::string(name))
+        .collect::<Vec<_>>();
+
+    let ref_var_ident_string_literals = &var_ident_string_literals;
+
+    // The tag is stored alongside the fields, so struct and newtype variants are read from
+    // the dictionary without it. This keeps the tag out of flattened fields and newtype values.
+    let var_from_variants = variants
+        .iter()
+        .map(|(var_ident, var_repr)| match var_repr {
+            VariantRepr::Unit(_) => Ok(quote! { Ok(#ident::#var_ident) }),
+            VariantRepr::Tuple(fields) if fields.len() != 1 => Err(syn::Error::new(
+                var_ident.span(),
+                "internally tagged enums cannot contain tuple variants",
+            )),
+            _ => var_repr.make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident }),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let var_input_ident_iter = std::iter::repeat(&var_input_ident);
+    let extract_tag = expand_tag(tag, &expected);
+    let early_return = variants.is_empty().then(|| {
+        quote! {
+            return Err(FVE::UnknownEnumVariant {
+                variant: __tag,
+                expected: &[],
+            });
+        }
+    });
+
+    Ok(quote! {
+        let __dict = ::gdnative::core_types::Dictionary::from_variant(#input_ident)
+            .map_err(|__err| FVE::InvalidEnumRepr {
+                expected: #expected,
+                error: std::boxed::Box::new(__err),
+            })?;
+
+        #extract_tag
+
+        #early_return
+
+        let __fields = __dict.duplicate();
+        __fields.erase(#tag);
+        let __fields = __fields.into_shared().to_variant();
+
+        match __tag.as_str() {
+            #(
+                #ref_var_ident_string_literals => {
+                    let #var_input_ident_iter = &__fields;
+                    (#var_from_variants).map_err(|err| FVE::InvalidEnumVariant {
+                        variant: #ref_var_ident_string_literals,
+                        error: std::boxed::Box::new(err),
+                    })
+                },
+            )*
+            variant => Err(FVE::UnknownEnumVariant {
+                variant: variant.to_string(),
+                expected: &[#(#ref_var_ident_string_literals),*],
+            }),
+        }
+    })
+}
+
+fn expand_adjacent(
+    ident: &syn::Ident,
+    input_ident: &syn::Ident,
+    tag: &syn::LitStr,
+    content: &syn::LitStr,
+    variants: Vec<(Ident, VariantRepr)>,
+    names: &[String],
+) -> Result<TokenStream2, syn::Error> {
+    let var_input_ident = Ident::new("__enum_variant", Span::call_site());
+    let expected = quote! { VariantEnumRepr::AdjacentlyTagged };
+
+    let var_ident_string_literals = names
+        .iter()
+        .map(|name| Literal::string(name))
+        .collect::<Vec<_>>();
+
+    let ref_var_ident_string_literals = &var_ident_string_literals;
+
+    // Unit variants are represented by the tag alone, so any content is ignored.
+    let var_from_variants = variants
+        .iter()
+        .map(|(var_ident, var_repr)| match var_repr {
+            VariantRepr::Unit(_) => Ok(quote! { Ok(#ident::#var_ident) }),
+            _ => var_repr.make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident }),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let var_input_ident_iter = std::iter::repeat(&var_input_ident);
+    let extract_tag = expand_tag(tag, &expected);
+    let early_return = variants.is_empty().then(|| {
+        quote! {
+            return Err(FVE::UnknownEnumVariant {
+                variant: __tag,
+                expected: &[],
+            });
+        }
+    });
+
+    Ok(quote! {
+        let __dict = ::gdnative::core_types::Dictionary::from_variant(#input_ident)
+            .map_err(|__err| FVE::InvalidEnumRepr {
+                expected: #expected,
+                error: std::boxed::Box::new(__err),
+            })?;
+
+        #extract_tag
+
+        #early_return
+
+        match __tag.as_str() {
+            #(
+                #ref_var_ident_string_literals => {
+                    let #var_input_ident_iter = &__dict.get_or_nil(#content);
+                    (#var_from_variants).map_err(|err| FVE::InvalidEnumVariant {
+                        variant: #ref_var_ident_string_literals,
+                        error: std::boxed::Box::new(err),
+                    })
+                },
+            )*
+            variant => Err(FVE::UnknownEnumVariant {
+                variant: variant.to_string(),
+                expected: &[#(#ref_var_ident_string_literals),*],
+            }),
+        }
+    })
+}
+
+fn expand_untagged(
+    ident: &syn::Ident,
+    input_ident: &syn::Ident,
+    variants: Vec<(Ident, VariantRepr)>,
+    names: &[String],
+) -> Result<TokenStream2, syn::Error> {
+    let var_input_ident = Ident::new("__enum_variant", Span::call_site());
+
+    let var_ident_string_literals = names
+        .iter()
+        .map(|name| Literal::string(name))
+        .collect::<Vec<_>>();
+
+    // Unit variants are represented as `Nil`.
+    let var_from_variants = variants
+        .iter()
+        .map(|(var_ident, var_repr)| match var_repr {
+            VariantRepr::Unit(_) => Ok(quote! {
+                if #var_input_ident.is_nil() {
+                    Ok(#ident::#var_ident)
+                } else {
+                    Err(FVE::InvalidVariantType {
+                        variant_type: #var_input_ident.get_type(),
+                        expected: ::gdnative::core_types::VariantType::Nil,
+                    })
+                }
+            }),
+            _ => var_repr.make_from_variant_expr(&var_input_ident, &quote! { #ident::#var_ident }),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let var_input_ident_iter = std::iter::repeat(&var_input_ident);
+
+    // Variants are tried in declaration order, and the first one that matches is returned.
+    Ok(quote! {
+        #[allow(unused_mut)]
+        let mut __errors = std::vec::Vec::new();
+
+        #(
+            let #var_input_ident_iter = #input_ident;
+            let __result = { #var_from_variants };
+            match __result {
+                Ok(__value) => return Ok(__value),
+                Err(__err) => __errors.push((#var_ident_string_literals, __err)),
+            }
+        )*
+
+        Err(FVE::NoMatchingEnumVariant { errors: __errors })
+    })
+}
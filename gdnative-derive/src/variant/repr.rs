@@ -1,7 +1,8 @@
 use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use syn::spanned::Spanned;
 use syn::{DataEnum, Fields, Ident, Type};
 
-use super::attr::{FieldAttr, FieldAttrBuilder, ItemAttr};
+use super::attr::{FieldAttr, FieldAttrBuilder, ItemAttr, RenameRule};
 use super::{parse_attrs, ToVariantTrait};
 
 // Shouldn't matter since this is immediately unpacked anyway.
@@ -24,6 +25,8 @@ pub(crate) enum VariantRepr {
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub(crate) struct Field {
     pub ident: Ident,
+    /// Key of the field in the `Dictionary` representation, after renaming.
+    pub name: String,
     pub ty: Type,
     pub attr: FieldAttr,
 }
@@ -36,9 +39,11 @@ pub(crate) struct EnumRepr {
     pub kind: EnumReprKind,
     pub primitive_repr: Option<Type>,
     pub variants: Vec<(Ident, VariantRepr)>,
+    /// Names of the variants in the representation, after renaming, in declaration order.
+    pub names: Vec<String>,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub enum EnumReprKind {
     /// Externally-tagged objects, i.e. the original behavior.
     External,
@@ -46,6 +51,15 @@ pub enum EnumReprKind {
     Repr,
     /// Represent as strings.
     Str,
+    /// Internally-tagged objects, with the variant name stored alongside the fields.
+    Internal { tag: syn::LitStr },
+    /// Adjacently-tagged objects, with the variant name and value under separate keys.
+    Adjacent {
+        tag: syn::LitStr,
+        content: syn::LitStr,
+    },
+    /// Represent as the value of the variant, without any tag.
+    Untagged,
 }
 
 impl EnumRepr {
@@ -58,7 +72,7 @@ impl EnumRepr {
             .variants
             .iter()
             .map(|variant| {
-                let mut repr = VariantRepr::repr_for(&variant.fields)?;
+                let mut repr = VariantRepr::repr_for(&variant.fields, None)?;
                 if let VariantRepr::Unit(discriminant) = &mut repr {
                     if let Some((_, expr)) = &variant.discriminant {
                         *discriminant = Some(expr.clone());
@@ -67,33 +81,74 @@ impl EnumRepr {
 
                 Ok((variant.ident.clone(), repr))
             })
-            .collect::<Result<_, syn::Error>>()?;
+            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+        let names = variants
+            .iter()
+            .map(|(ident, _)| {
+                let name = ident.to_string();
+                attr.rename_all
+                    .map_or_else(|| name.clone(), |rule| rule.apply_to_variant(&name))
+            })
+            .collect();
+
+        let kind = attr
+            .enum_repr_kind
+            .map_or(EnumReprKind::External, |(kind, _)| kind);
+
+        // The tag of internally tagged enums shares the dictionary with the fields.
+        if let EnumReprKind::Internal { tag } = &kind {
+            let tag_value = tag.value();
+            for (_, repr) in &variants {
+                if let VariantRepr::Struct(fields) = repr {
+                    if let Some(field) = fields
+                        .iter()
+                        .find(|f| !f.attr.flatten && f.name == tag_value)
+                    {
+                        return Err(syn::Error::new(
+                            field.ident.span(),
+                            format!("field name `{tag_value}` conflicts with the `tag` key"),
+                        ));
+                    }
+                }
+            }
+        }
 
         Ok(EnumRepr {
-            kind: attr
-                .enum_repr_kind
-                .map_or(EnumReprKind::External, |(kind, _)| kind),
+            kind,
             primitive_repr,
             variants,
+            names,
         })
     }
 }
 
 impl StructRepr {
     pub(crate) fn repr_for(attr: ItemAttr, fields: &Fields) -> Result<Self, syn::Error> {
-        if let Some((_, span)) = attr.enum_repr_kind {
-            return Err(syn::Error::new(
-                span,
-                "`enum` representation can only be set for enums",
-            ));
+        if let Some((kind, span)) = attr.enum_repr_kind {
+            let message = match kind {
+                EnumReprKind::External | EnumReprKind::Repr | EnumReprKind::Str => {
+                    "`enum` representation can only be set for enums"
+                }
+                EnumReprKind::Internal { .. }
+                | EnumReprKind::Adjacent { .. }
+                | EnumReprKind::Untagged => {
+                    "`tag`, `content` and `untagged` can only be set for enums"
+                }
+            };
+
+            return Err(syn::Error::new(span, message));
         }
 
-        VariantRepr::repr_for(fields).map(StructRepr)
+        VariantRepr::repr_for(fields, attr.rename_all).map(StructRepr)
     }
 }
 
 impl VariantRepr {
-    pub(crate) fn repr_for(fields: &Fields) -> Result<Self, syn::Error> {
+    pub(crate) fn repr_for(
+        fields: &Fields,
+        rename_all: Option<RenameRule>,
+    ) -> Result<Self, syn::Error> {
         let this = match fields {
             Fields::Named(fields) => VariantRepr::Struct(
                 fields
@@ -103,7 +158,16 @@ impl VariantRepr {
                         let ident = f.ident.clone().expect("fields should be named");
                         let ty = f.ty.clone();
                         let attr = parse_attrs::<FieldAttrBuilder, _>(&f.attrs)?;
-                        Ok(Field { ident, ty, attr })
+                        let name = attr.rename.clone().unwrap_or_else(|| {
+                            let name = ident.to_string();
+                            rename_all.map_or(name.clone(), |rule| rule.apply_to_field(&name))
+                        });
+                        Ok(Field {
+                            ident,
+                            name,
+                            ty,
+                            attr,
+                        })
                     })
                     .collect::<Result<Vec<_>, syn::Error>>()?,
             ),
@@ -116,7 +180,19 @@ impl VariantRepr {
                         let ident = Ident::new(&format!("__field_{n}"), Span::call_site());
                         let ty = f.ty.clone();
                         let attr = parse_attrs::<FieldAttrBuilder, _>(&f.attrs)?;
-                        Ok(Field { ident, ty, attr })
+                        if attr.rename.is_some() || attr.flatten || attr.default.is_some() {
+                            return Err(syn::Error::new(
+                                f.ty.span(),
+                                "`rename`, `default` and `flatten` can only be used on named fields",
+                            ));
+                        }
+                        let name = n.to_string();
+                        Ok(Field {
+                            ident,
+                            name,
+                            ty,
+                            attr,
+                        })
                     })
                     .collect::<Result<_, syn::Error>>()?,
             ),
@@ -184,26 +260,12 @@ impl VariantRepr {
                 }
             }
             VariantRepr::Struct(fields) => {
-                let fields: Vec<&Field> =
-                    fields.iter().filter(|f| !f.attr.skip_to_variant).collect();
-
-                let name_strings: Vec<String> =
-                    fields.iter().map(|f| format!("{}", &f.ident)).collect();
-
-                let name_string_literals =
-                    name_strings.iter().map(|string| Literal::string(string));
-
-                let exprs = fields.iter().map(|f| f.make_to_variant_expr(trait_kind));
+                let inserts = Self::make_dict_inserts(fields, trait_kind);
 
                 quote! {
                     {
                         let __dict = ::gdnative::core_types::Dictionary::new();
-                        #(
-                            {
-                                let __key = ::gdnative::core_types::GodotString::from(#name_string_literals).to_variant();
-                                __dict.insert(&__key, &#exprs);
-                            }
-                        )*
+                        #inserts
                         __dict.into_shared().to_variant()
                     }
                 }
@@ -213,6 +275,47 @@ impl VariantRepr {
         Ok(tokens)
     }
 
+    /// Generates statements inserting the entries of `value`, whose type implements
+    /// `ToVariantDictionary`, into a unique `Dictionary` bound to `__dict`.
+    pub(crate) fn make_dict_merge(value: &TokenStream2) -> TokenStream2 {
+        quote! {
+            {
+                let __value = #value;
+                if let Ok(__inner) = ::gdnative::core_types::Dictionary::from_variant(&__value) {
+                    for (__key, __value) in __inner.iter() {
+                        __dict.insert(&__key, &__value);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generates statements inserting the non-skipped `fields` into a unique `Dictionary`
+    /// bound to `__dict`. The entries of flattened fields are inserted individually.
+    pub(crate) fn make_dict_inserts(fields: &[Field], trait_kind: ToVariantTrait) -> TokenStream2 {
+        let inserts = fields
+            .iter()
+            .filter(|f| !f.attr.skip_to_variant)
+            .map(|f| {
+                let expr = f.make_to_variant_expr(trait_kind);
+                if f.attr.flatten {
+                    Self::make_dict_merge(&expr)
+                } else {
+                    let name_string_literal = Literal::string(&f.name);
+                    quote! {
+                        {
+                            let __key = ::gdnative::core_types::GodotString::from(#name_string_literal).to_variant();
+                            __dict.insert(&__key, &#expr);
+                        }
+                    }
+                }
+            });
+
+        quote! {
+            #( #inserts )*
+        }
+    }
+
     pub(crate) fn make_from_variant_expr(
         &self,
         variant: &Ident,
@@ -302,31 +405,85 @@ impl VariantRepr {
                 }
             }
             VariantRepr::Struct(fields) => {
-                let skipped_fields: Vec<&Field> =
-                    fields.iter().filter(|f| f.attr.skip_from_variant).collect();
-
-                let non_skipped_fields: Vec<&Field> = fields
+                // Flattened fields are read from the entries that are not claimed by any
+                // other field.
+                let rest = fields
                     .iter()
-                    .filter(|f| !f.attr.skip_from_variant)
-                    .collect();
-
-                let skipped_idents = skipped_fields.iter().map(|f| &f.ident);
-                let non_skipped_idents: Vec<&Ident> =
-                    non_skipped_fields.iter().map(|f| &f.ident).collect();
-                let ctor_idents = fields.iter().map(|f| &f.ident);
+                    .any(|f| f.attr.flatten && !f.attr.skip_from_variant)
+                    .then(|| {
+                        let claimed_names = fields
+                            .iter()
+                            .filter(|f| !f.attr.flatten && !f.attr.skip_from_variant)
+                            .map(|f| Literal::string(&f.name));
+
+                        quote! {
+                            let __rest = __dict.duplicate();
+                            #(
+                                __rest.erase(#claimed_names);
+                            )*
+                            let __rest = __rest.into_shared().to_variant();
+                        }
+                    });
 
-                let name_strings: Vec<String> = non_skipped_idents
-                    .iter()
-                    .map(|ident| format!("{ident}"))
-                    .collect();
+                let field_exprs = fields.iter().map(|f| {
+                    let ident = &f.ident;
+                    let default = f
+                        .attr
+                        .default
+                        .clone()
+                        .unwrap_or_else(|| parse_quote!(std::default::Default::default));
+
+                    if f.attr.skip_from_variant {
+                        quote! {
+                            let #ident = #default();
+                        }
+                    } else if f.attr.flatten {
+                        let field_name_literal = Literal::string(&format!("{ident}"));
+                        let expr = f.make_from_variant_expr(&quote!(&__rest));
+                        quote! {
+                            let #ident = #expr
+                                .map_err(|err| FVE::InvalidField {
+                                    field_name: #field_name_literal,
+                                    error: std::boxed::Box::new(err),
+                                })?;
+                        }
+                    } else {
+                        let name_string_literal = Literal::string(&f.name);
+                        let expr = f.make_from_variant_expr(&quote!(&__value));
+                        let map_err = quote! {
+                            .map_err(|err| FVE::InvalidField {
+                                field_name: __field_name,
+                                error: std::boxed::Box::new(err),
+                            })?
+                        };
+
+                        // Fields with a default value only fall back to it when the key is
+                        // missing entirely.
+                        let value = if f.attr.default.is_some() {
+                            quote! {
+                                match __dict.get(&__key) {
+                                    Some(__value) => #expr #map_err,
+                                    None => #default(),
+                                }
+                            }
+                        } else {
+                            quote! {
+                                {
+                                    let __value = __dict.get_or_nil(&__key);
+                                    #expr #map_err
+                                }
+                            }
+                        };
 
-                let name_string_literals =
-                    name_strings.iter().map(|string| Literal::string(string));
+                        quote! {
+                            let __field_name = #name_string_literal;
+                            let __key = ::gdnative::core_types::GodotString::from(__field_name).to_variant();
+                            let #ident = #value;
+                        }
+                    }
+                });
 
-                let expr_variant = &quote!(&__dict.get_or_nil(&__key));
-                let exprs = non_skipped_fields
-                    .iter()
-                    .map(|f| f.make_from_variant_expr(expr_variant));
+                let ctor_idents = fields.iter().map(|f| &f.ident);
 
                 quote! {
                     {
@@ -336,18 +493,8 @@ impl VariantRepr {
                                 error: std::boxed::Box::new(__err),
                             })
                             .and_then(|__dict| {
-                                #(
-                                    let __field_name = #name_string_literals;
-                                    let __key = ::gdnative::core_types::GodotString::from(__field_name).to_variant();
-                                    let #non_skipped_idents = #exprs
-                                        .map_err(|err| FVE::InvalidField {
-                                            field_name: __field_name,
-                                            error: std::boxed::Box::new(err),
-                                        })?;
-                                )*
-                                #(
-                                    let #skipped_idents = std::default::Default::default();
-                                )*
+                                #rest
+                                #( #field_exprs )*
                                 Ok(#ctor { #( #ctor_idents ),* })
                             })
                     }
@@ -1,5 +1,6 @@
 use crate::variant::bounds;
 use proc_macro2::{Literal, TokenStream as TokenStream2};
+use syn::spanned::Spanned;
 
 use crate::variant::repr::{EnumReprKind, VariantRepr};
 
@@ -25,6 +26,27 @@ pub(crate) fn expand_to_variant(
         param.default = None;
     }
 
+    let generics_no_bounds = bounds::remove_bounds(generics.clone());
+
+    // Bounds on the types of merged fields aren't needed for the marker, and would make it
+    // recursive for types that contain themselves.
+    let dictionary_impl = is_dictionary_repr(&repr).then(|| {
+        let where_clause = &generics.where_clause;
+        quote! {
+            #derived
+            impl #generics ::gdnative::core_types::ToVariantDictionary for #ident #generics_no_bounds #where_clause {}
+        }
+    });
+
+    for ty in merged_field_types(&repr) {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote_spanned! {ty.span()=>
+                #ty: ::gdnative::core_types::ToVariantDictionary
+            });
+    }
+
     let return_expr = match repr {
         Repr::Struct(StructRepr(var_repr)) => {
             let destructure_pattern = var_repr.destructure_pattern();
@@ -40,6 +62,7 @@ pub(crate) fn expand_to_variant(
             variants,
             primitive_repr,
             kind,
+            names,
         }) => {
             if variants.is_empty() {
                 quote! {
@@ -50,11 +73,11 @@ pub(crate) fn expand_to_variant(
                     EnumReprKind::External => {
                         let match_arms = variants
                             .iter()
-                            .map(|(var_ident, var_repr)| {
+                            .zip(&names)
+                            .map(|((var_ident, var_repr), var_name)| {
                                 let destructure_pattern = var_repr.destructure_pattern();
                                 let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
-                                let var_ident_string = format!("{var_ident}");
-                                let var_ident_string_literal = Literal::string(&var_ident_string);
+                                let var_ident_string_literal = Literal::string(var_name);
                                 let tokens = quote! {
                                     #ident::#var_ident #destructure_pattern => {
                                         let __dict = ::gdnative::core_types::Dictionary::new();
@@ -76,16 +99,141 @@ pub(crate) fn expand_to_variant(
                             }
                         }
                     }
-                    EnumReprKind::Str => {
+                    EnumReprKind::Internal { tag } => {
+                        let match_arms = variants
+                            .iter()
+                            .zip(&names)
+                            .map(|((var_ident, var_repr), var_name)| {
+                                let destructure_pattern = var_repr.destructure_pattern();
+                                let var_ident_string_literal = Literal::string(var_name);
+
+                                let inserts = match var_repr {
+                                    VariantRepr::Unit(_) => quote! {},
+                                    VariantRepr::Struct(fields) => {
+                                        VariantRepr::make_dict_inserts(fields, trait_kind)
+                                    }
+                                    VariantRepr::Tuple(fields) if fields.len() == 1 => {
+                                        if let Some(path) = &fields[0].attr.to_variant_with {
+                                            return Err(syn::Error::new(
+                                                path.span(),
+                                                "`to_variant_with` cannot be used in newtype variants of internally tagged enums",
+                                            ));
+                                        }
+                                        let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
+                                        VariantRepr::make_dict_merge(&to_variant)
+                                    }
+                                    VariantRepr::Tuple(_) => {
+                                        return Err(syn::Error::new(
+                                            var_ident.span(),
+                                            "internally tagged enums cannot contain tuple variants",
+                                        ));
+                                    }
+                                };
+
+                                // The tag is inserted last, so that merged entries can't replace it.
+                                let tokens = quote! {
+                                    #ident::#var_ident #destructure_pattern => {
+                                        let __dict = ::gdnative::core_types::Dictionary::new();
+                                        #inserts
+                                        __dict.insert(
+                                            &::gdnative::core_types::GodotString::from(#tag).to_variant(),
+                                            &::gdnative::core_types::GodotString::from(#var_ident_string_literal).to_variant(),
+                                        );
+                                        ::gdnative::core_types::ToVariant::to_variant(&__dict.into_shared())
+                                    }
+                                };
+                                Ok(tokens)
+                            })
+                            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                        quote! {
+                            match #to_variant_receiver {
+                                #( #match_arms ),*
+                            }
+                        }
+                    }
+                    EnumReprKind::Adjacent { tag, content } => {
+                        let match_arms = variants
+                            .iter()
+                            .zip(&names)
+                            .map(|((var_ident, var_repr), var_name)| {
+                                let destructure_pattern = var_repr.destructure_pattern();
+                                let var_ident_string_literal = Literal::string(var_name);
+
+                                // Unit variants are represented by the tag alone.
+                                let content_insert = if matches!(var_repr, VariantRepr::Unit(_)) {
+                                    quote! {}
+                                } else {
+                                    let to_variant = var_repr.make_to_variant_expr(trait_kind)?;
+                                    quote! {
+                                        let __value = #to_variant;
+                                        __dict.insert(
+                                            &::gdnative::core_types::GodotString::from(#content).to_variant(),
+                                            &__value,
+                                        );
+                                    }
+                                };
+
+                                let tokens = quote! {
+                                    #ident::#var_ident #destructure_pattern => {
+                                        let __dict = ::gdnative::core_types::Dictionary::new();
+                                        __dict.insert(
+                                            &::gdnative::core_types::GodotString::from(#tag).to_variant(),
+                                            &::gdnative::core_types::GodotString::from(#var_ident_string_literal).to_variant(),
+                                        );
+                                        #content_insert
+                                        ::gdnative::core_types::ToVariant::to_variant(&__dict.into_shared())
+                                    }
+                                };
+                                Ok(tokens)
+                            })
+                            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                        quote! {
+                            match #to_variant_receiver {
+                                #( #match_arms ),*
+                            }
+                        }
+                    }
+                    EnumReprKind::Untagged => {
                         let match_arms = variants
                             .iter()
                             .map(|(var_ident, var_repr)| {
+                                let destructure_pattern = var_repr.destructure_pattern();
+
+                                // Unit variants are represented as `Nil`, since there is nothing
+                                // else to tell them apart from other variants.
+                                let to_variant = if matches!(var_repr, VariantRepr::Unit(_)) {
+                                    quote! { ::gdnative::core_types::Variant::nil() }
+                                } else {
+                                    var_repr.make_to_variant_expr(trait_kind)?
+                                };
+
+                                let tokens = quote! {
+                                    #ident::#var_ident #destructure_pattern => {
+                                        #to_variant
+                                    }
+                                };
+                                Ok(tokens)
+                            })
+                            .collect::<Result<Vec<_>, syn::Error>>()?;
+
+                        quote! {
+                            match #to_variant_receiver {
+                                #( #match_arms ),*
+                            }
+                        }
+                    }
+                    EnumReprKind::Str => {
+                        let match_arms = variants
+                            .iter()
+                            .zip(&names)
+                            .map(|((var_ident, var_repr), var_name)| {
                                 if !matches!(var_repr, VariantRepr::Unit(_)) {
                                     return Err(syn::Error::new(var_ident.span(), "`str` representation can only be used for fieldless enums"));
                                 }
 
-                                let var_ident_string = format!("{var_ident}");
-                                let var_ident_string_literal = Literal::string(&var_ident_string);
+                                let var_ident_string_literal = Literal::string(var_name);
                                 let tokens = quote! {
                                     #ident::#var_ident => {
                                         ::gdnative::core_types::ToVariant::to_variant(#var_ident_string_literal)
@@ -138,7 +286,6 @@ pub(crate) fn expand_to_variant(
         }
     };
 
-    let generics_no_bounds = bounds::remove_bounds(generics.clone());
     let where_clause = &generics.where_clause;
 
     let result = quote! {
@@ -151,7 +298,50 @@ pub(crate) fn expand_to_variant(
                 #return_expr
             }
         }
+
+        #dictionary_impl
     };
 
     Ok(result)
 }
+
+/// Returns whether the type is always represented as a `Dictionary`.
+fn is_dictionary_repr(repr: &Repr) -> bool {
+    match repr {
+        Repr::Struct(StructRepr(var_repr)) => !matches!(var_repr, VariantRepr::Tuple(_)),
+        Repr::Enum(EnumRepr { kind, .. }) => matches!(
+            kind,
+            EnumReprKind::External | EnumReprKind::Internal { .. } | EnumReprKind::Adjacent { .. }
+        ),
+    }
+}
+
+/// Returns the types of the fields whose entries are merged into the `Dictionary` of their
+/// parent, which must implement `ToVariantDictionary`: flattened fields, and the values of
+/// newtype variants in internally tagged enums.
+fn merged_field_types(repr: &Repr) -> Vec<syn::Type> {
+    let (var_reprs, internal) = match repr {
+        Repr::Struct(StructRepr(var_repr)) => (vec![var_repr], false),
+        Repr::Enum(EnumRepr { kind, variants, .. }) => (
+            variants.iter().map(|(_, var_repr)| var_repr).collect(),
+            matches!(kind, EnumReprKind::Internal { .. }),
+        ),
+    };
+
+    let mut types = Vec::new();
+    for var_repr in var_reprs {
+        match var_repr {
+            VariantRepr::Struct(fields) => types.extend(
+                fields
+                    .iter()
+                    .filter(|f| f.attr.flatten && !f.attr.skip_to_variant)
+                    .map(|f| f.ty.clone()),
+            ),
+            VariantRepr::Tuple(fields) if internal && fields.len() == 1 => {
+                types.push(fields[0].ty.clone());
+            }
+            _ => {}
+        }
+    }
+    types
+}
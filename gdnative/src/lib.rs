@@ -61,6 +61,12 @@
 //!   Please refer to [the `rust-ctor` README][ctor-repo] for an up-to-date listing of platforms
 //!   that *do* support automatic registration.
 //!
+//! * **`debug-checks`**<br>
+//!   Makes `assume_safe` on manually-managed objects verify in debug builds that the object is
+//!   still alive, and that nodes inside the scene tree are only accessed from the main thread.
+//!   Violations panic with a message instead of causing undefined behavior. Has no effect on
+//!   release builds. See also [`Ref::try_access`](object::Ref::try_access).
+//!
 //! Bindings generation:
 //!
 //! * **`custom-godot`**<br>
@@ -104,7 +110,7 @@
 #[doc(inline)]
 pub use gdnative_core::{
     core_types, derive, export, godot_dbg, godot_error, godot_print, godot_site, init, log, object,
-    profiler,
+    profiler, string_name,
 };
 
 pub mod globalscope;
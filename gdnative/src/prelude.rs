@@ -6,11 +6,11 @@ pub use gdnative_bindings::{
 };
 pub use gdnative_core::core_types::{
     Aabb, Basis, Color, Dictionary, GodotError, GodotString, NodePath, Plane, PoolArray, Quat,
-    Rect2, Rid, StringName, Transform, Transform2D, Variant, VariantArray, VariantDispatch,
-    VariantOperator, VariantType, Vector2, Vector3,
+    Rect2, Rid, StringName, Transform, Transform2D, TypedArray, TypedDictionary, Variant,
+    VariantArray, VariantDispatch, VariantOperator, VariantType, Vector2, Vector3,
 };
 pub use gdnative_core::core_types::{
-    FromVariant, FromVariantError, OwnedToVariant, ToVariant, ToVariantEq,
+    FromVariant, FromVariantError, OwnedToVariant, ToVariant, ToVariantDictionary, ToVariantEq,
 };
 pub use gdnative_core::export::{
     ClassBuilder, ExportInfo, Method, MethodBuilder, NativeClass, NativeClassMethods, Property,
@@ -23,7 +23,7 @@ pub use gdnative_core::object::{
     AsArg, GodotObject, Instance, Instanciable, NewRef, Null, QueueFree, Ref, SubClass, TInstance,
     TRef,
 };
-pub use gdnative_core::{godot_dbg, godot_error, godot_print, godot_warn};
+pub use gdnative_core::{godot_dbg, godot_error, godot_print, godot_warn, string_name};
 #[allow(deprecated)]
 pub use gdnative_core::{
     godot_gdnative_init, godot_gdnative_terminate, godot_init, godot_nativescript_init,
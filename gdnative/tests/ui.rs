@@ -29,6 +29,9 @@ fn ui_tests() {
     t.compile_fail("tests/ui/to_variant_fail_07.rs");
     t.compile_fail("tests/ui/to_variant_fail_08.rs");
     t.compile_fail("tests/ui/to_variant_fail_09.rs");
+    t.compile_fail("tests/ui/to_variant_fail_10.rs");
+    t.compile_fail("tests/ui/to_variant_fail_11.rs");
+    t.compile_fail("tests/ui/to_variant_fail_12.rs");
 
     // FromVariant
     t.compile_fail("tests/ui/from_variant_fail_01.rs");
@@ -39,6 +42,7 @@ fn ui_tests() {
     t.compile_fail("tests/ui/from_variant_fail_07.rs");
     t.compile_fail("tests/ui/from_variant_fail_08.rs");
     t.compile_fail("tests/ui/from_variant_fail_09.rs");
+    t.compile_fail("tests/ui/from_variant_fail_10.rs");
 }
 
 // FIXME(rust/issues/54725): Full path spans are only available on nightly as of now
@@ -0,0 +1,14 @@
+use gdnative::prelude::*;
+
+#[derive(FromVariant)]
+pub struct Foo {
+    #[variant(flatten, default)]
+    bar: Bar,
+}
+
+#[derive(Default, FromVariant)]
+pub struct Bar {
+    baz: i64,
+}
+
+fn main() {}
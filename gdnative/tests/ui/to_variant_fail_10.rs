@@ -0,0 +1,9 @@
+use gdnative::prelude::*;
+
+#[derive(ToVariant)]
+#[variant(tag = "kind")]
+pub enum Foo {
+    Bar { kind: String },
+}
+
+fn main() {}
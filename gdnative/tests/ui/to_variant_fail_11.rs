@@ -0,0 +1,13 @@
+use gdnative::prelude::*;
+
+#[derive(ToVariant)]
+#[variant(tag = "type")]
+pub enum Foo {
+    Count(#[variant(to_variant_with = "to_dict")] i64),
+}
+
+fn to_dict(value: &i64) -> Variant {
+    value.to_variant()
+}
+
+fn main() {}
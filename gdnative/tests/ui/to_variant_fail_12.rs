@@ -0,0 +1,13 @@
+use gdnative::prelude::*;
+
+#[derive(ToVariant)]
+pub struct Foo {
+    #[variant(flatten, to_variant_with = "to_dict")]
+    bar: i64,
+}
+
+fn to_dict(value: &i64) -> Variant {
+    value.to_variant()
+}
+
+fn main() {}
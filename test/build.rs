@@ -0,0 +1,5 @@
+fn main() {
+    gdnative_build::ProjectBindings::new("project")
+        .write_to_out_dir("project.rs")
+        .expect("project.godot should be valid");
+}
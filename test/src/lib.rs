@@ -11,6 +11,7 @@ mod test_derive;
 mod test_free_ub;
 mod test_generic_class;
 mod test_indexed_props;
+mod test_input_action;
 mod test_map_owned;
 mod test_register;
 mod test_return_leak;
@@ -26,17 +27,26 @@ pub extern "C" fn run_tests(
 ) -> gdnative::sys::godot_variant {
     let mut status = true;
     status &= gdnative::core_types::test_string();
+    status &= gdnative::core_types::test_string_manipulation();
     status &= gdnative::core_types::test_string_name_eq();
     status &= gdnative::core_types::test_string_name_ord();
+    status &= gdnative::core_types::test_string_name_macro();
 
     status &= gdnative::core_types::test_dictionary();
     status &= gdnative::core_types::test_dictionary_clone_clear();
+    status &= gdnative::core_types::test_dictionary_entry();
+    status &= gdnative::core_types::test_dictionary_duplicate_deep();
     status &= gdnative::core_types::test_color();
     status &= gdnative::core_types::test_array();
     status &= gdnative::core_types::test_array_debug();
     status &= gdnative::core_types::test_array_clone_clear();
+    status &= gdnative::core_types::test_array_ext();
+    status &= gdnative::core_types::test_typed_array();
+    status &= gdnative::core_types::test_typed_dictionary();
+    status &= gdnative::core_types::test_node_path();
 
     status &= gdnative::core_types::test_variant_nil();
+    status &= gdnative::core_types::test_variant_key();
     status &= gdnative::core_types::test_variant_i64();
     status &= gdnative::core_types::test_variant_bool();
 
@@ -49,9 +59,13 @@ pub extern "C" fn run_tests(
     status &= gdnative::core_types::test_variant_hash_map();
     status &= gdnative::core_types::test_variant_hash_set();
     status &= gdnative::core_types::test_variant_vec();
+    status &= gdnative::core_types::test_variant_std_types();
+    status &= gdnative::core_types::test_variant_index_map();
     status &= gdnative::core_types::test_to_variant_iter();
     status &= gdnative::core_types::test_variant_tuple();
     status &= gdnative::core_types::test_variant_dispatch();
+    status &= gdnative::core_types::test_variant_ref();
+    status &= gdnative::core_types::test_variant_value();
 
     status &= gdnative::core_types::test_byte_array_access();
     status &= gdnative::core_types::test_int32_array_access();
@@ -60,6 +74,10 @@ pub extern "C" fn run_tests(
     status &= gdnative::core_types::test_string_array_access();
     status &= gdnative::core_types::test_vector2_array_access();
     status &= gdnative::core_types::test_vector3_array_access();
+    status &= gdnative::core_types::test_pool_array_flat_access();
+    status &= gdnative::core_types::test_pool_array_make_unique();
+    status &= gdnative::core_types::test_byte_array_compression();
+    status &= gdnative::core_types::test_byte_array_hashing();
     status &= gdnative::core_types::test_transform2d_behavior();
 
     status &= test_from_instance_id();
@@ -74,6 +92,7 @@ pub extern "C" fn run_tests(
     status &= test_free_ub::run_tests();
     status &= test_generic_class::run_tests();
     status &= test_indexed_props::run_tests();
+    status &= test_input_action::run_tests();
     status &= test_map_owned::run_tests();
     status &= test_register::run_tests();
     status &= test_return_leak::run_tests();
@@ -2,8 +2,10 @@ use std::cell::{self, Cell, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
 
+use gdnative::api;
 use gdnative::export::Property;
 use gdnative::prelude::*;
+use indexmap::{IndexMap, IndexSet};
 
 pub(crate) fn run_tests() -> bool {
     let mut status = true;
@@ -11,7 +13,10 @@ pub(crate) fn run_tests() -> bool {
     status &= test_derive_to_variant();
     status &= test_derive_to_variant_repr();
     status &= test_derive_to_variant_str();
+    status &= test_derive_to_variant_tagged();
+    status &= test_derive_to_variant_rename();
     status &= test_derive_owned_to_variant();
+    status &= test_derive_to_variant_index_map();
     status &= test_derive_nativeclass();
     status &= test_derive_nativeclass_without_constructor();
     status &= test_derive_nativeclass_without_inherit();
@@ -22,6 +27,7 @@ pub(crate) fn run_tests() -> bool {
     status &= test_derive_nativeclass_godot_attr_all_arguments();
     status &= test_derive_nativeclass_with_property_get_set();
     status &= test_derive_nativeclass_property_with_only_getter();
+    status &= test_derive_scene_nodes();
 
     status
 }
@@ -271,6 +277,190 @@ crate::godot_itest! { test_derive_to_variant_str {
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
+crate::godot_itest! { test_derive_to_variant_tagged {
+    #[derive(Clone, Eq, PartialEq, Debug, ToVariant, FromVariant)]
+    struct Payload {
+        amount: i64,
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(tag = "type")]
+    enum Internal {
+        Hit { damage: i64 },
+        Heal(Payload),
+        Extra(std::collections::HashMap<String, i64>),
+        Miss,
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(tag = "t", content = "c")]
+    enum Adjacent {
+        Pair(i64, bool),
+        Single(String),
+        Empty,
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(untagged)]
+    enum Untagged {
+        Int(i64),
+        Named { name: String },
+        Nothing,
+    }
+
+    let variant = Internal::Hit { damage: 3 }.to_variant();
+    let dict = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(2, dict.len());
+    assert_eq!(Some("Hit".into()), dict.get("type").and_then(|v| v.to::<String>()));
+    assert_eq!(Some(3), dict.get("damage").and_then(|v| v.to::<i64>()));
+
+    let variant = Internal::Heal(Payload { amount: 5 }).to_variant();
+    let dict = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(Some("Heal".into()), dict.get("type").and_then(|v| v.to::<String>()));
+    assert_eq!(Some(5), dict.get("amount").and_then(|v| v.to::<i64>()));
+
+    for value in [
+        Internal::Hit { damage: 3 },
+        Internal::Heal(Payload { amount: 5 }),
+        Internal::Miss,
+    ] {
+        assert_eq!(Ok(value.clone()), Internal::from_variant(&value.to_variant()));
+    }
+
+    // The tag isn't passed on to newtype values, and can't be replaced by their entries.
+    let extra = Internal::Extra([("crits".to_owned(), 2)].into_iter().collect());
+    assert_eq!(Ok(extra.clone()), Internal::from_variant(&extra.to_variant()));
+
+    let variant = Internal::Extra([("type".to_owned(), 2)].into_iter().collect()).to_variant();
+    let dict = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(1, dict.len());
+    assert_eq!(Some("Extra".into()), dict.get("type").and_then(|v| v.to::<String>()));
+    assert_eq!(
+        Ok(Internal::Extra(Default::default())),
+        Internal::from_variant(&variant)
+    );
+
+    let unknown = Dictionary::new();
+    unknown.insert("type", "Crit");
+    assert_eq!(
+        Err(FromVariantError::UnknownEnumVariant {
+            variant: "Crit".into(),
+            expected: &["Hit", "Heal", "Extra", "Miss"],
+        }),
+        Internal::from_variant(&unknown.into_shared().to_variant())
+    );
+
+    let variant = Adjacent::Pair(1, true).to_variant();
+    let dict = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(Some("Pair".into()), dict.get("t").and_then(|v| v.to::<String>()));
+    let content = dict.get("c").and_then(|v| v.to::<VariantArray>()).expect("should be array");
+    assert_eq!(2, content.len());
+
+    let variant = Adjacent::Empty.to_variant();
+    let dict = variant.to::<Dictionary>().expect("should be dictionary");
+    assert!(!dict.contains("c"));
+
+    for value in [
+        Adjacent::Pair(1, true),
+        Adjacent::Single("foo".into()),
+        Adjacent::Empty,
+    ] {
+        assert_eq!(Ok(value.clone()), Adjacent::from_variant(&value.to_variant()));
+    }
+
+    assert_eq!(Some(42), Untagged::Int(42).to_variant().to::<i64>());
+    assert!(Untagged::Nothing.to_variant().is_nil());
+
+    for value in [
+        Untagged::Int(42),
+        Untagged::Named { name: "foo".into() },
+        Untagged::Nothing,
+    ] {
+        assert_eq!(Ok(value.clone()), Untagged::from_variant(&value.to_variant()));
+    }
+
+    match Untagged::from_variant(&Variant::new(true)) {
+        Err(FromVariantError::NoMatchingEnumVariant { errors }) => {
+            let tried = errors.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+            assert_eq!(&["Int", "Named", "Nothing"], tried.as_slice());
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
+crate::godot_itest! { test_derive_to_variant_rename {
+    fn default_level() -> i64 {
+        1
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(rename_all = "camelCase")]
+    struct Stats {
+        max_health: i64,
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(rename_all = "camelCase")]
+    struct Player {
+        player_name: String,
+        #[variant(rename = "HP")]
+        hit_points: i64,
+        #[variant(default)]
+        lives: i64,
+        #[variant(default = "default_level")]
+        level: i64,
+        #[variant(flatten)]
+        stats: Stats,
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug, ToVariant, FromVariant)]
+    #[variant(enum = "str", rename_all = "snake_case")]
+    enum State {
+        Idle,
+        WallRunning,
+    }
+
+    let player = Player {
+        player_name: "foo".into(),
+        hit_points: 3,
+        lives: 2,
+        level: 4,
+        stats: Stats { max_health: 5 },
+    };
+
+    let variant = player.to_variant();
+    let dict = variant.to::<Dictionary>().expect("should be dictionary");
+    assert_eq!(5, dict.len());
+    assert_eq!(Some("foo".into()), dict.get("playerName").and_then(|v| v.to::<String>()));
+    assert_eq!(Some(3), dict.get("HP").and_then(|v| v.to::<i64>()));
+    assert_eq!(Some(5), dict.get("maxHealth").and_then(|v| v.to::<i64>()));
+    assert!(!dict.contains("stats"));
+    assert_eq!(Ok(player), Player::from_variant(&variant));
+
+    let partial = Dictionary::new();
+    partial.insert("playerName", "bar");
+    partial.insert("HP", 1);
+    partial.insert("maxHealth", 2);
+    assert_eq!(
+        Ok(Player {
+            player_name: "bar".into(),
+            hit_points: 1,
+            lives: 0,
+            level: 1,
+            stats: Stats { max_health: 2 },
+        }),
+        Player::from_variant(&partial.into_shared().to_variant())
+    );
+
+    assert_eq!(Some("wall_running"), State::WallRunning.to_variant().to::<String>().as_deref());
+    assert_eq!(Some(State::Idle), Variant::new("idle").to::<State>());
+    assert_eq!(None, Variant::new("Idle").to::<State>());
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
 crate::godot_itest! { test_derive_owned_to_variant {
     #[derive(OwnedToVariant)]
     struct ToVar {
@@ -300,6 +490,42 @@ crate::godot_itest! { test_derive_owned_to_variant {
 
 // ----------------------------------------------------------------------------------------------------------------------------------------------
 
+crate::godot_itest! { test_derive_to_variant_index_map {
+    #[derive(Clone, Eq, PartialEq, Debug, ToVariant, FromVariant)]
+    struct Config {
+        layers: IndexMap<String, i64>,
+        tags: IndexSet<String>,
+    }
+
+    let config = Config {
+        layers: [("world", 3), ("player", 1), ("enemies", 2)]
+            .into_iter()
+            .map(|(name, bit)| (name.to_string(), bit))
+            .collect(),
+        tags: ["zombie", "boss", "flying"]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+    };
+
+    let variant = config.to_variant();
+    let dictionary = variant.to::<Dictionary>().expect("should be dictionary");
+    let layers = dictionary
+        .get("layers")
+        .and_then(|v| v.to::<Dictionary>())
+        .expect("should be dictionary");
+    assert_eq!(
+        Some(vec!["world".to_string(), "player".to_string(), "enemies".to_string()]),
+        layers.keys().owned_to_variant().to::<Vec<String>>(),
+    );
+
+    let round_trip = Config::from_variant(&variant).expect("should succeed");
+    assert!(config.layers.iter().eq(round_trip.layers.iter()));
+    assert!(config.tags.iter().eq(round_trip.tags.iter()));
+}}
+
+// ----------------------------------------------------------------------------------------------------------------------------------------------
+
 #[derive(NativeClass)]
 #[inherit(Reference)]
 struct MinimalDerive(i64);
@@ -602,3 +828,40 @@ crate::godot_itest! { test_derive_nativeclass_property_with_only_getter {
     let _ = std::panic::catch_unwind(|| owner.set("size", 3));
     assert_eq!(u32::from_variant(&owner.get("size")).unwrap(), 1);
 }}
+
+#[derive(SceneNodes)]
+#[scene("res://SceneNodes.tscn", project = "project")]
+struct SceneNodesDerive;
+
+crate::godot_itest! { test_derive_scene_nodes {
+    let scene = load::<PackedScene>("res://SceneNodes.tscn").expect("scene should load");
+    let scene = unsafe { scene.assume_safe() };
+    let root = scene
+        .instance(PackedScene::GEN_EDIT_STATE_DISABLED)
+        .expect("scene should instance");
+
+    {
+        let base = unsafe { root.assume_safe() };
+        let nodes = SceneNodesDerive;
+        unsafe {
+            let camera: TRef<api::Camera2D> = nodes.camera(base);
+            assert!(camera.is_current());
+
+            let label: TRef<Label> = nodes.score_label(base);
+            assert_eq!("0", label.text().to_string());
+
+            let sprite: TRef<Sprite> = nodes.sprite(base);
+            let ui_sprite: TRef<Sprite> = nodes.ui_sprite(base);
+            assert_ne!(sprite.get_instance_id(), ui_sprite.get_instance_id());
+
+            let ui: TRef<CanvasLayer> = nodes.ui(base);
+            let ui_sprite_parent = ui_sprite.get_parent().unwrap().assume_safe();
+            assert_eq!(ui.get_instance_id(), ui_sprite_parent.get_instance_id());
+
+            let instanced: TRef<Node> = nodes.instanced(base);
+            assert_eq!("Instanced", instanced.name().to_string());
+        }
+    }
+
+    unsafe { root.assume_unique().free() };
+}}
@@ -6,6 +6,11 @@ pub(crate) fn run_tests() -> bool {
     let mut status = true;
 
     status &= test_owner_free_ub();
+    status &= test_try_access_freed();
+    #[cfg(all(feature = "debug-checks", debug_assertions))]
+    {
+        status &= test_assume_safe_freed_panics();
+    }
 
     status
 }
@@ -73,3 +78,36 @@ crate::godot_itest! { test_owner_free_ub {
     // the values are eventually dropped
     assert_eq!(2, drop_counter.load(AtomicOrdering::Acquire));
 }}
+
+crate::godot_itest! { test_try_access_freed {
+    let node = Node::new().into_shared();
+    let copy = node;
+    assert!(unsafe { node.try_access() }.is_some());
+
+    unsafe { node.assume_unique().free() };
+    assert!(unsafe { copy.try_access() }.is_none());
+
+    let drop_counter = Arc::new(AtomicUsize::new(0));
+    let bar = Bar(42, Arc::clone(&drop_counter)).emplace().into_shared();
+    let value = unsafe { bar.try_access() }.map(|bar| bar.map(|bar, _| bar.0).unwrap());
+    assert_eq!(Some(42), value);
+
+    let base = *bar.base();
+    unsafe { base.assume_unique().free() };
+    assert!(unsafe { bar.try_access() }.is_none());
+    assert_eq!(1, drop_counter.load(AtomicOrdering::Acquire));
+
+    let reference = Reference::new().into_shared();
+    assert!(unsafe { reference.try_access() }.is_some());
+}}
+
+#[cfg(all(feature = "debug-checks", debug_assertions))]
+crate::godot_itest! { test_assume_safe_freed_panics {
+    let node = Node::new().into_shared();
+    unsafe { node.assume_unique().free() };
+
+    let result = std::panic::catch_unwind(|| {
+        unsafe { node.assume_safe() };
+    });
+    assert!(result.is_err(), "assume_safe should panic on a freed object");
+}}
@@ -0,0 +1,61 @@
+use gdnative::api::{InputEventAction, InputMap};
+use gdnative::prelude::*;
+
+include!(concat!(env!("OUT_DIR"), "/project.rs"));
+
+pub(crate) fn run_tests() -> bool {
+    let mut status = true;
+
+    status &= test_input_action_names();
+    status &= test_input_action_ext();
+    status &= test_input_event_action_ext();
+
+    status
+}
+
+crate::godot_itest! { test_input_action_names {
+    assert_eq!("test_jump", Action::TestJump.as_str());
+    assert_eq!("ui_accept", Action::UiAccept.to_string());
+    assert_eq!(Some(Action::TestJump), Action::from_name("test_jump"));
+    assert_eq!(None, Action::from_name("test_jmup"));
+
+    let name = Action::TestJump.string_name();
+    assert!(std::ptr::eq(name, Action::TestJump.string_name()));
+    assert_eq!("test_jump", name.to_godot_string().to_string());
+
+    let input_map = InputMap::godot_singleton();
+    for &action in Action::ALL {
+        assert!(input_map.has_action(action), "{} should exist", action);
+    }
+}}
+
+crate::godot_itest! { test_input_action_ext {
+    let input = Input::godot_singleton();
+    assert!(!input.is_pressed(Action::TestJump));
+
+    input.press(Action::TestJump);
+    assert!(input.is_pressed(Action::TestJump));
+    assert!(input.is_action_pressed(Action::TestJump, false));
+    approx::assert_relative_eq!(1.0, input.strength(Action::TestJump));
+
+    input.release(Action::TestJump);
+    assert!(!input.is_pressed(Action::TestJump));
+    approx::assert_relative_eq!(0.0, input.strength(Action::TestJump));
+}}
+
+crate::godot_itest! { test_input_event_action_ext {
+    let event = InputEventAction::new();
+    event.set_action(Action::TestJump);
+    event.set_pressed(true);
+
+    assert_eq!(Some(Action::TestJump), event.matching_action());
+    assert_eq!(Some(Action::TestJump), event.pressed_action());
+    assert_eq!(None, event.released_action::<Action>());
+
+    event.set_pressed(false);
+    assert_eq!(Some(Action::TestJump), event.released_action());
+    assert_eq!(None, event.pressed_action::<Action>());
+
+    event.set_action(Action::UiCancel);
+    assert_eq!(Some(Action::UiCancel), event.matching_action());
+}}
@@ -8,6 +8,7 @@ pub(crate) fn run_tests() -> bool {
     let mut status = true;
 
     status &= test_register_property();
+    status &= test_register_typed_signals();
     status &= test_advanced_methods();
     status &= test_varargs_gets();
     status &= test_varargs_to_tuple();
@@ -19,6 +20,8 @@ pub(crate) fn run_tests() -> bool {
 pub(crate) fn register(handle: InitHandle) {
     handle.add_class::<RegisterSignal>();
     handle.add_class::<RegisterProperty>();
+    handle.add_class::<TypedSignals>();
+    handle.add_class::<TypedSignalReceiver>();
     handle.add_class::<AdvancedMethods>();
     handle.add_class::<VarargsGets>();
     handle.add_class::<VarargsToTuple>();
@@ -104,6 +107,107 @@ crate::godot_itest! { test_register_property {
     assert_eq!(Some(4242), unsafe { base.call("get_value", &[]).to() });
 }}
 
+#[derive(NativeClass)]
+#[inherit(Reference)]
+#[no_constructor]
+struct TypedSignals;
+
+#[methods]
+impl TypedSignals {
+    /// Emitted with the amount of damage and its source.
+    #[signal]
+    fn hit(damage: i64, source: GodotString);
+
+    #[signal]
+    fn reset();
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct TypedSignalReceiver {
+    hits: Vec<(i64, String)>,
+    resets: u32,
+}
+
+#[methods]
+impl TypedSignalReceiver {
+    fn new(_base: &Reference) -> Self {
+        TypedSignalReceiver {
+            hits: Vec::new(),
+            resets: 0,
+        }
+    }
+
+    #[method]
+    fn on_hit(&mut self, damage: i64, source: String) {
+        self.hits.push((damage, source));
+    }
+
+    #[method]
+    fn on_reset(&mut self) {
+        self.resets += 1;
+    }
+}
+
+crate::godot_itest! { test_register_typed_signals {
+    let emitter = Instance::emplace(TypedSignals).into_shared();
+    let emitter = unsafe { emitter.assume_safe() };
+    let receiver = TypedSignalReceiver::new_instance().into_shared();
+    let receiver = unsafe { receiver.assume_safe() };
+
+    let hit = emitter
+        .base()
+        .get_signal_list()
+        .iter()
+        .filter_map(|signal| signal.to::<Dictionary>())
+        .find(|signal| {
+            let name = signal.get("name").and_then(|name| name.to::<String>());
+            name.as_deref() == Some("hit")
+        })
+        .expect("signal should be registered");
+    let args = hit
+        .get("args")
+        .and_then(|args| args.to::<VariantArray>())
+        .expect("signal should have arguments")
+        .iter()
+        .map(|arg| {
+            let arg = arg.to::<Dictionary>().unwrap();
+            (
+                arg.get("name").and_then(|name| name.to::<String>()).unwrap(),
+                arg.get("type").and_then(|ty| ty.to::<i64>()).unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            ("damage".to_owned(), VariantType::I64 as i64),
+            ("source".to_owned(), VariantType::GodotString as i64),
+        ],
+        args
+    );
+
+    emitter
+        .map(|signals, base| {
+            signals.connect_hit(&base, receiver.base(), "on_hit", 0).unwrap();
+            signals.connect_reset(&base, receiver.base(), "on_reset", 0).unwrap();
+            assert_eq!(
+                Err(GodotError::MethodNotFound),
+                signals.connect_reset(&base, receiver.base(), "on_missing", 0),
+            );
+
+            signals.emit_hit(&base, 42, "spikes".into());
+            signals.emit_hit(&base, 7, "lava".into());
+            signals.emit_reset(&base);
+        })
+        .unwrap();
+
+    let (hits, resets) = receiver
+        .map(|receiver, _| (receiver.hits.clone(), receiver.resets))
+        .unwrap();
+    assert_eq!(vec![(42, "spikes".to_owned()), (7, "lava".to_owned())], hits);
+    assert_eq!(1, resets);
+}}
+
 #[derive(NativeClass)]
 #[inherit(Reference)]
 #[register_with(register_methods)]
@@ -13,6 +13,7 @@ pub(crate) fn run_tests() -> bool {
         return false;
     }
 
+    status &= test_variant_serializer();
     status &= test_ron();
     status &= test_json();
     status &= test_yaml();
@@ -128,6 +129,59 @@ crate::godot_itest! { test_dispatch_eq {
     assert_eq!(foo, result);
 }}
 
+crate::godot_itest! { test_variant_serializer {
+    use gdnative::core_types;
+
+    let foo = Foo::new();
+
+    let variant = core_types::to_variant(&foo).expect("Foo to Variant");
+    let dict = variant.to::<Dictionary>().expect("Foo is serialized as a Dictionary");
+    let vec2 = dict.get("vec2").expect("vec2 is present");
+    assert_eq!(VariantType::Dictionary, vec2.get_type());
+    assert_eq!(Ok(foo.vec2), core_types::from_variant::<Vector2>(&vec2));
+    let xform = dict.get("xform").expect("xform is present");
+    assert_eq!(Ok(foo.xform), core_types::from_variant::<Transform>(&xform));
+    assert!(dict.get("none").expect("none is present").is_nil());
+
+    let result = core_types::from_variant::<Foo>(&variant).expect("Foo from Variant");
+    assert_eq!(foo, result);
+
+    let result = core_types::from_variant::<Foo>(&foo.to_variant()).expect("Foo from ToVariant");
+    assert_eq!(foo, result);
+
+    let disp = core_types::from_variant::<VariantDispatch>(&foo.to_variant())
+        .expect("Dispatch from Variant");
+    let disp_variant = core_types::to_variant(&disp).expect("Dispatch to Variant");
+    let result = Foo::from_variant(&disp_variant).expect("Foo from Dispatch from Variant");
+    assert_eq!(foo, result);
+
+    // User types with the names of Godot's types are serialized like any other. They are declared
+    // in a separate block to not shadow Godot's types in the rest of the test.
+    {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Vector2 {
+            x: f64,
+            y: f64,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        enum VariantDispatch {
+            Vector2(Vector2),
+        }
+
+        let v = Vector2 { x: 0.1, y: 1e300 };
+        let variant = core_types::to_variant(&v).expect("Vector2 to Variant");
+        assert_eq!(VariantType::Dictionary, variant.get_type());
+        assert_eq!(Ok(v), core_types::from_variant::<Vector2>(&variant));
+
+        let disp = VariantDispatch::Vector2(Vector2 { x: 0.1, y: 0.2 });
+        let variant = core_types::to_variant(&disp).expect("VariantDispatch to Variant");
+        let dict = variant.to::<Dictionary>().expect("enum is serialized as a Dictionary");
+        assert!(dict.contains("Vector2"));
+        assert_eq!(Ok(disp), core_types::from_variant::<VariantDispatch>(&variant));
+    }
+}}
+
 crate::godot_itest! { test_ron {
     let foo = Foo::new();
 
@@ -5,6 +5,7 @@ pub(crate) fn run_tests() -> bool {
     let mut status = true;
 
     status &= test_variant_ops();
+    status &= test_variant_checked_ops();
 
     status
 }
@@ -35,3 +36,36 @@ crate::godot_itest! { test_variant_ops {
             .evaluate(VariantOperator::Multiply, &"bar".to_variant()),
     );
 }}
+
+crate::godot_itest! { test_variant_checked_ops {
+    let arr = VariantArray::new();
+    arr.push(&"bar".to_variant());
+    let arr = arr.into_shared().to_variant();
+
+    assert_eq!(Ok(5.to_variant()), 2.to_variant().checked_add(&3.to_variant()));
+    assert_eq!(Ok(2.5.to_variant()), 5.0.to_variant().checked_div(&2.to_variant()));
+    assert_eq!(Ok(1.to_variant()), 7.to_variant().checked_rem(&3.to_variant()));
+    assert_eq!(Ok((-4).to_variant()), 4.to_variant().checked_neg());
+    assert_eq!(Err(InvalidOp), 1.to_variant().checked_div(&0.to_variant()));
+    assert_eq!(Err(InvalidOp), "foo".to_variant().checked_sub(&1.to_variant()));
+
+    assert_eq!(Ok(true), 1.to_variant().checked_lt(&1.5.to_variant()));
+    assert_eq!(Ok(true), 2.to_variant().checked_ge(&2.to_variant()));
+    assert_eq!(Ok(false), "a".to_variant().checked_gt(&"b".to_variant()));
+    assert_eq!(Err(InvalidOp), "a".to_variant().checked_le(&1.to_variant()));
+
+    assert_eq!(Ok(true), "bar".to_variant().in_(&arr));
+    assert_eq!(Ok(false), "foo".to_variant().in_(&arr));
+    assert_eq!(Ok(true), "oo".to_variant().in_(&"foo".to_variant()));
+
+    let a = 6.to_variant();
+    let b = 7.to_variant();
+    assert_eq!(42.to_variant(), &a * &b);
+    assert_eq!(13.to_variant(), a.clone() + b.clone());
+    assert_eq!((-1).to_variant(), &a - &b);
+    assert_eq!((-6).to_variant(), -a);
+    assert_eq!("foobar".to_variant(), "foo".to_variant() + "bar".to_variant());
+
+    let result = std::panic::catch_unwind(|| "foo".to_variant() * "bar".to_variant());
+    assert!(result.is_err());
+}}